@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    fn new(prefix: &str) -> Self {
+        let mut dir = std::env::temp_dir();
+        let unique = format!(
+            "{prefix}-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        );
+        dir.push(unique);
+        fs::create_dir_all(&dir).expect("create temp dir");
+        Self { path: dir }
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+fn write_file(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent dirs");
+    }
+    fs::write(path, contents).expect("write file");
+}
+
+fn callgraph(dir: &Path, jobs: &str) -> serde_json::Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_dwg-cli"))
+        .current_dir(dir)
+        .args(["flow", "callgraph", "--jobs", jobs, "src"])
+        .output()
+        .expect("run dwg-cli");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("callgraph json")
+}
+
+#[test]
+fn flow_callgraph_honours_jobs() {
+    let temp = TempDir::new("toneguard-callgraph-jobs");
+    let root = &temp.path;
+    write_file(
+        &root.join("src/lib.rs"),
+        "pub fn run() {\n    helper();\n}\n\nfn helper() {}\n",
+    );
+    write_file(
+        &root.join("src/other.rs"),
+        "pub fn start() {\n    crate::run();\n}\n",
+    );
+
+    let single = callgraph(root, "1");
+    assert_eq!(single, callgraph(root, "4"));
+    let nodes = single["nodes"].as_array().expect("nodes array");
+    assert!(nodes.len() >= 3, "{single:#}");
+}
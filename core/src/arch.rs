@@ -4,9 +4,12 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use globset::{Glob, GlobSet, GlobSetBuilder};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
+use crate::cache::FileCache;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Language {
@@ -158,7 +161,7 @@ impl Default for FlowAuditConfig {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct DupSignature {
     language: Language,
     normalized: String,
@@ -182,7 +185,62 @@ struct DupGroup {
     occurrences: Vec<DupOccurrence>,
 }
 
+/// Per-file facts feeding the cross-file passes; this is what the audit
+/// cache stores.
+#[derive(Serialize, Deserialize)]
+enum FileFacts {
+    Rust(RustFileReport),
+    Ts(TsFileReport),
+    Py(PyFileReport),
+}
+
+fn analyze_file(file: &Path, text: &str, language: &Language) -> FileFacts {
+    match language {
+        Language::Rust => FileFacts::Rust(analyze_rust_file(file, text)),
+        Language::TypeScript | Language::JavaScript => {
+            FileFacts::Ts(analyze_ts_file(file, text, language))
+        }
+        Language::Python => FileFacts::Py(analyze_py_file(file, text)),
+    }
+}
+
+/// Facts for one file, or `None` if its language is not audited.
+fn file_facts(
+    file: &Path,
+    config: &FlowAuditConfig,
+    cache: Option<&FileCache>,
+) -> Result<Option<FileFacts>> {
+    let language = match language_for_path(file) {
+        Some(lang) => lang,
+        None => return Ok(None),
+    };
+    if !config.languages.contains(&language) {
+        return Ok(None);
+    }
+    let text = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let scope = file.to_string_lossy();
+    if let Some(facts) = cache.and_then(|c| c.get(&scope, text.as_bytes())) {
+        return Ok(Some(facts));
+    }
+    let facts = analyze_file(file, &text, &language);
+    if let Some(cache) = cache {
+        cache.put(&scope, text.as_bytes(), &facts);
+    }
+    Ok(Some(facts))
+}
+
 pub fn audit_paths(paths: &[PathBuf], config: &FlowAuditConfig) -> Result<FlowAuditReport> {
+    audit_paths_cached(paths, config, None)
+}
+
+/// Like [`audit_paths`], but reuses per-file facts from `cache` for files
+/// whose content is unchanged. Cross-file passes always rerun.
+pub fn audit_paths_cached(
+    paths: &[PathBuf],
+    config: &FlowAuditConfig,
+    cache: Option<&FileCache>,
+) -> Result<FlowAuditReport> {
     let ignore_set = build_ignore_set(&config.ignore_globs)?;
     let files = collect_code_files(paths, &ignore_set, config)?;
 
@@ -192,29 +250,27 @@ pub fn audit_paths(paths: &[PathBuf], config: &FlowAuditConfig) -> Result<FlowAu
     let mut ts_aggregate = TsAggregate::default();
     let mut py_aggregate = PyAggregate::default();
 
-    for file in &files {
-        let language = match language_for_path(file) {
-            Some(lang) => lang,
-            None => continue,
-        };
-        if !config.languages.contains(&language) {
+    // Per-file analysis runs in parallel; aggregation consumes the results
+    // in file order so findings stay deterministic.
+    let per_file: Vec<Result<Option<FileFacts>>> = files
+        .par_iter()
+        .map(|file| file_facts(file, config, cache))
+        .collect();
+
+    for facts in per_file {
+        let Some(facts) = facts? else {
             continue;
-        }
-        let text = std::fs::read_to_string(file)
-            .with_context(|| format!("Failed to read {}", file.display()))?;
-        match language {
-            Language::Rust => {
-                let report = analyze_rust_file(file, &text);
+        };
+        match facts {
+            FileFacts::Rust(report) => {
                 dup_signatures.extend(report.dup_signatures.clone());
                 rust_aggregate.absorb(report);
             }
-            Language::TypeScript | Language::JavaScript => {
-                let report = analyze_ts_file(file, &text, &language);
+            FileFacts::Ts(report) => {
                 dup_signatures.extend(report.dup_signatures.clone());
                 ts_aggregate.absorb(report);
             }
-            Language::Python => {
-                let report = analyze_py_file(file, &text);
+            FileFacts::Py(report) => {
                 dup_signatures.extend(report.dup_signatures.clone());
                 py_aggregate.absorb(report);
             }
@@ -842,13 +898,13 @@ impl RustAggregate {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct RustPassThrough {
     callee: String,
     line: Option<u32>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct RustTraitInfo {
     name: String,
     path: PathBuf,
@@ -856,6 +912,7 @@ struct RustTraitInfo {
     allow_lonely: bool,
 }
 
+#[derive(Serialize, Deserialize)]
 struct RustFileReport {
     path: PathBuf,
     trait_defs: Vec<RustTraitInfo>,
@@ -1435,14 +1492,14 @@ impl TsAggregate {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct TsPassThrough {
     callee: String,
     line: Option<u32>,
     language: Language,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct TsInterfaceInfo {
     name: String,
     path: PathBuf,
@@ -1450,6 +1507,7 @@ struct TsInterfaceInfo {
     language: Language,
 }
 
+#[derive(Serialize, Deserialize)]
 struct TsFileReport {
     path: PathBuf,
     interface_defs: Vec<TsInterfaceInfo>,
@@ -1905,19 +1963,20 @@ fn normalize_finding_path(finding: &mut FlowFinding, config: &FlowAuditConfig) {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct PyPassThrough {
     callee: String,
     line: Option<u32>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct PyAbstractInfo {
     name: String,
     path: PathBuf,
     line: Option<u32>,
 }
 
+#[derive(Serialize, Deserialize)]
 struct PyFileReport {
     path: PathBuf,
     abstract_defs: Vec<PyAbstractInfo>,
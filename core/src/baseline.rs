@@ -0,0 +1,195 @@
+//! Baseline files for adopting strict mode on an existing docs tree.
+//!
+//! A baseline records a fingerprint for every diagnostic present when it was
+//! written. Later runs drop diagnostics that match a fingerprint, so only new
+//! findings are reported. Fingerprints avoid line numbers: they combine the
+//! rule ID, the file path, the normalised snippet and a hash of the line the
+//! diagnostic starts on, so edits elsewhere in the file do not invalidate them.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::Diagnostic;
+
+/// Bump when the fingerprint recipe changes.
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Fingerprint of one accepted diagnostic.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BaselineEntry {
+    pub path: String,
+    pub rule: String,
+    /// Lowercased snippet with whitespace collapsed.
+    pub snippet: String,
+    /// Truncated SHA-256 of the normalised source line.
+    pub context: String,
+}
+
+impl BaselineEntry {
+    /// Fingerprint `diag`, found in `text` at the config-relative `path`.
+    pub fn new(path: &str, text: &str, diag: &Diagnostic) -> Self {
+        let start = diag.span.0.min(text.len());
+        let line_start = text[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = text[start..]
+            .find('\n')
+            .map_or(text.len(), |idx| start + idx);
+        let line = text.get(line_start..line_end).unwrap_or("");
+        let digest = Sha256::digest(normalize(line).as_bytes());
+        let context = digest
+            .iter()
+            .take(8)
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        Self {
+            path: path.to_string(),
+            rule: diag.rule.clone(),
+            snippet: normalize(&diag.snippet),
+            context,
+        }
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Contents of a baseline file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    pub fn new(mut entries: Vec<BaselineEntry>) -> Self {
+        entries.sort();
+        Self {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+        let baseline: Baseline = serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid baseline {}", path.display()))?;
+        if baseline.version != BASELINE_VERSION {
+            anyhow::bail!(
+                "baseline {} has version {}, expected {}; rewrite it with --write-baseline",
+                path.display(),
+                baseline.version,
+                BASELINE_VERSION
+            );
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n")
+            .with_context(|| format!("Failed to write baseline {}", path.display()))
+    }
+
+    /// Start matching diagnostics against this baseline.
+    pub fn matcher(&self) -> BaselineMatcher {
+        let mut remaining: HashMap<BaselineEntry, usize> = HashMap::new();
+        for entry in &self.entries {
+            *remaining.entry(entry.clone()).or_default() += 1;
+        }
+        BaselineMatcher { remaining }
+    }
+}
+
+/// Consumes baseline entries as matching diagnostics are seen. Identical
+/// fingerprints are counted, so a second copy of a known issue is new.
+#[derive(Debug, Clone)]
+pub struct BaselineMatcher {
+    remaining: HashMap<BaselineEntry, usize>,
+}
+
+impl BaselineMatcher {
+    /// Returns `true` and consumes one entry if `entry` is in the baseline.
+    pub fn take(&mut self, entry: &BaselineEntry) -> bool {
+        match self.remaining.get_mut(entry) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Entries for the scanned paths that no diagnostic matched, i.e. issues
+    /// that have been fixed since the baseline was written.
+    pub fn into_fixed(self, scanned: &BTreeSet<String>) -> Vec<BaselineEntry> {
+        let mut fixed: Vec<BaselineEntry> = self
+            .remaining
+            .into_iter()
+            .filter(|(entry, _)| scanned.contains(&entry.path))
+            .flat_map(|(entry, count)| std::iter::repeat_n(entry, count))
+            .collect();
+        fixed.sort();
+        fixed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Analyzer, Config};
+
+    fn entries(path: &str, text: &str) -> Vec<BaselineEntry> {
+        let analyzer = Analyzer::new(Config::default()).unwrap();
+        analyzer
+            .analyze(text)
+            .diagnostics
+            .iter()
+            .map(|diag| BaselineEntry::new(path, text, diag))
+            .collect()
+    }
+
+    #[test]
+    fn test_fingerprints_survive_line_shifts() {
+        let before = "We will leverage the system.\n";
+        let after = "# Intro\n\nA new opening line.\n\nWe will leverage the system.\n";
+        let baseline = Baseline::new(entries("a.md", before));
+        assert!(!baseline.entries.is_empty());
+        let mut matcher = baseline.matcher();
+        let shifted: Vec<_> = entries("a.md", after)
+            .into_iter()
+            .filter(|entry| entry.rule == "buzzword/phrase")
+            .collect();
+        assert!(shifted.iter().all(|entry| matcher.take(entry)));
+    }
+
+    #[test]
+    fn test_duplicates_and_fixed_entries() {
+        let text = "We will leverage the system.\n";
+        let baseline = Baseline::new(entries("a.md", text));
+        let mut matcher = baseline.matcher();
+        let entry = entries("a.md", text)
+            .into_iter()
+            .find(|entry| entry.rule == "buzzword/phrase")
+            .unwrap();
+        assert!(matcher.take(&entry));
+        assert!(!matcher.take(&entry), "second copy is new");
+
+        let scanned: BTreeSet<String> = ["a.md".to_string()].into();
+        let fixed = matcher.clone().into_fixed(&scanned);
+        assert_eq!(fixed.len(), baseline.entries.len() - 1);
+        assert!(matcher.into_fixed(&BTreeSet::new()).is_empty());
+    }
+}
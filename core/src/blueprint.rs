@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use anyhow::Result;
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
@@ -347,6 +348,73 @@ struct RawEdge {
     line: Option<u32>,
 }
 
+/// Node and outgoing edges for one file, or the error that stopped it.
+type FileScan = std::result::Result<(BlueprintNode, Vec<BlueprintEdge>), BlueprintError>;
+
+/// Scan one file; `None` if its language is not supported.
+fn scan_file(
+    path: &Path,
+    base_dir: &Option<PathBuf>,
+    workspace_root: &Path,
+    workspace_crates: &BTreeMap<String, PathBuf>,
+    scanned_abs: &BTreeSet<PathBuf>,
+) -> Option<FileScan> {
+    let language = language_for_path(path)?;
+
+    let (size_bytes, content) = match std::fs::metadata(path)
+        .and_then(|meta| Ok((meta.len(), std::fs::read_to_string(path)?)))
+    {
+        Ok((size, text)) => (size, text),
+        Err(err) => {
+            return Some(Err(BlueprintError {
+                path: to_display_path(path, base_dir),
+                message: format!("{err}"),
+            }));
+        }
+    };
+
+    let line_count = content.lines().count().max(1) as u32;
+    let abs_path = path
+        .canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .to_string();
+    let display = to_display_path(path, base_dir);
+
+    let node = BlueprintNode {
+        path: display.clone(),
+        abs_path,
+        language: language.clone(),
+        size_bytes,
+        lines: line_count,
+    };
+
+    let mut edges = Vec::new();
+    let raw_edges = extract_edges(language, path, &content, workspace_root, workspace_crates);
+    for raw in raw_edges {
+        let mut resolved = false;
+        let to_display = raw.to_path.as_ref().and_then(|p| {
+            let abs = p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+            if scanned_abs.contains(&abs) {
+                resolved = true;
+                Some(to_display_path(&abs, base_dir))
+            } else {
+                None
+            }
+        });
+
+        edges.push(BlueprintEdge {
+            from: display.clone(),
+            to: to_display,
+            to_raw: raw.to_raw,
+            kind: raw.kind,
+            line: raw.line,
+            resolved,
+        });
+    }
+    Some(Ok((node, edges)))
+}
+
 pub fn blueprint_paths(paths: &[PathBuf], config: &BlueprintConfig) -> Result<BlueprintReport> {
     let ignore_set = build_ignore_set(&config.ignore_globs)?;
     let files = collect_code_files(paths, ignore_set.as_ref())?;
@@ -371,62 +439,25 @@ pub fn blueprint_paths(paths: &[PathBuf], config: &BlueprintConfig) -> Result<Bl
         }
     }
 
-    for path in &files {
-        let language = match language_for_path(path) {
-            Some(lang) => lang,
-            None => continue,
-        };
-
-        let (size_bytes, content) = match std::fs::metadata(path)
-            .and_then(|meta| Ok((meta.len(), std::fs::read_to_string(path)?)))
-        {
-            Ok((size, text)) => (size, text),
-            Err(err) => {
-                errors.push(BlueprintError {
-                    path: to_display_path(path, &base_dir),
-                    message: format!("{err}"),
-                });
-                continue;
+    let scans: Vec<Option<FileScan>> = files
+        .par_iter()
+        .map(|path| {
+            scan_file(
+                path,
+                &base_dir,
+                &workspace_root,
+                &workspace_crates,
+                &scanned_abs,
+            )
+        })
+        .collect();
+    for scan in scans.into_iter().flatten() {
+        match scan {
+            Ok((node, file_edges)) => {
+                nodes.push(node);
+                edges.extend(file_edges);
             }
-        };
-
-        let line_count = content.lines().count().max(1) as u32;
-        let abs_path = path
-            .canonicalize()
-            .unwrap_or_else(|_| path.to_path_buf())
-            .to_string_lossy()
-            .to_string();
-        let display = to_display_path(path, &base_dir);
-
-        nodes.push(BlueprintNode {
-            path: display.clone(),
-            abs_path,
-            language: language.clone(),
-            size_bytes,
-            lines: line_count,
-        });
-
-        let raw_edges = extract_edges(language, path, &content, &workspace_root, &workspace_crates);
-        for raw in raw_edges {
-            let mut resolved = false;
-            let to_display = raw.to_path.as_ref().and_then(|p| {
-                let abs = p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
-                if scanned_abs.contains(&abs) {
-                    resolved = true;
-                    Some(to_display_path(&abs, &base_dir))
-                } else {
-                    None
-                }
-            });
-
-            edges.push(BlueprintEdge {
-                from: display.clone(),
-                to: to_display,
-                to_raw: raw.to_raw,
-                kind: raw.kind,
-                line: raw.line,
-                resolved,
-            });
+            Err(error) => errors.push(error),
         }
     }
 
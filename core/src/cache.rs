@@ -0,0 +1,150 @@
+//! On-disk cache of per-file analysis results.
+//!
+//! Entries are keyed on a hash of the file content plus a scope string (the
+//! profile for prose, the path for flow audit facts). Each cache namespace
+//! keeps one salt directory derived from the tool version and the resolved
+//! config; opening the cache with a different salt drops the old directory,
+//! so upgrades and config edits never serve stale results.
+//!
+//! The cache is best-effort: unreadable entries are misses and write errors
+//! are ignored.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Cache location relative to the config root.
+pub const DEFAULT_CACHE_DIR: &str = ".toneguard/cache";
+
+#[derive(Debug, Clone)]
+pub struct FileCache {
+    dir: PathBuf,
+}
+
+impl FileCache {
+    /// Open the `namespace` cache under `root` for results produced with
+    /// `config`.
+    pub fn open(root: &Path, namespace: &str, config: &impl Serialize) -> anyhow::Result<Self> {
+        let config_json = serde_json::to_string(config)?;
+        let salt = hex_digest(&[
+            env!("CARGO_PKG_VERSION").as_bytes(),
+            namespace.as_bytes(),
+            config_json.as_bytes(),
+        ]);
+        let namespace_dir = root.join(namespace);
+        if let Ok(entries) = fs::read_dir(&namespace_dir) {
+            for entry in entries.flatten() {
+                if entry.file_name().to_string_lossy() != salt[..16] {
+                    let _ = fs::remove_dir_all(entry.path());
+                }
+            }
+        }
+        let dir = namespace_dir.join(&salt[..16]);
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, scope: &str, content: &[u8]) -> Option<T> {
+        let raw = fs::read(self.entry_path(scope, content)).ok()?;
+        serde_json::from_slice(&raw).ok()
+    }
+
+    pub fn put<T: Serialize>(&self, scope: &str, content: &[u8], value: &T) {
+        let path = self.entry_path(scope, content);
+        let Ok(json) = serde_json::to_vec(value) else {
+            return;
+        };
+        // Write then rename so a concurrent reader never sees a partial entry.
+        let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+        if fs::write(&tmp, json).is_ok() && fs::rename(&tmp, &path).is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+    }
+
+    fn entry_path(&self, scope: &str, content: &[u8]) -> PathBuf {
+        let key = hex_digest(&[scope.as_bytes(), content]);
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+pub(crate) fn hex_digest(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("toneguard-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn test_entries_are_keyed_on_scope_and_content() {
+        let root = temp_root("keys");
+        let cache = FileCache::open(&root, "lint", &"config-a").unwrap();
+        cache.put("default", b"hello", &42u32);
+        assert_eq!(cache.get::<u32>("default", b"hello"), Some(42));
+        assert_eq!(cache.get::<u32>("default", b"hello!"), None);
+        assert_eq!(cache.get::<u32>("readme", b"hello"), None);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_config_change_drops_old_entries() {
+        let root = temp_root("salt");
+        let first = FileCache::open(&root, "lint", &"config-a").unwrap();
+        first.put("default", b"hello", &1u32);
+        let second = FileCache::open(&root, "lint", &"config-b").unwrap();
+        assert_eq!(second.get::<u32>("default", b"hello"), None);
+        assert_eq!(fs::read_dir(root.join("lint")).unwrap().count(), 1);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_cached_audit_matches_fresh_audit() {
+        use crate::arch::{audit_paths, audit_paths_cached, FlowAuditConfig};
+
+        let root = temp_root("audit");
+        let src = root.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("lib.rs"),
+            "pub trait Store { fn get(&self) -> u32; }\npub struct Db;\nimpl Store for Db { fn get(&self) -> u32 { 1 } }\n",
+        )
+        .unwrap();
+        let config = FlowAuditConfig::default();
+        let cache = FileCache::open(&root.join("cache"), "audit", &config).unwrap();
+        let summaries = |report: crate::arch::FlowAuditReport| {
+            let mut lines: Vec<String> = report
+                .findings
+                .iter()
+                .map(|f| format!("{}:{:?}:{}", f.path, f.line, f.message))
+                .collect();
+            lines.sort();
+            lines
+        };
+        let paths = vec![src];
+        let fresh = summaries(audit_paths(&paths, &config).unwrap());
+        let cold = summaries(audit_paths_cached(&paths, &config, Some(&cache)).unwrap());
+        let warm = summaries(audit_paths_cached(&paths, &config, Some(&cache)).unwrap());
+        assert!(!fresh.is_empty());
+        assert_eq!(fresh, cold);
+        assert_eq!(fresh, warm);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
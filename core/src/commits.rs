@@ -0,0 +1,180 @@
+//! Prose lint for commit messages.
+//!
+//! Messages come either from `git log` over a revision range or from the
+//! file git hands to a `commit-msg` hook. Trailers (`Signed-off-by:`,
+//! `Co-authored-by:` and friends) are dropped before analysis, so only the
+//! subject and body are judged.
+//!
+//! Analysis runs with the `commit-message` profile. The analyzer provides a
+//! built-in version with the document structure checks turned off, a subject
+//! length limit, and extra template phrases for boilerplate such as "This
+//! commit enhances the robustness of...". Declaring a profile with that name
+//! in the config replaces it.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context};
+use serde::Serialize;
+
+use crate::{Analyzer, DocumentReport};
+
+/// Profile used for commit messages.
+pub const COMMIT_MESSAGE_PROFILE: &str = "commit-message";
+
+/// Subject limit for the built-in `commit-message` profile.
+pub const DEFAULT_MAX_SUBJECT_LENGTH: usize = 72;
+
+/// Template phrases added by the built-in `commit-message` profile.
+pub const COMMIT_TEMPLATE_PHRASES: &[&str] = &[
+    r"\bthis (commit|pr|pull request|change|patch|mr) (aims to|seeks to|is designed to|enhances|improves|introduces|ensures|streamlines|addresses)\b",
+    r"\b(enhances?|improves?|bolsters?) the (overall )?(robustness|reliability|maintainability|readability|resilience|user experience)\b",
+    r"\b(significantly|greatly|substantially) (improves?|enhances?|boosts?)\b",
+    r"\bfor (better|improved|enhanced) (maintainability|readability|clarity|robustness)\b",
+    r"\bthis ensures that\b",
+];
+
+/// Marker line below which git drops the rest of a message file
+/// (`git commit --verbose`).
+const SCISSORS: &str = "------------------------ >8 ------------------------";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitMessage {
+    pub sha: String,
+    pub message: String,
+}
+
+impl CommitMessage {
+    pub fn subject(&self) -> &str {
+        self.message.trim_start().lines().next().unwrap_or_default()
+    }
+}
+
+/// Read commit messages for `range` (anything `git log` accepts) from the
+/// repository at `repo`, newest first.
+pub fn read_commits(repo: &Path, range: &str) -> anyhow::Result<Vec<CommitMessage>> {
+    let output = Command::new("git")
+        .args(["log", "--format=%H%x00%B%x1e", range, "--"])
+        .current_dir(repo)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git log {range} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut commits = Vec::new();
+    for record in stdout.split('\x1e') {
+        let Some((sha, message)) = record.split_once('\0') else {
+            continue;
+        };
+        commits.push(CommitMessage {
+            sha: sha.trim().to_string(),
+            message: message.trim_end().to_string(),
+        });
+    }
+    Ok(commits)
+}
+
+/// Clean a message file the way git does before recording it: everything
+/// below the scissors line goes and `#` comment lines are blanked. Blanking
+/// rather than removing keeps line numbers aligned with the file.
+pub fn clean_message_file(raw: &str) -> String {
+    let mut cleaned = String::with_capacity(raw.len());
+    for line in raw.lines() {
+        if line.starts_with('#') {
+            if line.contains(SCISSORS) {
+                break;
+            }
+            cleaned.push('\n');
+            continue;
+        }
+        cleaned.push_str(line);
+        cleaned.push('\n');
+    }
+    cleaned
+}
+
+/// `message` without its trailer block. Trailers are the last paragraph
+/// when every line in it is a `Token: value` pair or a continuation; a
+/// message that is only a subject keeps it.
+pub fn strip_trailers(message: &str) -> &str {
+    let body = message.trim_end();
+    let Some(split) = body.rfind("\n\n") else {
+        return body;
+    };
+    let paragraph = body[split + 2..].trim_start_matches('\n');
+    let all_trailers = paragraph.lines().enumerate().all(|(idx, line)| {
+        if idx > 0 && line.starts_with([' ', '\t']) {
+            return true;
+        }
+        line.split_once(": ").is_some_and(|(token, _)| {
+            !token.is_empty()
+                && token
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+        })
+    });
+    if all_trailers {
+        body[..split].trim_end()
+    } else {
+        body
+    }
+}
+
+/// Analyze a commit message with `profile`, ignoring its trailers.
+pub fn analyze_message(
+    analyzer: &Analyzer,
+    message: &str,
+    profile: &str,
+) -> anyhow::Result<DocumentReport> {
+    analyzer.analyze_profile_name(strip_trailers(message), profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    #[test]
+    fn test_trailers_are_stripped() {
+        let message = "Fix cache eviction\n\nDrop stale salts on open.\n\nSigned-off-by: A <a@example.com>\nCo-authored-by: B <b@example.com>\n";
+        assert_eq!(
+            strip_trailers(message),
+            "Fix cache eviction\n\nDrop stale salts on open."
+        );
+        let prose = "Fix cache eviction\n\nSee the note: salts are per config.";
+        assert_eq!(strip_trailers(prose), prose);
+        assert_eq!(strip_trailers("Refs: #12"), "Refs: #12");
+    }
+
+    #[test]
+    fn test_message_file_cleanup_keeps_line_numbers() {
+        let raw = "Fix parser\n# Please enter the commit message\n\nBody line.\n# ------------------------ >8 ------------------------\ndiff --git a/x b/x\n";
+        assert_eq!(clean_message_file(raw), "Fix parser\n\n\nBody line.\n");
+    }
+
+    #[test]
+    fn test_commit_profile_flags_subject_and_boilerplate() {
+        let analyzer = Analyzer::new(Config::default()).unwrap();
+        let long_subject = format!("Fix {}", "the widget ".repeat(10));
+        let report = analyze_message(&analyzer, &long_subject, COMMIT_MESSAGE_PROFILE).unwrap();
+        assert!(report
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == "structure/subject-length"));
+
+        let message = "Refactor cache module\n\nThis commit enhances the robustness of the cache layer.\n\nSigned-off-by: A <a@example.com>\n";
+        let report = analyze_message(&analyzer, message, COMMIT_MESSAGE_PROFILE).unwrap();
+        assert!(report
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == "template/phrase"));
+        assert!(report
+            .diagnostics
+            .iter()
+            .all(|d| d.rule != "structure/subject-length"));
+    }
+}
@@ -0,0 +1,364 @@
+//! User-defined rules declared under `custom_rules:` in the config.
+//!
+//! A custom rule matches a regex or a phrase list inside one scope of the
+//! parsed document and reports under the `custom` category as
+//! `custom/<id>`. House rules such as "never write 'simply'" or "spell the
+//! product name ToneGuard" live here instead of in a fork of the analyzer:
+//!
+//! ```yaml
+//! custom_rules:
+//!   - id: product-name
+//!     pattern: '\btone ?guard\b'
+//!     allow: ["ToneGuard"]
+//!     severity: error
+//!     message: "Write the product name as ToneGuard, not `$0`."
+//!     replacement: ToneGuard
+//!     profiles: [readme, docs]
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+
+use regex::{Regex, RegexBuilder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::markdown::{BlockKind, MarkdownDoc};
+use crate::{
+    byte_to_location, is_word_char, Applicability, Category, Diagnostic, DisabledRanges, Fix,
+    Severity,
+};
+
+/// Part of the document a custom rule is matched against. Matches never
+/// cross the boundary of a scope unit.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuleScope {
+    #[default]
+    Sentence,
+    Paragraph,
+    Heading,
+    ListItem,
+    Document,
+}
+
+/// One `custom_rules:` entry.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CustomRule {
+    /// Short name; diagnostics report it as `custom/<id>`.
+    pub id: String,
+    /// Regex to match. Give either this or `phrases`.
+    pub pattern: Option<String>,
+    /// Literal phrases matched on word boundaries.
+    pub phrases: Vec<String>,
+    /// Matching ignores case unless this is set.
+    pub case_sensitive: bool,
+    /// Matched text accepted as-is, e.g. the correct spelling of a name.
+    pub allow: Vec<String>,
+    pub scope: RuleScope,
+    pub severity: Severity,
+    /// Message template. `$0`, `${1}` and `${name}` expand to capture groups.
+    pub message: String,
+    /// Replacement template offered as a fix; same expansion as `message`.
+    pub replacement: Option<String>,
+    pub applicability: Applicability,
+    /// Profiles the rule runs in. Empty means every profile.
+    pub profiles: Vec<String>,
+}
+
+impl Default for CustomRule {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            pattern: None,
+            phrases: Vec::new(),
+            case_sensitive: false,
+            allow: Vec::new(),
+            scope: RuleScope::Sentence,
+            severity: Severity::Warning,
+            message: String::new(),
+            replacement: None,
+            applicability: Applicability::Safe,
+            profiles: Vec::new(),
+        }
+    }
+}
+
+/// Normalise `custom/<id>` to lowercase, or `None` if `name` is not a
+/// well-formed custom rule ID.
+pub fn normalize_id(name: &str) -> Option<String> {
+    let name = name.trim().to_lowercase();
+    let slug = name.strip_prefix("custom/")?;
+    let valid = !slug.is_empty()
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    valid.then(|| format!("custom/{slug}"))
+}
+
+/// A custom rule with its regex compiled.
+#[derive(Debug, Clone)]
+pub(crate) struct CompiledRule {
+    pub(crate) id: String,
+    regex: Regex,
+    allow: HashSet<String>,
+    scope: RuleScope,
+    severity: Severity,
+    message: String,
+    replacement: Option<String>,
+    applicability: Applicability,
+    profiles: Vec<String>,
+}
+
+impl CompiledRule {
+    pub(crate) fn compile(rule: &CustomRule) -> anyhow::Result<Self> {
+        let raw_id = rule.id.trim();
+        let id = if raw_id.contains('/') {
+            normalize_id(raw_id)
+        } else {
+            normalize_id(&format!("custom/{raw_id}"))
+        }
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "invalid custom rule id `{raw_id}`: use lowercase letters, digits and dashes"
+            )
+        })?;
+
+        let pattern = match (&rule.pattern, rule.phrases.is_empty()) {
+            (Some(pattern), true) => pattern.clone(),
+            (None, false) => phrase_pattern(&rule.phrases),
+            (Some(_), false) => {
+                anyhow::bail!("custom rule `{id}` sets both `pattern` and `phrases`")
+            }
+            (None, true) => anyhow::bail!("custom rule `{id}` needs a `pattern` or `phrases`"),
+        };
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(!rule.case_sensitive)
+            .multi_line(true)
+            .build()
+            .map_err(|e| anyhow::anyhow!("invalid regex in custom rule `{id}`: {e}"))?;
+
+        let message = if rule.message.trim().is_empty() {
+            format!("`$0` matches custom rule `{id}`.")
+        } else {
+            rule.message.clone()
+        };
+
+        Ok(Self {
+            id,
+            regex,
+            allow: rule.allow.iter().cloned().collect(),
+            scope: rule.scope,
+            severity: rule.severity,
+            message,
+            replacement: rule.replacement.clone(),
+            applicability: rule.applicability,
+            profiles: rule.profiles.clone(),
+        })
+    }
+
+    pub(crate) fn profiles(&self) -> &[String] {
+        &self.profiles
+    }
+
+    pub(crate) fn applies_to(&self, profile: &str) -> bool {
+        self.profiles.is_empty() || self.profiles.iter().any(|name| name == profile)
+    }
+}
+
+/// Build a case-insensitive-ready alternation of escaped phrases. Word
+/// boundaries are only added next to word characters so phrases such as
+/// `e.g.` still match.
+fn phrase_pattern(phrases: &[String]) -> String {
+    let mut phrases: Vec<&str> = phrases
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect();
+    phrases.sort_by_key(|p| std::cmp::Reverse(p.len()));
+    let alternatives: Vec<String> = phrases
+        .iter()
+        .map(|phrase| {
+            let lead = if phrase.chars().next().is_some_and(is_word_char) {
+                r"\b"
+            } else {
+                ""
+            };
+            let trail = if phrase.chars().last().is_some_and(is_word_char) {
+                r"\b"
+            } else {
+                ""
+            };
+            format!("{lead}{}{trail}", regex::escape(phrase))
+        })
+        .collect();
+    format!("(?:{})", alternatives.join("|"))
+}
+
+/// Byte ranges covered by each unit of `scope`.
+fn scope_spans(
+    scope: RuleScope,
+    text: &str,
+    doc: &MarkdownDoc,
+    sentences: &[(String, usize, BlockKind)],
+) -> Vec<(usize, usize)> {
+    let blocks = |kind: BlockKind| {
+        doc.blocks
+            .iter()
+            .filter(|block| block.kind == kind)
+            .map(|block| block.span)
+            .collect()
+    };
+    match scope {
+        RuleScope::Sentence => sentences
+            .iter()
+            .map(|(sentence, start, _)| (*start, start + sentence.len()))
+            .collect(),
+        RuleScope::Paragraph => blocks(BlockKind::Paragraph),
+        RuleScope::Heading => blocks(BlockKind::Heading),
+        RuleScope::ListItem => blocks(BlockKind::ListItem),
+        RuleScope::Document => vec![(0, text.len())],
+    }
+}
+
+/// Run the custom rules enabled for the current profile.
+pub(crate) fn detect(
+    rules: &[CompiledRule],
+    text: &str,
+    doc: &MarkdownDoc,
+    sentences: &[(String, usize, BlockKind)],
+    filtered: &DisabledRanges,
+    diagnostics: &mut Vec<Diagnostic>,
+    counts: &mut BTreeMap<Category, usize>,
+) {
+    for rule in rules {
+        for (base, end) in scope_spans(rule.scope, text, doc, sentences) {
+            for caps in rule.regex.captures_iter(&text[base..end]) {
+                let whole = caps.get(0).expect("group 0 always matches");
+                if whole.as_str().is_empty() || rule.allow.contains(whole.as_str()) {
+                    continue;
+                }
+                let (start, stop) = (base + whole.start(), base + whole.end());
+                if filtered.is_category_disabled(start, Category::Custom) {
+                    continue;
+                }
+                let location = byte_to_location(text, start);
+                if filtered.is_line_ignored(location.line) {
+                    continue;
+                }
+                let mut message = String::new();
+                caps.expand(&rule.message, &mut message);
+                let replacement = rule.replacement.as_ref().map(|template| {
+                    let mut out = String::new();
+                    caps.expand(template, &mut out);
+                    out
+                });
+                let suggestion = replacement.as_ref().map(|r| {
+                    if r.is_empty() {
+                        "Remove it.".to_string()
+                    } else {
+                        format!("Replace with `{r}`.")
+                    }
+                });
+                let fix = replacement.map(|r| {
+                    if r.is_empty() {
+                        let (edit_end, capital) = deletion(text, start, stop);
+                        Fix::replace((start, edit_end), capital, rule.applicability)
+                    } else {
+                        Fix::replace((start, stop), r, rule.applicability)
+                    }
+                });
+                diagnostics.push(Diagnostic {
+                    category: Category::Custom,
+                    rule: rule.id.clone(),
+                    severity: rule.severity,
+                    message,
+                    suggestion,
+                    location,
+                    span: (start, stop),
+                    snippet: whole.as_str().to_string(),
+                    fix,
+                });
+                *counts.entry(Category::Custom).or_default() += 1;
+            }
+        }
+    }
+}
+
+/// End of the edit that deletes `start..stop`, and the text that replaces
+/// it. Deleting a word should not leave a double space behind, and deleting
+/// the capitalised first word of a sentence moves the capital to the next
+/// word.
+fn deletion(text: &str, start: usize, stop: usize) -> (usize, String) {
+    let end = if text[stop..].starts_with(' ') {
+        stop + 1
+    } else {
+        stop
+    };
+    let before = text[..start].trim_end_matches([' ', '\t']);
+    let starts_sentence =
+        before.is_empty() || before.ends_with(['\n', '.', '!', '?', '#', '-', '*', '>']);
+    let capitalised = text[start..stop].starts_with(char::is_uppercase);
+    match text[end..].chars().next() {
+        Some(next) if starts_sentence && capitalised && next.is_lowercase() => {
+            (end + next.len_utf8(), next.to_uppercase().collect())
+        }
+        _ => (end, String::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deleting_first_word_keeps_capital() {
+        let text = "Simply put, it works. You simply run it.";
+        assert_eq!(deletion(text, 0, 6), (8, "P".to_string()));
+        let mid = text.find("simply").unwrap();
+        assert_eq!(deletion(text, mid, mid + 6), (mid + 7, String::new()));
+        let text = "The Acme widget.";
+        assert_eq!(deletion(text, 4, 8), (9, String::new()));
+    }
+
+    #[test]
+    fn test_normalize_id() {
+        assert_eq!(
+            normalize_id("Custom/No-Simply"),
+            Some("custom/no-simply".into())
+        );
+        assert_eq!(normalize_id("custom/"), None);
+        assert_eq!(normalize_id("custom/no simply"), None);
+        assert_eq!(normalize_id("tone/passive-density"), None);
+    }
+
+    #[test]
+    fn test_phrase_pattern_respects_word_boundaries() {
+        let regex = Regex::new(&phrase_pattern(&["simply".into(), "e.g.".into()])).unwrap();
+        assert!(regex.is_match("You simply run it."));
+        assert!(!regex.is_match("Simplyfied."));
+        assert!(regex.is_match("Tools, e.g. linters."));
+    }
+
+    #[test]
+    fn test_compile_rejects_ambiguous_rules() {
+        let both = CustomRule {
+            id: "x".into(),
+            pattern: Some("a".into()),
+            phrases: vec!["b".into()],
+            ..CustomRule::default()
+        };
+        assert!(CompiledRule::compile(&both).is_err());
+        let neither = CustomRule {
+            id: "x".into(),
+            ..CustomRule::default()
+        };
+        assert!(CompiledRule::compile(&neither).is_err());
+        let bad_id = CustomRule {
+            id: "No Simply".into(),
+            phrases: vec!["simply".into()],
+            ..CustomRule::default()
+        };
+        assert!(CompiledRule::compile(&bad_id).is_err());
+    }
+}
@@ -0,0 +1,432 @@
+//! Inline directives: HTML comments that turn checks off in part of a
+//! document.
+//!
+//! - `<!-- dwg:off -->` ... `<!-- dwg:on -->` turns every check off
+//! - `<!-- dwg:ignore tone, structure/max-headings -->` ...
+//!   `<!-- dwg:end-ignore -->` turns categories or rule IDs off
+//! - `<!-- dwg:ignore-line -->` turns every check off on its line, and on
+//!   the next line when the comment stands alone
+//!
+//! Directives quoted in code are left alone. After analysis each directive
+//! is reported when it cannot do what it says:
+//!
+//! - `directive/unknown-name`: a `dwg:ignore` name that is no category or
+//!   rule ID. When none of its names is known the block turns every check
+//!   off
+//! - `directive/unterminated-ignore`: a `dwg:ignore` without
+//!   `dwg:end-ignore`, which runs to the end of the document
+//! - `directive/unused`: a directive that suppressed nothing, that is one
+//!   no check consulted while it was hiding a finding
+//!
+//! Their unsafe fixes replace an unknown name with the known one it is
+//! closest to, close an unterminated ignore at the end of the document, and
+//! remove an unused directive with its closing comment.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::markdown::MarkdownDoc;
+use crate::rules::RuleSelector;
+use crate::{
+    byte_to_location, edit_distance, find_subsequence, is_in_ranges, Applicability, Category,
+    Diagnostic, Fix, Severity, TextEdit,
+};
+
+/// Rule ID of directives that suppressed nothing.
+pub(crate) const UNUSED_RULE: &str = "directive/unused";
+
+const OFF: &str = "<!-- dwg:off -->";
+const ON: &str = "<!-- dwg:on -->";
+const END_IGNORE: &str = "<!-- dwg:end-ignore -->";
+
+/// Regex to match inline ignore comments: <!-- dwg:ignore category -->
+static INLINE_IGNORE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<!--\s*dwg:ignore\s+([a-z0-9/,\s-]+)\s*-->").expect("valid inline ignore regex")
+});
+
+/// Regex to match end-ignore comments: <!-- dwg:end-ignore -->
+static END_IGNORE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<!--\s*dwg:end-ignore\s*-->").expect("valid end ignore regex"));
+
+/// Regex to match ignore-line comments: <!-- dwg:ignore-line -->
+static IGNORE_LINE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<!--\s*dwg:ignore-line\s*-->").expect("valid ignore line regex"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Off,
+    Ignore,
+    IgnoreLine,
+}
+
+/// What a directive turns off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Scope {
+    /// Every check in `range`.
+    All,
+    /// The named categories and rules in `range`.
+    Selectors(Vec<RuleSelector>),
+    /// Every check on these 1-based lines.
+    Lines(Vec<usize>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Directive {
+    pub(crate) kind: Kind,
+    /// The opening comment.
+    pub(crate) span: (usize, usize),
+    /// The closing comment, if there is one.
+    pub(crate) close: Option<(usize, usize)>,
+    /// From the opening comment to the end of the closing one, or to the
+    /// end of the document.
+    pub(crate) range: (usize, usize),
+    pub(crate) scope: Scope,
+    /// `dwg:ignore` names that are no category or rule, with their spans.
+    unknown: Vec<(String, (usize, usize))>,
+}
+
+impl Directive {
+    fn label(&self) -> &'static str {
+        match self.kind {
+            Kind::Off => "dwg:off",
+            Kind::Ignore => "dwg:ignore",
+            Kind::IgnoreLine => "dwg:ignore-line",
+        }
+    }
+}
+
+/// The directives in `text`, in document order.
+pub(crate) fn parse(text: &str, doc: &MarkdownDoc) -> Vec<Directive> {
+    let in_code = |offset: usize| {
+        is_in_ranges(&doc.code_blocks, offset) || is_in_ranges(&doc.inline_code, offset)
+    };
+    let mut directives = Vec::new();
+
+    // Explicit dwg:off ranges (global disable).
+    let mut cursor = 0;
+    let bytes = text.as_bytes();
+    while let Some(start) = find_subsequence(bytes, OFF.as_bytes(), cursor) {
+        let span = (start, start + OFF.len());
+        if in_code(start) {
+            cursor = span.1;
+            continue;
+        }
+        let close = find_subsequence(bytes, ON.as_bytes(), span.1).map(|on| (on, on + ON.len()));
+        directives.push(Directive {
+            kind: Kind::Off,
+            span,
+            close,
+            range: (start, close.map_or(text.len(), |(_, end)| end)),
+            scope: Scope::All,
+            unknown: Vec::new(),
+        });
+        match close {
+            Some((_, end)) => cursor = end,
+            None => break,
+        }
+    }
+
+    // Category- or rule-specific ignores: <!-- dwg:ignore category --> ... <!-- dwg:end-ignore -->
+    for cap in INLINE_IGNORE_RE.captures_iter(text) {
+        let full_match = cap.get(0).unwrap();
+        let names = cap.get(1).unwrap();
+        if in_code(full_match.start()) {
+            continue;
+        }
+        // Find the end-ignore marker; without one, apply to rest of document
+        let search_from = full_match.end();
+        let close = END_IGNORE_RE
+            .find(&text[search_from..])
+            .map(|end| (search_from + end.start(), search_from + end.end()));
+        let mut selectors = Vec::new();
+        let mut unknown = Vec::new();
+        let mut offset = names.start();
+        for raw in names.as_str().split(',') {
+            let name = raw.trim();
+            let start = offset + raw.find(name).unwrap_or_default();
+            offset += raw.len() + 1;
+            if name.is_empty() {
+                continue;
+            }
+            match RuleSelector::parse(name) {
+                Some(selector) => selectors.push(selector),
+                None => unknown.push((name.to_string(), (start, start + name.len()))),
+            }
+        }
+        directives.push(Directive {
+            kind: Kind::Ignore,
+            span: (full_match.start(), full_match.end()),
+            close,
+            range: (full_match.start(), close.map_or(text.len(), |(_, end)| end)),
+            scope: if selectors.is_empty() {
+                Scope::All
+            } else {
+                Scope::Selectors(selectors)
+            },
+            unknown,
+        });
+    }
+
+    // Ignore-line markers: <!-- dwg:ignore-line -->
+    let mut line_start = 0;
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        if let Some(mat) = IGNORE_LINE_RE.find(line) {
+            let start = line_start + mat.start();
+            if !in_code(start) {
+                let line_num = index + 1;
+                let mut lines = vec![line_num];
+                if line.trim() == "<!-- dwg:ignore-line -->" {
+                    lines.push(line_num + 1);
+                }
+                directives.push(Directive {
+                    kind: Kind::IgnoreLine,
+                    span: (start, line_start + mat.end()),
+                    close: None,
+                    range: (line_start, line_start + line.len()),
+                    scope: Scope::Lines(lines),
+                    unknown: Vec::new(),
+                });
+            }
+        }
+        line_start += line.len();
+    }
+
+    directives.sort_by_key(|directive| directive.span);
+    directives
+}
+
+/// Diagnostics for `directives`. `used` holds the opening spans of the
+/// directives that hid something while the document was checked. With
+/// `duplicates` on, ignores naming `repetition` or
+/// `repetition/duplicate-paragraph` also hide paragraphs from the
+/// cross-file duplicate pass, which one document cannot see, so they are
+/// never reported as unused.
+pub(crate) fn check(
+    text: &str,
+    directives: &[Directive],
+    used: &[(usize, usize)],
+    duplicates: bool,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for (index, directive) in directives.iter().enumerate() {
+        let hides_duplicates = duplicates
+            && matches!(&directive.scope, Scope::Selectors(selectors)
+                if selectors
+                    .iter()
+                    .any(|selector| selector.matches(Category::Repetition, crate::duplicates::RULE)));
+        let unused = !hides_duplicates && !used.contains(&directive.span);
+        for (name, span) in &directive.unknown {
+            let message = if directive.scope == Scope::All {
+                format!(
+                    "Unknown category or rule `{name}`; this `dwg:ignore` turns every check off."
+                )
+            } else {
+                format!("Unknown category or rule `{name}` in `dwg:ignore`.")
+            };
+            let known = closest_known(name);
+            let suggestion = match &known {
+                Some(candidate) => format!("Did you mean `{candidate}`?"),
+                None => "Use a category or rule ID, such as `tone` or `tone/passive-density`."
+                    .to_string(),
+            };
+            diagnostics.push(note(
+                text,
+                "directive/unknown-name",
+                Severity::Warning,
+                message,
+                suggestion,
+                *span,
+                known.map(|candidate| Fix::replace(*span, candidate, Applicability::Unsafe)),
+            ));
+        }
+        if directive.kind == Kind::Ignore && directive.close.is_none() {
+            diagnostics.push(note(
+                text,
+                "directive/unterminated-ignore",
+                Severity::Hint,
+                "`dwg:ignore` has no `dwg:end-ignore` and runs to the end of the document."
+                    .to_string(),
+                format!("Close the block with `{END_IGNORE}`."),
+                directive.span,
+                // Removing the directive is the better fix when it is unused.
+                (!unused).then(|| close_fix(text)),
+            ));
+        }
+        if unused {
+            diagnostics.push(note(
+                text,
+                UNUSED_RULE,
+                Severity::Warning,
+                format!("`{}` suppresses nothing.", directive.label()),
+                "Remove the directive; the text it covered no longer trips a check.".to_string(),
+                directive.span,
+                Some(removal_fix(text, directives, index)),
+            ));
+        }
+    }
+    diagnostics
+}
+
+fn note(
+    text: &str,
+    rule: &str,
+    severity: Severity,
+    message: String,
+    suggestion: String,
+    span: (usize, usize),
+    fix: Option<Fix>,
+) -> Diagnostic {
+    Diagnostic {
+        category: Category::Directive,
+        rule: rule.to_string(),
+        severity,
+        message,
+        suggestion: Some(suggestion),
+        location: byte_to_location(text, span.0),
+        span,
+        snippet: text[span.0..span.1].to_string(),
+        fix,
+    }
+}
+
+/// The category or rule ID `name` is most likely a typo of.
+fn closest_known(name: &str) -> Option<String> {
+    crate::rules::RULES
+        .iter()
+        .flat_map(|rule| [rule.id.to_string(), rule.category.to_string()])
+        .map(|candidate| (edit_distance(name, &candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= (candidate.len() / 3).max(1))
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Close an unterminated ignore at the end of the document, where it ends
+/// now. Every unterminated ignore inserts at the same offset, so
+/// [`crate::apply_fixes`] keeps the first and only one closing comment is
+/// added.
+fn close_fix(text: &str) -> Fix {
+    let gap = if text.ends_with('\n') { "\n" } else { "\n\n" };
+    Fix::replace(
+        (text.len(), text.len()),
+        format!("{gap}{END_IGNORE}\n"),
+        Applicability::Unsafe,
+    )
+}
+
+/// Remove the directive at `index` and its closing comment, unless another
+/// directive shares that comment.
+fn removal_fix(text: &str, directives: &[Directive], index: usize) -> Fix {
+    let directive = &directives[index];
+    let mut edits = vec![removal(text, directive.span)];
+    if let Some(close) = directive.close {
+        let shared = directives
+            .iter()
+            .enumerate()
+            .any(|(other, d)| other != index && d.close == Some(close));
+        if !shared {
+            edits.push(removal(text, close));
+        }
+    }
+    Fix {
+        applicability: Applicability::Unsafe,
+        edits,
+    }
+}
+
+/// Delete a comment: its whole line when it stands alone, otherwise the
+/// comment and the blanks before it.
+fn removal(text: &str, (start, end): (usize, usize)) -> TextEdit {
+    let line_start = text[..start].rfind('\n').map_or(0, |newline| newline + 1);
+    let line_end = text[end..]
+        .find('\n')
+        .map_or(text.len(), |newline| end + newline + 1);
+    let span = if text[line_start..start].trim().is_empty() && text[end..line_end].trim().is_empty()
+    {
+        (line_start, line_end)
+    } else {
+        (
+            start - (text[..start].len() - text[..start].trim_end_matches([' ', '\t']).len()),
+            end,
+        )
+    };
+    TextEdit {
+        span,
+        replacement: String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directives(text: &str) -> Vec<Directive> {
+        parse(text, &MarkdownDoc::parse(text))
+    }
+
+    #[test]
+    fn test_directives_in_code_are_text() {
+        let text = "Use `<!-- dwg:off -->` to stop.\n\n```\n<!-- dwg:ignore tone -->\n```\n\n<!-- dwg:ignore tone, nope -->\nText.\n<!-- dwg:end-ignore -->\n";
+        let found = directives(text);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, Kind::Ignore);
+        assert_eq!(
+            found[0].scope,
+            Scope::Selectors(vec![RuleSelector::Category(Category::Tone)])
+        );
+        let (name, (start, end)) = &found[0].unknown[0];
+        assert_eq!((name.as_str(), &text[*start..*end]), ("nope", "nope"));
+        assert!(found[0].close.is_some());
+    }
+
+    #[test]
+    fn test_removal_takes_lone_comment_lines() {
+        let text = "One.\n<!-- dwg:off -->\nTwo. <!-- dwg:ignore-line -->\n<!-- dwg:on -->\n";
+        let found = directives(text);
+        let fix = removal_fix(text, &found, 0);
+        let outcome = crate::apply_fixes(
+            text,
+            &[note(
+                text,
+                UNUSED_RULE,
+                Severity::Warning,
+                String::new(),
+                String::new(),
+                found[0].span,
+                Some(fix),
+            )],
+            true,
+        );
+        assert_eq!(outcome.text, "One.\nTwo. <!-- dwg:ignore-line -->\n");
+        assert_eq!(
+            removal(text, found[1].span).span.0,
+            text.find(" <!--").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unknown_names_get_suggestions() {
+        assert_eq!(closest_known("spellng").as_deref(), Some("spelling"));
+        assert_eq!(closest_known("zzzzzz"), None);
+    }
+
+    #[test]
+    fn test_unterminated_ignores_close_once() {
+        let text = "<!-- dwg:ignore tone -->\nOne.\n<!-- dwg:ignore spelling -->\nTwo.";
+        let closes = [close_fix(text), close_fix(text)].map(|fix| Diagnostic {
+            fix: Some(fix),
+            ..note(
+                text,
+                "directive/unterminated-ignore",
+                Severity::Hint,
+                String::new(),
+                String::new(),
+                (0, 1),
+                None,
+            )
+        });
+        let outcome = crate::apply_fixes(text, &closes, true);
+        assert_eq!(outcome.text, format!("{text}\n\n{END_IGNORE}\n"));
+        let found = directives(&outcome.text);
+        assert!(found.iter().all(|directive| directive.close.is_some()));
+    }
+}
@@ -0,0 +1,462 @@
+//! Per-directory config discovery.
+//!
+//! Besides the root config (`layth-style.yml` by default), any directory
+//! may hold a `.toneguard.yml`. As with `.editorconfig`, a file is linted
+//! with the root config plus every such file found walking up from the
+//! file's own directory, nearest last. The walk stops at the directory of
+//! the root config or at the repository root (the nearest directory with a
+//! `.git` entry), whichever comes first. Outside a repository it stops at
+//! the config root even without a root config, and a file that is not below
+//! the config root only sees its own directory. Each local file is layered
+//! onto the fully resolved config above it with [`packs::layer`]: mappings
+//! merge key by key, lists and scalars it sets replace the inherited value,
+//! and `{add, remove}` edits the inherited list. Paths and globs inside a
+//! local file are still relative to the config root.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Context};
+use serde_yaml::Value;
+
+use crate::cache::hex_digest;
+use crate::{packs, Analyzer, Config};
+
+/// File names checked in each directory, in order of preference.
+pub const LOCAL_CONFIG_FILES: &[&str] = &[".toneguard.yml", ".toneguard.yaml"];
+
+/// Whether `path` names a per-directory config file.
+pub fn is_local_config(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| LOCAL_CONFIG_FILES.contains(&name))
+}
+
+type Adjust = Box<dyn Fn(&mut Config) -> anyhow::Result<()> + Send + Sync>;
+
+/// The root config plus the `.toneguard.yml` files below it, resolved on
+/// demand and cached per chain of local files.
+pub struct ConfigTree {
+    root_dir: PathBuf,
+    root_file: Option<PathBuf>,
+    /// Resolved root config, with every key filled in so local files layer
+    /// over the effective values rather than the file as written.
+    root_value: Value,
+    adjust: Option<Adjust>,
+    chains: Mutex<HashMap<PathBuf, Arc<[PathBuf]>>>,
+    layers: Mutex<HashMap<Arc<[PathBuf]>, Arc<ConfigLayer>>>,
+}
+
+/// The effective config for one chain of local config files.
+pub struct ConfigLayer {
+    files: Arc<[PathBuf]>,
+    fingerprint: String,
+    config: Config,
+    analyzer: Mutex<Option<Arc<Analyzer>>>,
+}
+
+impl ConfigTree {
+    /// Open the tree rooted at `root_dir`. `root_file` is the root config;
+    /// when it is `None` or missing, the built-in defaults are used.
+    pub fn open(root_dir: impl Into<PathBuf>, root_file: Option<&Path>) -> anyhow::Result<Self> {
+        let root_dir = absolute(&root_dir.into());
+        let root_file = root_file.filter(|path| path.exists()).map(absolute);
+        let config: Config = match &root_file {
+            Some(path) => {
+                let value = packs::resolve(read_value(path)?)
+                    .with_context(|| format!("Failed to resolve config {}", path.display()))?;
+                serde_yaml::from_value(value)
+                    .with_context(|| format!("Failed to parse config {}", path.display()))?
+            }
+            None => Config::default(),
+        };
+        Ok(Self {
+            root_dir,
+            root_file,
+            root_value: serde_yaml::to_value(config)?,
+            adjust: None,
+            chains: Mutex::new(HashMap::new()),
+            layers: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Run `adjust` on every effective config after layering, e.g. to apply
+    /// command-line overrides.
+    pub fn with_adjust(
+        mut self,
+        adjust: impl Fn(&mut Config) -> anyhow::Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.adjust = Some(Box::new(adjust));
+        self
+    }
+
+    pub fn root_dir(&self) -> &Path {
+        &self.root_dir
+    }
+
+    pub fn root_file(&self) -> Option<&Path> {
+        self.root_file.as_deref()
+    }
+
+    /// The config for the config root itself.
+    pub fn root(&self) -> anyhow::Result<Arc<ConfigLayer>> {
+        let root_dir = self.root_dir.clone();
+        self.for_dir(&root_dir)
+    }
+
+    /// The config that applies to `path`, a file or directory.
+    pub fn for_path(&self, path: &Path) -> anyhow::Result<Arc<ConfigLayer>> {
+        let path = absolute(path);
+        let dir = if path.is_dir() {
+            path.as_path()
+        } else {
+            path.parent().unwrap_or(&path)
+        };
+        self.for_dir(dir)
+    }
+
+    /// Drop cached chains and layers so local files are read again.
+    pub fn invalidate(&self) {
+        lock(&self.chains).clear();
+        lock(&self.layers).clear();
+    }
+
+    fn for_dir(&self, dir: &Path) -> anyhow::Result<Arc<ConfigLayer>> {
+        let files = self.local_files(dir);
+        if let Some(layer) = lock(&self.layers).get(&files) {
+            return Ok(layer.clone());
+        }
+        let layer = Arc::new(self.build(files.clone())?);
+        lock(&self.layers).insert(files, layer.clone());
+        Ok(layer)
+    }
+
+    fn local_files(&self, dir: &Path) -> Arc<[PathBuf]> {
+        if let Some(files) = lock(&self.chains).get(dir) {
+            return files.clone();
+        }
+        let repo = repo_root(dir);
+        let stop = |ancestor: &Path| {
+            let at_root = ancestor == self.root_dir && (self.root_file.is_some() || repo.is_none());
+            at_root || Some(ancestor) == repo
+        };
+        let dirs: Vec<&Path> = match dir.ancestors().position(stop) {
+            Some(last) => dir.ancestors().take(last + 1).collect(),
+            None => vec![dir],
+        };
+        let files: Arc<[PathBuf]> = dirs
+            .iter()
+            .rev()
+            .filter_map(|dir| {
+                LOCAL_CONFIG_FILES
+                    .iter()
+                    .map(|name| dir.join(name))
+                    .find(|path| path.is_file())
+            })
+            .filter(|path| Some(path) != self.root_file.as_ref())
+            .collect();
+        lock(&self.chains).insert(dir.to_path_buf(), files.clone());
+        files
+    }
+
+    fn build(&self, files: Arc<[PathBuf]>) -> anyhow::Result<ConfigLayer> {
+        let mut value = self.root_value.clone();
+        let mut contents = Vec::new();
+        for file in files.iter() {
+            let text = fs::read_to_string(file)
+                .with_context(|| format!("Failed to read config {}", file.display()))?;
+            let overlay = serde_yaml::from_str(&text)
+                .with_context(|| format!("Failed to parse config {}", file.display()))?;
+            packs::layer(&mut value, overlay)
+                .with_context(|| format!("Failed to resolve config {}", file.display()))?;
+            contents.push(file.to_string_lossy().into_owned());
+            contents.push(text);
+        }
+        let mut config: Config =
+            serde_yaml::from_value(value).with_context(|| match files.last() {
+                Some(file) => format!("Failed to parse config {}", file.display()),
+                None => "Failed to parse root config".to_string(),
+            })?;
+        if let Some(adjust) = &self.adjust {
+            adjust(&mut config)?;
+        }
+        // Word lists are read per layer; a local config may add its own.
+        // Root-level lists are covered by the caller's config hash, since
+        // their words end up in the config itself.
+        let dictionaries = config.spelling.load_dictionaries(&self.root_dir)?;
+        if !files.is_empty() {
+            contents.extend(dictionaries);
+        }
+        let fingerprint = if files.is_empty() {
+            String::new()
+        } else {
+            let parts: Vec<&[u8]> = contents.iter().map(|part| part.as_bytes()).collect();
+            hex_digest(&parts)[..16].to_string()
+        };
+        Ok(ConfigLayer {
+            files,
+            fingerprint,
+            config,
+            analyzer: Mutex::new(None),
+        })
+    }
+}
+
+impl ConfigLayer {
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Local config files layered over the root config, outermost first.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Short digest of the local files' paths and contents; empty when only
+    /// the root config applies. Useful for keying caches.
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// The analyzer for this config, compiled on first use.
+    pub fn analyzer(&self) -> anyhow::Result<Arc<Analyzer>> {
+        let mut slot = lock(&self.analyzer);
+        if let Some(analyzer) = slot.as_ref() {
+            return Ok(analyzer.clone());
+        }
+        let analyzer =
+            Arc::new(Analyzer::new(self.config.clone()).map_err(
+                |err| match self.files.last() {
+                    Some(file) => anyhow!("{}: {err:#}", file.display()),
+                    None => err,
+                },
+            )?);
+        *slot = Some(analyzer.clone());
+        Ok(analyzer)
+    }
+}
+
+/// The nearest file called `name` in `start` or a directory above it, up to
+/// the repository root. Outside a repository only `start` is checked.
+pub fn find_upward(start: &Path, name: &Path) -> Option<PathBuf> {
+    let top = repo_root(start).unwrap_or(start);
+    for dir in start.ancestors() {
+        let candidate = dir.join(name);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if dir == top {
+            break;
+        }
+    }
+    None
+}
+
+/// The nearest directory at or above `dir` with a `.git` entry.
+fn repo_root(dir: &Path) -> Option<&Path> {
+    dir.ancestors().find(|dir| dir.join(".git").exists())
+}
+
+fn read_value(path: &Path) -> anyhow::Result<Value> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config {}", path.display()))?;
+    serde_yaml::from_str(&text)
+        .with_context(|| format!("Failed to parse config {}", path.display()))
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    std::env::current_dir()
+        .map(|cwd| cwd.join(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("toneguard-discovery-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_local_files_layer_from_root_down() {
+        let root = temp_root("layers");
+        fs::create_dir_all(root.join("docs/team/deep")).unwrap();
+        fs::write(
+            root.join("style.yml"),
+            "buzzwords:\n  throttle: [leverage, robust]\nlimits:\n  em_dashes_per_paragraph: 2\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/.toneguard.yml"),
+            "buzzwords:\n  throttle:\n    add: [synergy]\nlimits:\n  bold_spans_per_paragraph: 5\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/team/.toneguard.yaml"),
+            "buzzwords:\n  throttle:\n    remove: [robust]\nlimits:\n  em_dashes_per_paragraph: 0\n",
+        )
+        .unwrap();
+
+        let tree = ConfigTree::open(&root, Some(&root.join("style.yml"))).unwrap();
+        let top = tree.root().unwrap();
+        assert!(top.files().is_empty());
+        assert!(top.fingerprint().is_empty());
+        assert_eq!(top.config().buzzwords.throttle, ["leverage", "robust"]);
+
+        let deep = tree.for_path(&root.join("docs/team/deep/page.md")).unwrap();
+        assert_eq!(deep.files().len(), 2);
+        assert_eq!(deep.config().buzzwords.throttle, ["leverage", "synergy"]);
+        assert_eq!(deep.config().limits.em_dashes_per_paragraph, 0);
+        assert_eq!(deep.config().limits.bold_spans_per_paragraph, 5);
+        assert!(!deep.fingerprint().is_empty());
+        assert!(Arc::ptr_eq(
+            &deep,
+            &tree.for_path(&root.join("docs/team/other.md")).unwrap()
+        ));
+
+        let outside = tree.for_path(&std::env::temp_dir().join("x.md")).unwrap();
+        assert!(outside.files().is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_local_files_found_walking_up_to_repo_root() {
+        let root = temp_root("upward");
+        fs::create_dir_all(root.join("repo/.git")).unwrap();
+        fs::create_dir_all(root.join("repo/docs/sub")).unwrap();
+        fs::write(
+            root.join(".toneguard.yml"),
+            "limits:\n  em_dashes_per_paragraph: 7\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("repo/docs/.toneguard.yml"),
+            "buzzwords:\n  throttle:\n    remove: [leverage]\n",
+        )
+        .unwrap();
+
+        // No root config: the tree is rooted where the command ran.
+        let tree = ConfigTree::open(root.join("repo/docs/sub"), None).unwrap();
+        let here = tree.root().unwrap();
+        assert_eq!(here.files(), [root.join("repo/docs/.toneguard.yml")]);
+        assert!(!here
+            .config()
+            .buzzwords
+            .throttle
+            .contains(&"leverage".to_string()));
+        assert_ne!(here.config().limits.em_dashes_per_paragraph, 7);
+
+        assert_eq!(
+            find_upward(&root.join("repo/docs/sub"), Path::new(".toneguard.yml")),
+            Some(root.join("repo/docs/.toneguard.yml"))
+        );
+        assert_eq!(
+            find_upward(&root.join("repo/docs/sub"), Path::new("style.yml")),
+            None
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_walks_stop_at_the_config_root_outside_a_repo() {
+        let root = temp_root("norepo");
+        fs::create_dir_all(root.join("work/docs")).unwrap();
+        fs::create_dir_all(root.join("other")).unwrap();
+        fs::write(
+            root.join("style.yml"),
+            "limits:\n  em_dashes_per_paragraph: 7\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join(".toneguard.yml"),
+            "limits:\n  em_dashes_per_paragraph: 7\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("work/.toneguard.yml"),
+            "limits:\n  em_dashes_per_paragraph: 3\n",
+        )
+        .unwrap();
+
+        // No root config: the tree is rooted where the command ran.
+        let tree = ConfigTree::open(root.join("work"), None).unwrap();
+        let docs = tree.for_path(&root.join("work/docs/a.md")).unwrap();
+        assert_eq!(docs.files(), [root.join("work/.toneguard.yml")]);
+        let other = tree.for_path(&root.join("other/a.md")).unwrap();
+        assert!(other.files().is_empty());
+
+        assert_eq!(
+            find_upward(&root.join("work/docs"), Path::new("style.yml")),
+            None
+        );
+        assert_eq!(
+            find_upward(&root.join("work"), Path::new(".toneguard.yml")),
+            Some(root.join("work/.toneguard.yml"))
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_word_lists_load_per_layer() {
+        let root = temp_root("words");
+        fs::create_dir_all(root.join(".toneguard")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join(".toneguard/words.txt"),
+            "frobnicate # our verb\n\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/.toneguard.yml"),
+            "spelling:\n  dictionaries:\n    add: [docs/words.txt]\n",
+        )
+        .unwrap();
+        fs::write(root.join("docs/words.txt"), "grommet\n").unwrap();
+
+        let tree = ConfigTree::open(&root, None).unwrap();
+        assert_eq!(tree.root().unwrap().config().spelling.words, ["frobnicate"]);
+        let docs = tree.for_path(&root.join("docs/a.md")).unwrap();
+        assert_eq!(docs.config().spelling.words, ["frobnicate", "grommet"]);
+
+        let before = docs.fingerprint().to_string();
+        fs::write(root.join("docs/words.txt"), "grommets\n").unwrap();
+        tree.invalidate();
+        let docs = tree.for_path(&root.join("docs/a.md")).unwrap();
+        assert_ne!(docs.fingerprint(), before);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_invalid_local_config_names_the_file() {
+        let root = temp_root("invalid");
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/.toneguard.yml"),
+            "extends: [toneguard:nope]\n",
+        )
+        .unwrap();
+        let tree = ConfigTree::open(&root, None).unwrap();
+        let Err(err) = tree.for_path(&root.join("docs/a.md")) else {
+            panic!("bad pack name should fail");
+        };
+        assert!(format!("{err:#}").contains(".toneguard.yml"));
+        assert!(tree.for_path(&root.join("a.md")).is_ok());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
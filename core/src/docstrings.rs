@@ -0,0 +1,462 @@
+//! Prose lint for doc comments and docstrings in source files.
+//!
+//! Rust `///`, `//!`, `/** */` and `/*! */` comments, JSDoc blocks in
+//! TypeScript and JavaScript, and Python docstrings are pulled out of the
+//! source into one Markdown document per file. Each extracted line keeps its
+//! source offset, so diagnostics, spans and fixes are mapped back onto the
+//! original file after analysis.
+//!
+//! Analysis runs with the `code-docs` profile. The analyzer provides a
+//! built-in version of it with the document structure checks turned off;
+//! declaring a profile with that name in the config replaces it.
+
+use std::path::Path;
+
+use proc_macro2::LineColumn;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use tree_sitter::{Node, Parser};
+
+use crate::{Analyzer, DocumentReport, Location};
+
+/// Profile used for doc comments unless a config profile glob matches.
+pub const CODE_DOCS_PROFILE: &str = "code-docs";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceLanguage {
+    Rust,
+    TypeScript,
+    Tsx,
+    JavaScript,
+    Python,
+}
+
+impl SourceLanguage {
+    fn for_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "rs" => Some(Self::Rust),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "py" => Some(Self::Python),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `path` is a source file whose doc comments can be linted.
+pub fn is_source_path(path: &Path) -> bool {
+    SourceLanguage::for_path(path).is_some()
+}
+
+/// A run of extracted text copied verbatim from the source.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    text_start: usize,
+    source_start: usize,
+    len: usize,
+}
+
+/// Doc comment prose extracted from one source file.
+#[derive(Debug, Clone, Default)]
+pub struct SourceDocs {
+    /// Markdown built from the doc comments, one paragraph group per comment.
+    pub text: String,
+    segments: Vec<Segment>,
+    last_line: Option<usize>,
+    /// Byte offset of each source line.
+    line_starts: Vec<usize>,
+}
+
+impl SourceDocs {
+    /// Extract doc comments from `source`, or `None` if `path` is not a
+    /// supported source file.
+    pub fn extract(path: &Path, source: &str) -> Option<Self> {
+        let mut docs = Self {
+            line_starts: line_starts(source),
+            ..Self::default()
+        };
+        match SourceLanguage::for_path(path)? {
+            SourceLanguage::Rust => docs.extract_rust(source),
+            SourceLanguage::Python => docs.extract_python(source),
+            language => docs.extract_jsdoc(source, language),
+        }
+        Some(docs)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Append one line of prose found at `source_start`. Lines that do not
+    /// follow the previous one in the source start a new paragraph.
+    fn push_line(&mut self, source_start: usize, content: &str) {
+        let line = self.line_of(source_start);
+        if !self.text.is_empty() && self.last_line.is_none_or(|last| last + 1 != line) {
+            self.text.push('\n');
+        }
+        self.last_line = Some(line);
+        if !content.is_empty() {
+            self.segments.push(Segment {
+                text_start: self.text.len(),
+                source_start,
+                len: content.len(),
+            });
+        }
+        self.text.push_str(content);
+        self.text.push('\n');
+    }
+
+    /// End the current comment so the next one starts a new paragraph.
+    fn break_paragraph(&mut self) {
+        self.last_line = None;
+    }
+
+    /// Add the lines of a block comment body (`source[start..end]`),
+    /// dropping leading `*` decorations.
+    fn push_block(&mut self, source: &str, start: usize, end: usize, skip: fn(&str) -> bool) {
+        let mut offset = start;
+        for raw in source[start..end].split_inclusive('\n') {
+            let line = raw.trim_end_matches(['\n', '\r']);
+            let indent = line.len() - line.trim_start().len();
+            let mut content_start = offset + indent;
+            let mut content = line.trim_start();
+            if let Some(rest) = content.strip_prefix('*') {
+                content_start += 1;
+                content = rest;
+            }
+            if let Some(rest) = content.strip_prefix(' ') {
+                content_start += 1;
+                content = rest;
+            }
+            let content = content.trim_end();
+            if !skip(content) {
+                self.push_line(content_start, content);
+            }
+            offset += raw.len();
+        }
+        self.break_paragraph();
+    }
+
+    fn extract_rust(&mut self, source: &str) {
+        let Ok(file) = syn::parse_file(source) else {
+            return;
+        };
+        let mut visitor = DocAttrVisitor::default();
+        visitor.visit_file(&file);
+        visitor
+            .spans
+            .sort_by_key(|(start, _)| (start.line, start.column));
+        visitor.spans.dedup();
+
+        let line_count = self.line_starts.len();
+        let offset_of = |line_starts: &[usize], at: LineColumn| {
+            let line_start = line_starts[at.line - 1];
+            source[line_start..]
+                .char_indices()
+                .nth(at.column)
+                .map_or(source.len(), |(idx, _)| line_start + idx)
+        };
+        for (start, end) in visitor.spans {
+            if start.line == 0 || start.line > line_count {
+                continue;
+            }
+            let begin = offset_of(&self.line_starts, start);
+            let rest = &source[begin..];
+            if rest.starts_with("///") || rest.starts_with("//!") {
+                let line_end = rest.find('\n').map_or(source.len(), |idx| begin + idx);
+                let mut content_start = begin + 3;
+                if source[content_start..line_end].starts_with(' ') {
+                    content_start += 1;
+                }
+                let content = source[content_start..line_end].trim_end();
+                self.push_line(content_start, content);
+            } else if rest.starts_with("/**") || rest.starts_with("/*!") {
+                let close = offset_of(&self.line_starts, end)
+                    .saturating_sub(2)
+                    .max(begin + 3);
+                self.push_block(source, begin + 3, close, |_| false);
+            }
+            // Anything else is a `#[doc = "..."]` attribute, not comment prose.
+        }
+    }
+
+    fn extract_jsdoc(&mut self, source: &str, language: SourceLanguage) {
+        let mut parser = Parser::new();
+        let lang = match language {
+            SourceLanguage::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
+            SourceLanguage::Tsx => tree_sitter_typescript::LANGUAGE_TSX,
+            _ => tree_sitter_javascript::LANGUAGE,
+        };
+        if parser.set_language(&lang.into()).is_err() {
+            return;
+        }
+        let Some(tree) = parser.parse(source, None) else {
+            return;
+        };
+        let mut comments = Vec::new();
+        collect_nodes(tree.root_node(), &mut |node| {
+            if node.kind() == "comment" {
+                let text = &source[node.byte_range()];
+                if text.starts_with("/**") && !text.starts_with("/**/") && text.len() >= 5 {
+                    comments.push((node.start_byte(), node.end_byte()));
+                }
+            }
+        });
+        for (start, end) in comments {
+            // JSDoc tag lines (`@param {T} name`) are mostly identifiers.
+            self.push_block(source, start + 3, end - 2, |line| line.starts_with('@'));
+        }
+    }
+
+    fn extract_python(&mut self, source: &str) {
+        let mut parser = Parser::new();
+        if parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .is_err()
+        {
+            return;
+        }
+        let Some(tree) = parser.parse(source, None) else {
+            return;
+        };
+        let mut bodies = Vec::new();
+        collect_nodes(tree.root_node(), &mut |node| {
+            let owner = match node.kind() {
+                "module" => Some(node),
+                "block" => node
+                    .parent()
+                    .filter(|p| matches!(p.kind(), "function_definition" | "class_definition"))
+                    .map(|_| node),
+                _ => None,
+            };
+            if let Some(body) = owner.and_then(docstring_body) {
+                bodies.push(body);
+            }
+        });
+        for (start, end) in bodies {
+            self.push_block(source, start, end, |line| {
+                line.starts_with(">>>") || line.starts_with("...")
+            });
+        }
+    }
+
+    /// 1-based source line of `offset`.
+    fn line_of(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset)
+    }
+
+    /// Line and column of `offset` in `source`.
+    fn location(&self, source: &str, offset: usize) -> Location {
+        let line = self.line_of(offset);
+        let line_start = self
+            .line_starts
+            .get(line.saturating_sub(1))
+            .copied()
+            .unwrap_or(0);
+        let column = source[line_start..offset].chars().count() + 1;
+        Location { line, column }
+    }
+
+    /// Map an offset in [`SourceDocs::text`] to the source file. `end` selects
+    /// the segment an offset ends rather than starts.
+    fn source_offset(&self, offset: usize, end: bool) -> Option<usize> {
+        let idx = self.segments.partition_point(|seg| {
+            if end {
+                seg.text_start + seg.len < offset
+            } else {
+                seg.text_start + seg.len <= offset
+            }
+        });
+        let seg = self.segments.get(idx)?;
+        let within = offset.checked_sub(seg.text_start)?;
+        (within <= seg.len).then_some(seg.source_start + within)
+    }
+
+    fn same_segment(&self, start: usize, end: usize) -> bool {
+        self.segments
+            .iter()
+            .any(|seg| seg.text_start <= start && end <= seg.text_start + seg.len)
+    }
+
+    /// Rewrite spans, locations and fixes in `report` from extracted text to
+    /// `source`. Fixes that would cross a comment line boundary are dropped.
+    pub fn map_report(&self, source: &str, mut report: DocumentReport) -> DocumentReport {
+        for diag in &mut report.diagnostics {
+            let (start, end) = diag.span;
+            let source_start = self.source_offset(start, false).unwrap_or(0);
+            let source_end = self
+                .source_offset(end, true)
+                .unwrap_or(source_start)
+                .max(source_start);
+            diag.span = (source_start, source_end);
+            diag.location = self.location(source, source_start);
+            if let Some(fix) = &mut diag.fix {
+                let mappable = fix
+                    .edits
+                    .iter()
+                    .all(|edit| self.same_segment(edit.span.0, edit.span.1));
+                if mappable {
+                    for edit in &mut fix.edits {
+                        let edit_start = self.source_offset(edit.span.0, false).unwrap_or(0);
+                        edit.span = (edit_start, edit_start + (edit.span.1 - edit.span.0));
+                    }
+                } else {
+                    diag.fix = None;
+                }
+            }
+        }
+        for section in &mut report.sections {
+            let (start, end) = section.span;
+            let source_start = self.source_offset(start, false).unwrap_or(0);
+            let source_end = self
+                .source_offset(end, true)
+                .unwrap_or(source_start)
+                .max(source_start);
+            section.span = (source_start, source_end);
+            section.location = self.location(source, source_start);
+        }
+        report
+    }
+}
+
+/// Lint the doc comments of the source file at `path` with `profile`.
+/// Returns `None` for files that are not supported source files.
+pub fn analyze_source(
+    analyzer: &Analyzer,
+    path: &Path,
+    source: &str,
+    profile: &str,
+) -> anyhow::Result<Option<DocumentReport>> {
+    let Some(docs) = SourceDocs::extract(path, source) else {
+        return Ok(None);
+    };
+    let report = analyzer.analyze_profile_name(&docs.text, profile)?;
+    Ok(Some(docs.map_report(source, report)))
+}
+
+/// Profile for a source file: a config profile whose globs match the path,
+/// otherwise [`CODE_DOCS_PROFILE`].
+pub fn profile_for_path<'a>(analyzer: &'a Analyzer, relative_path: &str) -> &'a str {
+    let profile = analyzer.profile_for_path(relative_path);
+    if profile == analyzer.default_profile() {
+        CODE_DOCS_PROFILE
+    } else {
+        profile
+    }
+}
+
+#[derive(Default)]
+struct DocAttrVisitor {
+    spans: Vec<(LineColumn, LineColumn)>,
+}
+
+impl<'ast> Visit<'ast> for DocAttrVisitor {
+    fn visit_attribute(&mut self, attr: &'ast syn::Attribute) {
+        if attr.path().is_ident("doc") {
+            let span = attr.span();
+            self.spans.push((span.start(), span.end()));
+        }
+        syn::visit::visit_attribute(self, attr);
+    }
+}
+
+fn line_starts(source: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect()
+}
+
+fn collect_nodes<'a>(node: Node<'a>, visit: &mut impl FnMut(Node<'a>)) {
+    visit(node);
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_nodes(child, visit);
+    }
+}
+
+/// Byte range inside the quotes of the docstring opening `body`, if any.
+fn docstring_body(body: Node) -> Option<(usize, usize)> {
+    let first = body.named_child(0)?;
+    if first.kind() != "expression_statement" {
+        return None;
+    }
+    let string = first.named_child(0)?;
+    if string.kind() != "string" {
+        return None;
+    }
+    let mut cursor = string.walk();
+    let children: Vec<Node> = string.named_children(&mut cursor).collect();
+    let open = children.iter().find(|c| c.kind() == "string_start")?;
+    let close = children.iter().rev().find(|c| c.kind() == "string_end")?;
+    Some((open.end_byte(), close.start_byte()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, Diagnostic};
+
+    fn lint(name: &str, source: &str) -> Vec<Diagnostic> {
+        let analyzer = Analyzer::new(Config::default()).unwrap();
+        analyze_source(&analyzer, Path::new(name), source, CODE_DOCS_PROFILE)
+            .unwrap()
+            .unwrap()
+            .diagnostics
+    }
+
+    fn snippets(source: &str, diagnostics: &[Diagnostic]) -> Vec<String> {
+        diagnostics
+            .iter()
+            .map(|d| source[d.span.0..d.span.1].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_doc_comments_map_to_source() {
+        let source = "/// Parses the input.\n///\n/// We leverage a robust parser here.\nfn parse() {}\n\nconst S: &str = \"/// leverage\";\n";
+        let diagnostics = lint("lib.rs", source);
+        let found = snippets(source, &diagnostics);
+        assert!(
+            found.iter().any(|s| s.eq_ignore_ascii_case("leverage")),
+            "{found:?}"
+        );
+        let leverage = diagnostics
+            .iter()
+            .find(|d| source[d.span.0..d.span.1].eq_ignore_ascii_case("leverage"))
+            .unwrap();
+        assert_eq!(leverage.location.line, 3);
+        assert_eq!(
+            leverage.span.0,
+            source.find("leverage").unwrap(),
+            "string literal contents are not docs"
+        );
+    }
+
+    #[test]
+    fn test_fixes_apply_to_source() {
+        let source = "/// We leverage caching.\nfn cached() {}\n";
+        let diagnostics = lint("lib.rs", source);
+        let outcome = crate::apply_fixes(source, &diagnostics, false);
+        assert!(outcome.applied > 0, "{diagnostics:?}");
+        assert!(
+            outcome.text.starts_with("/// We use caching.\n"),
+            "{}",
+            outcome.text
+        );
+    }
+
+    #[test]
+    fn test_jsdoc_and_docstrings() {
+        let ts = "/**\n * Leverage the cache.\n * @param key lookup key\n */\nexport function get(key: string) {}\n// leverage is fine in line comments\n";
+        let found = snippets(ts, &lint("get.ts", ts));
+        assert_eq!(found.len(), 1, "{found:?}");
+        assert!(found[0].eq_ignore_ascii_case("leverage"));
+
+        let py = "def get(key):\n    \"\"\"Leverage the cache.\n\n    >>> get('leverage')\n    \"\"\"\n    return 'leverage'\n";
+        let found = snippets(py, &lint("get.py", py));
+        assert_eq!(found.len(), 1, "{found:?}");
+        assert!(found[0].eq_ignore_ascii_case("leverage"));
+    }
+}
@@ -0,0 +1,376 @@
+//! Near-duplicate paragraphs across a set of documents.
+//!
+//! Paragraphs are normalised (lowercased, punctuation dropped, whitespace
+//! collapsed) and cut into overlapping word shingles. A MinHash signature of
+//! each shingle set, split into bands, proposes candidate pairs without
+//! comparing every paragraph with every other; each candidate is confirmed
+//! with the exact Jaccard similarity of the two shingle sets. Confirmed pairs
+//! join into clusters, and only clusters spanning more than one file are
+//! reported: repeats inside a single file are left to
+//! `repetition/duplicate-sentence`.
+//!
+//! Banding finds pairs at the default threshold almost surely; at much lower
+//! thresholds some similar pairs are never proposed.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::markdown::{BlockKind, MarkdownDoc};
+use crate::{byte_to_location, normalize_sentence, Category, DisabledRanges};
+
+/// Rule ID reported for each copy of a duplicated paragraph.
+pub const RULE: &str = "repetition/duplicate-paragraph";
+
+const SIGNATURE_LEN: usize = 63;
+const BAND_ROWS: usize = 3;
+const SNIPPET_CHARS: usize = 80;
+
+/// Settings for the cross-document duplicate pass of `dwg` lint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DuplicateConfig {
+    pub enabled: bool,
+    /// Paragraphs with fewer words are skipped.
+    pub min_words: usize,
+    /// Lowest Jaccard similarity of two paragraphs' shingle sets for them to
+    /// count as duplicates.
+    pub threshold: f32,
+    /// Words per shingle.
+    pub shingle_size: usize,
+    /// Snippets that are shared on purpose. Paragraphs containing one are
+    /// skipped; matching ignores case and punctuation.
+    pub allow: Vec<String>,
+}
+
+impl Default for DuplicateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_words: 20,
+            threshold: 0.7,
+            shingle_size: 3,
+            allow: Vec::new(),
+        }
+    }
+}
+
+/// Where one copy of a duplicated paragraph sits.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DuplicateLocation {
+    pub path: String,
+    pub line: usize,
+    pub span: (usize, usize),
+}
+
+/// Paragraphs that are near-copies of each other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCluster {
+    /// Lowest similarity among the confirmed pairs joining the cluster.
+    pub similarity: f32,
+    /// Words in the first copy.
+    pub words: usize,
+    /// Start of the first copy.
+    pub snippet: String,
+    /// Every copy, in path and position order.
+    pub locations: Vec<DuplicateLocation>,
+}
+
+struct Paragraph {
+    location: DuplicateLocation,
+    words: usize,
+    snippet: String,
+    /// Sorted, deduplicated shingle hashes.
+    shingles: Vec<u64>,
+    signature: Vec<u64>,
+}
+
+/// Collects paragraphs file by file, then groups the near-duplicates.
+pub struct DuplicateFinder {
+    config: DuplicateConfig,
+    allow: Vec<String>,
+    paragraphs: Vec<Paragraph>,
+}
+
+impl DuplicateFinder {
+    pub fn new(config: &DuplicateConfig) -> Self {
+        Self {
+            config: config.clone(),
+            allow: config
+                .allow
+                .iter()
+                .map(|snippet| normalize_sentence(snippet))
+                .filter(|snippet| !snippet.is_empty())
+                .collect(),
+            paragraphs: Vec::new(),
+        }
+    }
+
+    /// Add the paragraphs of the Markdown document `text` found at `path`.
+    /// Paragraphs where the `repetition` category or [`RULE`] is disabled
+    /// are skipped.
+    pub fn add(&mut self, path: &str, text: &str) {
+        let doc = MarkdownDoc::parse(text);
+        let filtered = DisabledRanges::new(text, &doc);
+        for block in &doc.blocks {
+            if block.kind != BlockKind::Paragraph {
+                continue;
+            }
+            let (start, end) = block.span;
+            let location = byte_to_location(text, start);
+            if filtered.is_category_disabled(start, Category::Repetition)
+                || filtered.is_rule_disabled(start, RULE)
+                || filtered.is_line_ignored(location.line)
+            {
+                continue;
+            }
+            let normalised = normalize_sentence(&text[start..end]);
+            if self
+                .allow
+                .iter()
+                .any(|snippet| normalised.contains(snippet))
+            {
+                continue;
+            }
+            let words: Vec<&str> = normalised.split(' ').collect();
+            if words.len() < self.config.min_words.max(1) {
+                continue;
+            }
+            let mut shingles: Vec<u64> = words
+                .windows(self.config.shingle_size.clamp(1, words.len()))
+                .map(|shingle| fnv1a(shingle.join(" ").as_bytes()))
+                .collect();
+            shingles.sort_unstable();
+            shingles.dedup();
+            let signature = (0..SIGNATURE_LEN as u64)
+                .map(|seed| {
+                    shingles
+                        .iter()
+                        .map(|&hash| mix(hash ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15)))
+                        .min()
+                        .unwrap_or(u64::MAX)
+                })
+                .collect();
+            let snippet = snippet(&text[start..end]);
+            self.paragraphs.push(Paragraph {
+                location: DuplicateLocation {
+                    path: path.to_string(),
+                    line: location.line,
+                    span: (start, end),
+                },
+                words: words.len(),
+                snippet,
+                shingles,
+                signature,
+            });
+        }
+    }
+
+    /// Clusters of near-duplicate paragraphs that span at least two files,
+    /// ordered by their first location.
+    pub fn clusters(&self) -> Vec<DuplicateCluster> {
+        let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+        for (index, paragraph) in self.paragraphs.iter().enumerate() {
+            for (band, rows) in paragraph.signature.chunks(BAND_ROWS).enumerate() {
+                let key = rows.iter().fold(0u64, |acc, &row| mix(acc ^ row));
+                buckets.entry((band, key)).or_default().push(index);
+            }
+        }
+        let mut candidates = BTreeSet::new();
+        for members in buckets.values() {
+            for (i, &a) in members.iter().enumerate() {
+                for &b in &members[i + 1..] {
+                    candidates.insert((a.min(b), a.max(b)));
+                }
+            }
+        }
+
+        let mut parent: Vec<usize> = (0..self.paragraphs.len()).collect();
+        let mut similarity: HashMap<usize, f32> = HashMap::new();
+        for (a, b) in candidates {
+            let score = jaccard(&self.paragraphs[a].shingles, &self.paragraphs[b].shingles);
+            if score < self.config.threshold {
+                continue;
+            }
+            let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+            let lowest = [similarity.get(&root_a), similarity.get(&root_b)]
+                .into_iter()
+                .flatten()
+                .fold(score, |low, &value| low.min(value));
+            parent[root_b] = root_a;
+            similarity.remove(&root_b);
+            similarity.insert(root_a, lowest);
+        }
+
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for index in 0..self.paragraphs.len() {
+            let root = find(&mut parent, index);
+            if similarity.contains_key(&root) {
+                groups.entry(root).or_default().push(index);
+            }
+        }
+        let mut clusters: Vec<DuplicateCluster> = groups
+            .into_iter()
+            .filter_map(|(root, members)| {
+                let paths: BTreeSet<&str> = members
+                    .iter()
+                    .map(|&index| self.paragraphs[index].location.path.as_str())
+                    .collect();
+                if paths.len() < 2 {
+                    return None;
+                }
+                let first = &self.paragraphs[members[0]];
+                Some(DuplicateCluster {
+                    similarity: (similarity[&root] * 100.0).round() / 100.0,
+                    words: first.words,
+                    snippet: first.snippet.clone(),
+                    locations: members
+                        .iter()
+                        .map(|&index| self.paragraphs[index].location.clone())
+                        .collect(),
+                })
+            })
+            .collect();
+        clusters.sort_by(|a, b| {
+            let key = |cluster: &DuplicateCluster| {
+                let first = &cluster.locations[0];
+                (first.path.clone(), first.span)
+            };
+            key(a).cmp(&key(b))
+        });
+        clusters
+    }
+}
+
+/// The start of `paragraph` on one line, cut after a whole word.
+fn snippet(paragraph: &str) -> String {
+    let mut snippet = String::new();
+    for word in paragraph.split_whitespace() {
+        let len = snippet.chars().count() + word.chars().count() + 1;
+        if !snippet.is_empty() && len > SNIPPET_CHARS {
+            snippet.push('…');
+            break;
+        }
+        if !snippet.is_empty() {
+            snippet.push(' ');
+        }
+        snippet.push_str(word);
+    }
+    snippet
+}
+
+fn find(parent: &mut [usize], mut index: usize) -> usize {
+    while parent[index] != index {
+        parent[index] = parent[parent[index]];
+        index = parent[index];
+    }
+    index
+}
+
+fn jaccard(a: &[u64], b: &[u64]) -> f32 {
+    let (mut i, mut j, mut shared) = (0, 0, 0usize);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    let union = a.len() + b.len() - shared;
+    if union == 0 {
+        0.0
+    } else {
+        shared as f32 / union as f32
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// SplitMix64 finaliser.
+fn mix(mut value: u64) -> u64 {
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTRO: &str = "This guide walks you through installing the toolkit, configuring your first project and running the checks that keep your documentation consistent across every team.";
+
+    #[test]
+    fn test_near_duplicates_cluster_across_files() {
+        let mut finder = DuplicateFinder::new(&DuplicateConfig::default());
+        finder.add("a.md", &format!("# A\n\n{INTRO}\n\nSomething else entirely about the first page and its particular topic here.\n"));
+        finder.add(
+            "b.md",
+            &format!("# B\n\n{}\n", INTRO.replace("every team", "every teams")),
+        );
+        finder.add("c.md", &format!("Intro.\n\n{INTRO}\n"));
+        let clusters = finder.clusters();
+        assert_eq!(clusters.len(), 1);
+        let paths: Vec<&str> = clusters[0]
+            .locations
+            .iter()
+            .map(|location| location.path.as_str())
+            .collect();
+        assert_eq!(paths, ["a.md", "b.md", "c.md"]);
+        assert_eq!(clusters[0].locations[0].line, 3);
+        assert!(clusters[0].similarity >= 0.8 && clusters[0].similarity < 1.0);
+        assert!(clusters[0].snippet.starts_with("This guide walks"));
+        let cut = clusters[0].snippet.strip_suffix('…').unwrap();
+        assert!(cut.chars().count() <= SNIPPET_CHARS);
+        assert!(INTRO[cut.len()..].starts_with(' '));
+    }
+
+    #[test]
+    fn test_one_word_edits_cluster() {
+        let prerequisites = |word: &str| {
+            format!("## Prerequisites\n\nBefore you start, install the command line tools, sign in with your team account, and copy the sample {word} file into the project root.\n")
+        };
+        let mut finder = DuplicateFinder::new(&DuplicateConfig::default());
+        finder.add("a.md", &prerequisites("settings"));
+        finder.add("b.md", &prerequisites("config"));
+        let clusters = finder.clusters();
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].words, 24);
+        assert!(clusters[0].similarity >= 0.7 && clusters[0].similarity < 1.0);
+    }
+
+    #[test]
+    fn test_allowlisted_and_single_file_repeats_are_skipped() {
+        let text = format!("{INTRO}\n\n{INTRO}\n");
+        let mut finder = DuplicateFinder::new(&DuplicateConfig::default());
+        finder.add("a.md", &text);
+        assert!(finder.clusters().is_empty());
+
+        let config = DuplicateConfig {
+            allow: vec!["Installing the toolkit,".into()],
+            ..Default::default()
+        };
+        let mut finder = DuplicateFinder::new(&config);
+        finder.add("a.md", INTRO);
+        finder.add("b.md", INTRO);
+        assert!(finder.clusters().is_empty());
+
+        let mut finder = DuplicateFinder::new(&DuplicateConfig::default());
+        finder.add("a.md", INTRO);
+        finder.add(
+            "b.md",
+            &format!("<!-- dwg:ignore repetition -->\n\n{INTRO}\n"),
+        );
+        assert!(finder.clusters().is_empty());
+        finder.add("c.md", INTRO);
+        assert_eq!(finder.clusters()[0].locations.len(), 2);
+    }
+}
@@ -0,0 +1,275 @@
+//! Per-document overrides under a `toneguard:` key in YAML frontmatter.
+//!
+//! Generated pages can carry their own exceptions without HTML comments in
+//! the body:
+//!
+//! ```yaml
+//! ---
+//! title: Release notes
+//! toneguard:
+//!   profile: changelog
+//!   type: generated
+//!   disable: [rule-of-three, tone/passive-density]
+//!   rules:
+//!     max_sentence_length: 40
+//! ---
+//! ```
+//!
+//! - `profile` replaces the profile matched by path
+//! - `type` applies a preset from `document_types:` in the config first
+//! - `disable` turns categories or rule IDs off for the whole document
+//! - `rules` takes the keys of a profile's `rules` and layers them on the
+//!   profile
+//!
+//! The block is checked against the config the way `config check` checks
+//! the config file. Problems are reported as `directive/invalid-frontmatter`
+//! and the keys they concern are skipped; the rest of the block applies.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::markdown::MarkdownDoc;
+use crate::rules::RuleSelector;
+use crate::validate::{self, ConfigIssue, ConfigIssueKind};
+use crate::{byte_to_location, Category, Config, Diagnostic, ProfileRules, Severity};
+
+/// The frontmatter key the overrides live under.
+pub const FRONTMATTER_KEY: &str = "toneguard";
+
+/// A preset under `document_types:` in the config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DocumentType {
+    /// Profile for documents of this type.
+    pub profile: Option<String>,
+    /// Categories and rule IDs turned off for documents of this type.
+    pub disable: Vec<String>,
+    /// Rule overrides on top of the profile, as under a profile's `rules`.
+    pub rules: Option<ProfileRules>,
+}
+
+/// The `toneguard:` block of a document's frontmatter.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DocumentOverrides {
+    /// Profile for this document, instead of the one matched by path.
+    pub profile: Option<String>,
+    /// Preset from `document_types:`, applied before the keys below.
+    #[serde(rename = "type")]
+    pub document_type: Option<String>,
+    /// Categories and rule IDs turned off for this document.
+    pub disable: Vec<String>,
+    /// Rule overrides on top of the profile, as under a profile's `rules`.
+    pub rules: Option<ProfileRules>,
+}
+
+/// A document's overrides with its type preset folded in.
+#[derive(Debug, Default)]
+pub(crate) struct DocumentSettings {
+    pub(crate) profile: Option<String>,
+    /// Rule overrides in the order they apply: type preset, then document.
+    pub(crate) rules: Vec<ProfileRules>,
+    disable: Vec<RuleSelector>,
+    /// The `severity_overrides` of `rules`, categories before rules.
+    pub(crate) severity_overrides: Vec<(RuleSelector, Severity)>,
+    /// Problems with the block, as `directive/invalid-frontmatter`.
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+impl DocumentSettings {
+    /// Read and check the overrides of the document. Unknown names are
+    /// reported and skipped.
+    pub(crate) fn read(text: &str, doc: &MarkdownDoc, config: &Config) -> Self {
+        let Some((offset, body)) = frontmatter_body(text, doc) else {
+            return Self::default();
+        };
+        if !body.contains(FRONTMATTER_KEY) {
+            return Self::default();
+        }
+        let (overrides, issues) = validate::document_overrides(body, config);
+        let diagnostics = issues
+            .iter()
+            .map(|issue| issue_diagnostic(text, offset, body, issue))
+            .collect();
+        let Some(overrides) = overrides else {
+            return Self {
+                diagnostics,
+                ..Self::default()
+            };
+        };
+        let preset = overrides
+            .document_type
+            .as_ref()
+            .and_then(|name| config.document_types.get(name))
+            .cloned()
+            .unwrap_or_default();
+        let rules: Vec<ProfileRules> = preset.rules.into_iter().chain(overrides.rules).collect();
+        let mut severity_overrides: Vec<(RuleSelector, Severity)> = rules
+            .iter()
+            .flat_map(|rules| &rules.severity_overrides)
+            .filter_map(|(name, severity)| Some((RuleSelector::parse(name)?, *severity)))
+            .collect();
+        severity_overrides.sort_by_key(|(selector, _)| selector.is_rule());
+        Self {
+            profile: overrides.profile.or(preset.profile),
+            rules,
+            disable: preset
+                .disable
+                .iter()
+                .chain(&overrides.disable)
+                .filter_map(|name| RuleSelector::parse(name))
+                .collect(),
+            severity_overrides,
+            diagnostics,
+        }
+    }
+
+    /// Report rule overrides the profile could not be compiled with, at the
+    /// `toneguard:` key. A block that already has problems reported is left
+    /// alone, since those are what broke the compile.
+    pub(crate) fn report_compile_error(&mut self, text: &str, doc: &MarkdownDoc, error: &str) {
+        if !self.diagnostics.is_empty() {
+            return;
+        }
+        let Some((offset, body)) = frontmatter_body(text, doc) else {
+            return;
+        };
+        let line = body
+            .lines()
+            .position(|line| line.starts_with(FRONTMATTER_KEY))
+            .map_or(0, |index| index + 1);
+        let issue = ConfigIssue {
+            kind: ConfigIssueKind::Invalid,
+            path: FRONTMATTER_KEY.into(),
+            line,
+            column: 1,
+            message: format!("the rules do not compile: {error}"),
+        };
+        self.diagnostics
+            .push(issue_diagnostic(text, offset, body, &issue));
+    }
+
+    /// Whether the document turned off the diagnostic's category or rule.
+    pub(crate) fn disables(&self, diag: &Diagnostic) -> bool {
+        self.disable
+            .iter()
+            .any(|selector| selector.matches(diag.category, &diag.rule))
+    }
+}
+
+fn issue_diagnostic(text: &str, offset: usize, body: &str, issue: &ConfigIssue) -> Diagnostic {
+    let start = offset + body_offset(body, issue.line, issue.column);
+    let end = text[start..]
+        .find('\n')
+        .map_or(text.len(), |len| start + len);
+    Diagnostic {
+        category: Category::Directive,
+        rule: "directive/invalid-frontmatter".into(),
+        severity: Severity::Warning,
+        message: format!(
+            "Invalid `{FRONTMATTER_KEY}` frontmatter: {}.",
+            issue.message
+        ),
+        suggestion: Some(
+            "Fix the key; the parts of the block that do not check out are skipped.".into(),
+        ),
+        location: byte_to_location(text, start),
+        span: (start, end),
+        snippet: text[start..end].trim_end().to_string(),
+        fix: None,
+    }
+}
+
+/// Byte offset in `body` of a 1-based line and character column; zero when
+/// the position is unknown.
+fn body_offset(body: &str, line: usize, column: usize) -> usize {
+    if line == 0 {
+        return 0;
+    }
+    let line_start: usize = body
+        .split_inclusive('\n')
+        .take(line - 1)
+        .map(str::len)
+        .sum();
+    if line_start >= body.len() {
+        return 0;
+    }
+    body[line_start..]
+        .char_indices()
+        .take_while(|(_, ch)| *ch != '\n')
+        .nth(column.saturating_sub(1))
+        .map_or(line_start, |(index, _)| line_start + index)
+}
+
+/// The YAML between the frontmatter fences, with its offset in `text`.
+fn frontmatter_body<'a>(text: &'a str, doc: &MarkdownDoc) -> Option<(usize, &'a str)> {
+    let (start, end) = doc.frontmatter?;
+    let block = &text[start..end];
+    let body_start = if block.starts_with("---") {
+        block.find('\n').map_or(block.len(), |newline| newline + 1)
+    } else {
+        0
+    };
+    let mut body = &block[body_start..];
+    let trimmed = body.trim_end();
+    if let Some(newline) = trimmed.rfind('\n') {
+        if matches!(trimmed[newline + 1..].trim(), "---" | "...") {
+            body = &body[..newline + 1];
+        }
+    } else if matches!(trimmed.trim(), "---" | "...") {
+        body = "";
+    }
+    Some((start + body_start, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(text: &str, config: &Config) -> DocumentSettings {
+        DocumentSettings::read(text, &MarkdownDoc::parse(text), config)
+    }
+
+    #[test]
+    fn test_body_excludes_fences() {
+        let text = "---\ntitle: x\ntoneguard:\n  profile: docs\n---\n\nBody.\n";
+        let doc = MarkdownDoc::parse(text);
+        let (offset, body) = frontmatter_body(text, &doc).unwrap();
+        assert_eq!(offset, 4);
+        assert_eq!(body, "title: x\ntoneguard:\n  profile: docs\n");
+        assert_eq!(body_offset(body, 3, 3), body.find("profile").unwrap());
+    }
+
+    #[test]
+    fn test_type_preset_applies_before_document() {
+        let mut config = Config::default();
+        config.document_types.insert(
+            "generated".into(),
+            DocumentType {
+                profile: Some("reference".into()),
+                disable: vec!["spelling".into()],
+                rules: None,
+            },
+        );
+        let text = "---\ntoneguard:\n  type: generated\n  profile: docs\n  disable: [tone/passive-density, nope]\n---\n\nBody.\n";
+        let settings = settings(text, &config);
+        assert_eq!(settings.profile.as_deref(), Some("docs"));
+        assert_eq!(
+            settings.disable,
+            [
+                RuleSelector::Category(Category::Spelling),
+                RuleSelector::Rule("tone/passive-density".into()),
+            ]
+        );
+        assert!(settings.rules.is_empty());
+    }
+
+    #[test]
+    fn test_documents_without_block_have_no_settings() {
+        let config = Config::default();
+        let text = "---\ntitle: x\n---\n\nBody.\n";
+        let settings = settings(text, &config);
+        assert!(settings.profile.is_none() && settings.disable.is_empty());
+        assert!(settings.diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,422 @@
+//! Terminology glossary declared under `glossary:` in the config.
+//!
+//! Each term names a concept's preferred wording and the variants to avoid.
+//! Variants are reported as `terminology/discouraged-term` with the
+//! preferred wording as a fix. Terms without a preferred wording list
+//! interchangeable variants instead; with `consistency` on, a document that
+//! mixes them gets `terminology/inconsistent-term` on every use of the less
+//! common ones.
+//!
+//! ```yaml
+//! glossary:
+//!   terms:
+//!     - preferred: sign in
+//!       variants: [login, log in]
+//!       part_of_speech: verb
+//!     - preferred: ToneGuard
+//!       case: exact
+//!     - preferred: workspace
+//!       variants: [project]
+//!       profiles: [docs]
+//!     - variants: [email, e-mail]
+//! ```
+//!
+//! The part-of-speech hint looks only at the word before a match: a match
+//! that opens a sentence or follows `to`, a modal or a subject pronoun reads
+//! as a verb, anything else as a noun.
+
+use regex::{Regex, RegexBuilder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::markdown::{BlockKind, MarkdownDoc};
+use crate::{
+    byte_to_location, is_word_char, Applicability, Category, Diagnostic, DisabledRanges, Fix,
+    Severity,
+};
+
+/// Words after which a match reads as a verb.
+const VERB_CUES: &[&str] = &[
+    "to", "can", "cannot", "can't", "will", "won't", "must", "should", "shall", "may", "might",
+    "could", "would", "please", "then", "i", "you", "we", "they", "don't", "do", "not",
+];
+
+/// The `glossary:` section.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct GlossaryConfig {
+    /// Flag documents that mix the variants of a term without a preferred
+    /// wording.
+    pub consistency: bool,
+    pub terms: Vec<GlossaryTerm>,
+}
+
+impl Default for GlossaryConfig {
+    fn default() -> Self {
+        Self {
+            consistency: true,
+            terms: Vec::new(),
+        }
+    }
+}
+
+/// How a term's spelling is compared.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TermCase {
+    /// Match in any case; fixes keep a leading capital.
+    #[default]
+    Ignore,
+    /// Write the preferred wording exactly as given: other casings of it are
+    /// discouraged too, and variants match only as written.
+    Exact,
+}
+
+/// Which use of a variant the term is about.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum PartOfSpeech {
+    Noun,
+    Verb,
+}
+
+/// One `glossary.terms` entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct GlossaryTerm {
+    /// Wording to use. Without it, `variants` are interchangeable and only
+    /// the consistency check applies.
+    pub preferred: Option<String>,
+    /// Wordings to avoid, matched on word boundaries.
+    pub variants: Vec<String>,
+    pub case: TermCase,
+    /// Only flag variants used as this part of speech.
+    pub part_of_speech: Option<PartOfSpeech>,
+    /// Added to the message, e.g. why the variant is avoided.
+    pub note: Option<String>,
+    /// Profiles the term applies in. Empty means every profile.
+    pub profiles: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Form {
+    text: String,
+    regex: Regex,
+}
+
+/// A glossary term with its forms compiled. The preferred wording, when
+/// set, is the first form.
+#[derive(Debug, Clone)]
+pub(crate) struct CompiledTerm {
+    preferred: Option<String>,
+    forms: Vec<Form>,
+    case: TermCase,
+    part_of_speech: Option<PartOfSpeech>,
+    note: Option<String>,
+    profiles: Vec<String>,
+}
+
+impl CompiledTerm {
+    pub(crate) fn compile(term: &GlossaryTerm) -> anyhow::Result<Self> {
+        let preferred = term
+            .preferred
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty());
+        let variants: Vec<&str> = term
+            .variants
+            .iter()
+            .map(|text| text.trim())
+            .filter(|text| !text.is_empty())
+            .collect();
+        let name = preferred.or(variants.first().copied()).unwrap_or_default();
+        match preferred {
+            None if variants.len() < 2 => anyhow::bail!(
+                "glossary term `{name}` needs a `preferred` wording or at least two `variants`"
+            ),
+            Some(_) if variants.is_empty() && term.case == TermCase::Ignore => anyhow::bail!(
+                "glossary term `{name}` has no `variants`; add some or set `case: exact`"
+            ),
+            _ => {}
+        }
+        let mut forms = Vec::new();
+        for (i, text) in preferred.iter().chain(&variants).enumerate() {
+            // Under `exact`, the preferred form is found in any case so that
+            // miscasings can be reported.
+            let ignore_case = term.case == TermCase::Ignore || (i == 0 && preferred.is_some());
+            let regex = RegexBuilder::new(&form_pattern(text))
+                .case_insensitive(ignore_case)
+                .build()
+                .map_err(|e| anyhow::anyhow!("invalid glossary term `{text}`: {e}"))?;
+            forms.push(Form {
+                text: text.to_string(),
+                regex,
+            });
+        }
+        Ok(Self {
+            preferred: preferred.map(str::to_string),
+            forms,
+            case: term.case,
+            part_of_speech: term.part_of_speech,
+            note: term.note.clone(),
+            profiles: term.profiles.clone(),
+        })
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.forms[0].text
+    }
+
+    pub(crate) fn profiles(&self) -> &[String] {
+        &self.profiles
+    }
+
+    pub(crate) fn applies_to(&self, profile: &str) -> bool {
+        self.profiles.is_empty() || self.profiles.iter().any(|name| name == profile)
+    }
+
+    /// `target` spelled to fit in place of `matched`.
+    fn replacement(&self, target: &str, matched: &str) -> String {
+        let capitalised = matched.chars().next().is_some_and(char::is_uppercase);
+        let mut chars = target.chars();
+        match chars.next() {
+            Some(first) if self.case == TermCase::Ignore && capitalised => {
+                first.to_uppercase().chain(chars).collect()
+            }
+            _ => target.to_string(),
+        }
+    }
+}
+
+/// Escaped `text` with flexible whitespace, bounded by `\b` next to word
+/// characters.
+fn form_pattern(text: &str) -> String {
+    let body = text
+        .split_whitespace()
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(r"\s+");
+    let lead = if text.chars().next().is_some_and(is_word_char) {
+        r"\b"
+    } else {
+        ""
+    };
+    let trail = if text.chars().last().is_some_and(is_word_char) {
+        r"\b"
+    } else {
+        ""
+    };
+    format!("{lead}{body}{trail}")
+}
+
+/// Whether the match starting at `offset` in `sentence` reads as a verb.
+fn reads_as_verb(sentence: &str, offset: usize) -> bool {
+    let previous = sentence[..offset]
+        .split_whitespace()
+        .rev()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                .to_lowercase()
+        })
+        .find(|word| !word.is_empty());
+    match previous {
+        None => true,
+        Some(word) => VERB_CUES.contains(&word.as_str()),
+    }
+}
+
+/// One use of a term's form in the document.
+struct Occurrence {
+    span: (usize, usize),
+    form: usize,
+    matched: String,
+}
+
+/// Run the glossary terms enabled for the current profile.
+pub(crate) fn detect(
+    terms: &[CompiledTerm],
+    consistency: bool,
+    text: &str,
+    doc: &MarkdownDoc,
+    sentences: &[(String, usize, BlockKind)],
+    filtered: &DisabledRanges,
+) -> Vec<Diagnostic> {
+    let skipped: Vec<(usize, usize)> = doc
+        .inline_code
+        .iter()
+        .chain(&doc.link_destinations)
+        .copied()
+        .collect();
+    let mut diagnostics = Vec::new();
+    for term in terms {
+        let mut uses: Vec<Occurrence> = Vec::new();
+        for (sentence, base, _) in sentences {
+            let mut found: Vec<Occurrence> = Vec::new();
+            for (form, pattern) in term.forms.iter().enumerate() {
+                for hit in pattern.regex.find_iter(sentence) {
+                    found.push(Occurrence {
+                        span: (base + hit.start(), base + hit.end()),
+                        form,
+                        matched: hit.as_str().to_string(),
+                    });
+                }
+            }
+            // Longest match wins where forms overlap.
+            found.sort_by_key(|hit| (hit.span.0, std::cmp::Reverse(hit.span.1)));
+            let mut last_end = 0;
+            for hit in found {
+                let (start, end) = hit.span;
+                if start < last_end
+                    || skipped.iter().any(|&(s, e)| start < e && s < end)
+                    || filtered.is_category_disabled(start, Category::Terminology)
+                {
+                    continue;
+                }
+                last_end = end;
+                let location = byte_to_location(text, start);
+                if filtered.is_line_ignored(location.line) {
+                    continue;
+                }
+                let Some(preferred) = &term.preferred else {
+                    uses.push(hit);
+                    continue;
+                };
+                let miscased = hit.form == 0 && term.case == TermCase::Exact;
+                if hit.form == 0 && (!miscased || hit.matched == *preferred) {
+                    continue;
+                }
+                if let Some(part) = term.part_of_speech.filter(|_| hit.form != 0) {
+                    let verb = reads_as_verb(sentence, start - base);
+                    if verb != (part == PartOfSpeech::Verb) {
+                        continue;
+                    }
+                }
+                let replacement = term.replacement(preferred, &hit.matched);
+                let mut message = format!("Use `{replacement}` instead of `{}`.", hit.matched);
+                if let Some(note) = &term.note {
+                    message = format!("{message} {note}");
+                }
+                // The part-of-speech guess can be wrong, so such fixes need
+                // a look before they are applied.
+                let applicability = if term.part_of_speech.is_some() && !miscased {
+                    Applicability::Unsafe
+                } else {
+                    Applicability::Safe
+                };
+                diagnostics.push(Diagnostic {
+                    category: Category::Terminology,
+                    rule: "terminology/discouraged-term".into(),
+                    severity: Severity::Warning,
+                    message,
+                    suggestion: Some(format!("Replace with `{replacement}`.")),
+                    location,
+                    span: (start, end),
+                    snippet: hit.matched,
+                    fix: Some(Fix::replace((start, end), replacement, applicability)),
+                });
+            }
+        }
+        if consistency {
+            diagnostics.extend(inconsistent_uses(term, text, uses));
+        }
+    }
+    diagnostics
+}
+
+/// Flag uses of every form but the most common one, when a document uses
+/// more than one. Ties go to the form used first.
+fn inconsistent_uses(term: &CompiledTerm, text: &str, uses: Vec<Occurrence>) -> Vec<Diagnostic> {
+    let mut counts = vec![0usize; term.forms.len()];
+    let mut order: Vec<usize> = Vec::new();
+    for hit in &uses {
+        if counts[hit.form] == 0 {
+            order.push(hit.form);
+        }
+        counts[hit.form] += 1;
+    }
+    if order.len() < 2 {
+        return Vec::new();
+    }
+    let dominant = order
+        .iter()
+        .copied()
+        .reduce(|best, form| {
+            if counts[form] > counts[best] {
+                form
+            } else {
+                best
+            }
+        })
+        .unwrap_or_default();
+    let target = &term.forms[dominant].text;
+    let tied = order
+        .iter()
+        .any(|&form| form != dominant && counts[form] == counts[dominant]);
+    uses.into_iter()
+        .filter(|hit| hit.form != dominant)
+        .map(|hit| {
+            let replacement = term.replacement(target, &hit.matched);
+            Diagnostic {
+                category: Category::Terminology,
+                rule: "terminology/inconsistent-term".into(),
+                severity: Severity::Hint,
+                message: if tied {
+                    format!(
+                        "`{}` and `{target}` name the same thing; no form is more common here, so this follows the first one used, `{target}`.",
+                        hit.matched
+                    )
+                } else {
+                    format!(
+                        "`{}` and `{target}` name the same thing; this document mostly uses `{target}`.",
+                        hit.matched
+                    )
+                },
+                suggestion: Some(format!("Replace with `{replacement}`.")),
+                location: byte_to_location(text, hit.span.0),
+                span: hit.span,
+                snippet: hit.matched,
+                fix: Some(Fix::replace(hit.span, replacement, Applicability::Unsafe)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_pattern_allows_flexible_whitespace() {
+        let regex = Regex::new(&form_pattern("log in")).unwrap();
+        assert!(regex.is_match("then log\n in"));
+        assert!(!regex.is_match("blog in"));
+    }
+
+    #[test]
+    fn test_part_of_speech_cue() {
+        assert!(reads_as_verb("Login to the dashboard.", 0));
+        assert!(reads_as_verb("You must login first.", 9));
+        assert!(!reads_as_verb("Open the login page.", 9));
+    }
+
+    #[test]
+    fn test_compile_rejects_empty_terms() {
+        let lone = GlossaryTerm {
+            variants: vec!["email".into()],
+            ..GlossaryTerm::default()
+        };
+        assert!(CompiledTerm::compile(&lone).is_err());
+        let bare = GlossaryTerm {
+            preferred: Some("workspace".into()),
+            ..GlossaryTerm::default()
+        };
+        assert!(CompiledTerm::compile(&bare).is_err());
+        let exact = GlossaryTerm {
+            preferred: Some("ToneGuard".into()),
+            case: TermCase::Exact,
+            ..GlossaryTerm::default()
+        };
+        let term = CompiledTerm::compile(&exact).unwrap();
+        assert_eq!(term.replacement("ToneGuard", "toneguard"), "ToneGuard");
+    }
+}
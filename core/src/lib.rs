@@ -3,27 +3,62 @@
 //! configurable phrase lists and structural heuristics.
 
 use std::{
+    cell::Cell,
     collections::{BTreeMap, HashMap, HashSet},
     path::Path,
+    sync::{Arc, Mutex},
 };
 
-use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
+use custom::CompiledRule;
+use directives::{Directive, Scope};
+use frontmatter::DocumentSettings;
 use globset::{Glob, GlobSetBuilder};
+use glossary::CompiledTerm;
+use links::LinkChecker;
+use locale::{Language, LocaleRuntime};
+use markdown::{BlockKind, MarkdownDoc};
+use metrics::Metrics;
 use once_cell::sync::Lazy;
+use phrases::{MatchingConfig, PhraseMatcher, Words};
 use regex::Regex;
+use rules::RuleSelector;
+use schemars::JsonSchema;
+use sections::{Bounds as SectionBounds, Section};
 use serde::{Deserialize, Serialize};
+use spelling::SpellChecker;
 
 pub mod arch;
+pub mod baseline;
 pub mod blueprint;
+pub mod cache;
 pub mod cfg;
+pub mod commits;
 pub mod coverage;
+pub mod custom;
 pub mod dfg;
+pub mod directives;
+pub mod discovery;
+pub mod docstrings;
+pub mod duplicates;
 pub mod flow;
+pub mod frontmatter;
+pub mod glossary;
+pub mod links;
+pub mod locale;
+pub mod markdown;
+pub mod metrics;
 pub mod organize;
+pub mod packs;
+pub mod phrases;
+pub mod rules;
+pub mod sarif;
+pub mod sections;
+pub mod spelling;
 pub mod symbols;
+pub mod validate;
 
 /// Heading capitalisation policy.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum HeadingStyle {
     Any,
@@ -38,7 +73,7 @@ impl Default for HeadingStyle {
 }
 
 /// Preferred quotation mark style.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum QuoteStyle {
     Any,
@@ -52,7 +87,7 @@ impl Default for QuoteStyle {
 }
 
 /// Hard limits for stylistic constructs per document section.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct Limits {
     pub em_dashes_per_paragraph: usize,
@@ -74,12 +109,16 @@ impl Default for Limits {
     }
 }
 
-/// Thresholds for warnings / failures expressed as flags per 100 words.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Thresholds for warnings / failures expressed as weighted flags per 100
+/// words.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ScoreThresholds {
     pub warn_threshold_per_100w: u32,
     pub fail_threshold_per_100w: u32,
+    /// How much one diagnostic of each severity counts towards the density
+    /// compared with the thresholds.
+    pub weights: SeverityWeights,
 }
 
 impl Default for ScoreThresholds {
@@ -87,12 +126,45 @@ impl Default for ScoreThresholds {
         Self {
             warn_threshold_per_100w: 3,
             fail_threshold_per_100w: 6,
+            weights: SeverityWeights::default(),
+        }
+    }
+}
+
+/// Score weight per diagnostic severity.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SeverityWeights {
+    pub error: f32,
+    pub warning: f32,
+    pub hint: f32,
+    pub information: f32,
+}
+
+impl Default for SeverityWeights {
+    fn default() -> Self {
+        Self {
+            error: 1.0,
+            warning: 1.0,
+            hint: 0.5,
+            information: 0.0,
+        }
+    }
+}
+
+impl SeverityWeights {
+    pub fn weight(&self, severity: Severity) -> f32 {
+        match severity {
+            Severity::Error => self.error,
+            Severity::Warning => self.warning,
+            Severity::Hint => self.hint,
+            Severity::Information => self.information,
         }
     }
 }
 
 /// Whitelisted tokens and phrases that should not trigger diagnostics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct Whitelist {
     pub allowed_typos: Vec<String>,
@@ -113,7 +185,7 @@ impl Default for Whitelist {
 }
 
 /// Phrase container for banned expressions.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct PhraseList {
     pub ban: Vec<String>,
@@ -126,7 +198,7 @@ impl Default for PhraseList {
 }
 
 /// Phrase container for throttled expressions (soft suggestions).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct BuzzwordConfig {
     pub throttle: Vec<String>,
@@ -209,7 +281,7 @@ impl Default for BuzzwordConfig {
 }
 
 /// Profile-specific rule overrides applied to matched files.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ProfileRules {
     pub max_headings: Option<usize>,
@@ -233,6 +305,20 @@ pub struct ProfileRules {
     pub min_sentences_per_section: Option<usize>,
     pub min_code_blocks: Option<usize>,
     pub enable_triad_slop: bool,
+    /// Longest allowed first line, in characters (commit subjects).
+    pub max_subject_length: Option<usize>,
+    /// Highest Flesch–Kincaid grade level allowed for the document and
+    /// each section.
+    pub max_grade_level: Option<f32>,
+    /// Lowest Flesch reading ease allowed for the document and each section.
+    pub min_reading_ease: Option<f32>,
+    /// Thresholds for the statistical checks.
+    pub statistics: StatisticsRules,
+    /// Language of matched files, overriding the top-level `language`.
+    pub language: Option<Language>,
+    /// Severity per category or rule ID for matched files, applied after
+    /// the top-level `severity_overrides`.
+    pub severity_overrides: BTreeMap<String, Severity>,
 }
 
 impl Default for ProfileRules {
@@ -259,12 +345,111 @@ impl Default for ProfileRules {
             min_sentences_per_section: None,
             min_code_blocks: None,
             enable_triad_slop: true,
+            max_subject_length: None,
+            max_grade_level: None,
+            min_reading_ease: None,
+            statistics: StatisticsRules::default(),
+            language: None,
+            severity_overrides: BTreeMap::new(),
+        }
+    }
+}
+
+/// Thresholds for the statistical checks and readability targets. Unset
+/// values come from the parent profile (or `profile_defaults`), then from
+/// the built-in defaults shown in brackets.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct StatisticsRules {
+    /// Fewest sentences a document or section needs before any statistical
+    /// check runs [5].
+    pub min_sentences: Option<usize>,
+    /// Sentence-length coefficient of variation below which lengths count
+    /// as uniform [0.20].
+    pub uniform_max_cv: Option<f32>,
+    /// Fewest sentences of three or more words for the uniformity check [8].
+    pub uniform_min_sentences: Option<usize>,
+    /// Share of passive sentences above which passive voice is flagged [0.5].
+    pub passive_max_ratio: Option<f32>,
+    /// Fewest sentences for the passive-voice check [6].
+    pub passive_min_sentences: Option<usize>,
+    /// Share of paragraph sentences one opening may start before it is
+    /// flagged [0.4].
+    pub opening_max_ratio: Option<f32>,
+    /// Fewest uses of one opening before it is flagged [4].
+    pub opening_min_count: Option<usize>,
+    /// How many of the summary / conclusion / future development headings
+    /// make a template triad [2].
+    pub triad_min_headings: Option<usize>,
+    /// Fewest sentences a document or section needs for readability
+    /// targets [5].
+    pub readability_min_sentences: Option<usize>,
+}
+
+/// [`StatisticsRules`] with every value resolved.
+#[derive(Debug, Clone, Copy)]
+struct StatThresholds {
+    min_sentences: usize,
+    uniform_max_cv: f32,
+    uniform_min_sentences: usize,
+    passive_max_ratio: f32,
+    passive_min_sentences: usize,
+    opening_max_ratio: f32,
+    opening_min_count: usize,
+    triad_min_headings: usize,
+    readability_min_sentences: usize,
+}
+
+impl Default for StatThresholds {
+    fn default() -> Self {
+        Self {
+            min_sentences: 5,
+            uniform_max_cv: 0.20,
+            uniform_min_sentences: 8,
+            passive_max_ratio: 0.5,
+            passive_min_sentences: 6,
+            opening_max_ratio: 0.4,
+            opening_min_count: 4,
+            triad_min_headings: 2,
+            readability_min_sentences: 5,
+        }
+    }
+}
+
+impl StatThresholds {
+    fn apply(&mut self, rules: &StatisticsRules) {
+        if let Some(value) = rules.min_sentences {
+            self.min_sentences = value;
+        }
+        if let Some(value) = rules.uniform_max_cv {
+            self.uniform_max_cv = value;
+        }
+        if let Some(value) = rules.uniform_min_sentences {
+            self.uniform_min_sentences = value;
+        }
+        if let Some(value) = rules.passive_max_ratio {
+            self.passive_max_ratio = value;
+        }
+        if let Some(value) = rules.passive_min_sentences {
+            self.passive_min_sentences = value;
+        }
+        if let Some(value) = rules.opening_max_ratio {
+            self.opening_max_ratio = value;
+        }
+        if let Some(value) = rules.opening_min_count {
+            self.opening_min_count = value;
+        }
+        if let Some(value) = rules.triad_min_headings {
+            self.triad_min_headings = value;
+        }
+        if let Some(value) = rules.readability_min_sentences {
+            self.readability_min_sentences = value;
         }
     }
 }
 
 /// File matching configuration to attach rule overrides.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default)]
 pub struct ProfileConfig {
     pub name: String,
@@ -273,7 +458,7 @@ pub struct ProfileConfig {
     pub rules: ProfileRules,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct CommentPolicy {
     pub enabled: bool,
@@ -284,7 +469,7 @@ pub struct CommentPolicy {
     pub ticket_reference_regex: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct RepoRules {
     pub ignore_globs: Vec<String>,
@@ -378,13 +563,16 @@ impl Default for CommentPolicy {
 }
 
 /// Top-level configuration for the analyzer.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct Config {
     pub heading_style: HeadingStyle,
     pub quote_style: QuoteStyle,
     pub limits: Limits,
     pub scores: ScoreThresholds,
+    /// Severity per category or rule ID (`tone`, `tone/passive-density`).
+    /// Rule IDs take precedence over their category.
+    pub severity_overrides: BTreeMap<String, Severity>,
     pub whitelist: Whitelist,
     pub buzzwords: BuzzwordConfig,
     pub transitions: BuzzwordConfig,
@@ -392,13 +580,31 @@ pub struct Config {
     pub templates: PhraseList,
     pub weasel: PhraseList,
     pub marketing_cliches: PhraseList,
+    /// Normalisation and inflection handling for the phrase lists.
+    pub matching: MatchingConfig,
+    /// Document language (`en`, `de`, `es`). Frontmatter `lang:` and a
+    /// profile's `language` take precedence.
+    pub language: Language,
     pub profile_defaults: ProfileRules,
     pub profiles: Vec<ProfileConfig>,
+    /// Presets a document picks with `type:` in its `toneguard:`
+    /// frontmatter.
+    pub document_types: BTreeMap<String, frontmatter::DocumentType>,
+    /// House rules reported as `custom/<id>`.
+    pub custom_rules: Vec<custom::CustomRule>,
+    /// Preferred terms and the variants to avoid.
+    pub glossary: glossary::GlossaryConfig,
+    /// Spell checking of English prose.
+    pub spelling: spelling::SpellingConfig,
     pub repo_rules: RepoRules,
     pub comment_policy: CommentPolicy,
     pub flow_rules: flow::FlowRules,
     #[serde(default)]
     pub organize_rules: organize::OrganizeConfig,
+    /// Near-duplicate paragraphs across the linted files.
+    pub duplicates: duplicates::DuplicateConfig,
+    /// Offline checks of relative links and heading anchors.
+    pub links: links::LinkConfig,
 }
 
 impl Default for Config {
@@ -408,6 +614,7 @@ impl Default for Config {
             quote_style: QuoteStyle::Straight,
             limits: Limits::default(),
             scores: ScoreThresholds::default(),
+            severity_overrides: BTreeMap::new(),
             whitelist: Whitelist::default(),
             buzzwords: BuzzwordConfig::default(),
             transitions: BuzzwordConfig {
@@ -565,6 +772,8 @@ impl Default for Config {
                     "act now".into(),
                 ],
             },
+            matching: MatchingConfig::default(),
+            language: Language::En,
             profile_defaults: ProfileRules {
                 max_headings: None,
                 required_headings: Vec::new(),
@@ -627,12 +836,24 @@ impl Default for Config {
                 min_sentences_per_section: None,
                 min_code_blocks: None,
                 enable_triad_slop: true,
+                max_subject_length: None,
+                max_grade_level: None,
+                min_reading_ease: None,
+                statistics: StatisticsRules::default(),
+                language: None,
+                severity_overrides: BTreeMap::new(),
             },
             profiles: Vec::new(),
+            document_types: BTreeMap::new(),
+            custom_rules: Vec::new(),
+            glossary: glossary::GlossaryConfig::default(),
+            spelling: spelling::SpellingConfig::default(),
             repo_rules: RepoRules::default(),
             comment_policy: CommentPolicy::default(),
             flow_rules: flow::FlowRules::default(),
             organize_rules: organize::OrganizeConfig::default(),
+            duplicates: duplicates::DuplicateConfig::default(),
+            links: links::LinkConfig::default(),
         }
     }
 }
@@ -661,10 +882,18 @@ struct ProfileRecipe {
     min_sentences_per_section: Option<usize>,
     min_code_blocks: Option<usize>,
     enable_triad_slop: bool,
+    max_subject_length: Option<usize>,
+    max_grade_level: Option<f32>,
+    min_reading_ease: Option<f32>,
+    statistics: StatThresholds,
+    language: Option<Language>,
+    severity_overrides: BTreeMap<String, Severity>,
 }
 
 impl ProfileRecipe {
     fn from_rules(name: impl Into<String>, base: &ProfileRules) -> Self {
+        let mut statistics = StatThresholds::default();
+        statistics.apply(&base.statistics);
         Self {
             name: name.into(),
             max_headings: base.max_headings,
@@ -696,9 +925,25 @@ impl ProfileRecipe {
             min_sentences_per_section: base.min_sentences_per_section,
             min_code_blocks: base.min_code_blocks,
             enable_triad_slop: base.enable_triad_slop,
+            max_subject_length: base.max_subject_length,
+            max_grade_level: base.max_grade_level,
+            min_reading_ease: base.min_reading_ease,
+            statistics,
+            language: base.language,
+            severity_overrides: base.severity_overrides.clone(),
         }
     }
 
+    /// Drop checks that assume a standalone document, for prose stitched
+    /// together from doc comments.
+    fn relax_document_structure(&mut self) {
+        self.max_headings = None;
+        self.required_headings.clear();
+        self.required_patterns.clear();
+        self.min_sentences_per_section = None;
+        self.min_code_blocks = None;
+    }
+
     fn extend_with(&mut self, overrides: &ProfileRules) {
         if overrides.max_headings.is_some() {
             self.max_headings = overrides.max_headings;
@@ -781,6 +1026,22 @@ impl ProfileRecipe {
         if overrides.enable_triad_slop {
             self.enable_triad_slop = true;
         }
+        if overrides.max_subject_length.is_some() {
+            self.max_subject_length = overrides.max_subject_length;
+        }
+        if overrides.max_grade_level.is_some() {
+            self.max_grade_level = overrides.max_grade_level;
+        }
+        if overrides.min_reading_ease.is_some() {
+            self.min_reading_ease = overrides.min_reading_ease;
+        }
+        self.statistics.apply(&overrides.statistics);
+        if overrides.language.is_some() {
+            self.language = overrides.language;
+        }
+        for (name, severity) in &overrides.severity_overrides {
+            self.severity_overrides.insert(name.clone(), *severity);
+        }
     }
 
     fn clone_for(&self, name: impl Into<String>) -> Self {
@@ -807,6 +1068,12 @@ impl ProfileRecipe {
             min_sentences_per_section: self.min_sentences_per_section,
             min_code_blocks: self.min_code_blocks,
             enable_triad_slop: self.enable_triad_slop,
+            max_subject_length: self.max_subject_length,
+            max_grade_level: self.max_grade_level,
+            min_reading_ease: self.min_reading_ease,
+            statistics: self.statistics,
+            language: self.language,
+            severity_overrides: self.severity_overrides.clone(),
         }
     }
 }
@@ -817,14 +1084,14 @@ struct ProfileRuntime {
     max_headings: Option<usize>,
     required_headings: Vec<String>,
     banned_heading_regexes: Vec<Regex>,
-    call_to_action_matcher: Option<AhoCorasick>,
+    call_to_action_matcher: Option<PhraseMatcher>,
     template_regexes: Vec<Regex>,
     max_sentence_length: Option<usize>,
     max_duplicate_sentences: usize,
     cadence_starts: Vec<String>,
     cadence_limit: usize,
-    broad_terms: Vec<String>,
-    confidence_matcher: Option<AhoCorasick>,
+    broad_term_matcher: Option<PhraseMatcher>,
+    confidence_matcher: Option<PhraseMatcher>,
     detect_percent_claims: bool,
     max_heading_depth: Option<usize>,
     max_bullet_items: Option<usize>,
@@ -836,10 +1103,23 @@ struct ProfileRuntime {
     min_sentences_per_section: Option<usize>,
     min_code_blocks: Option<usize>,
     enable_triad_slop: bool,
+    max_subject_length: Option<usize>,
+    max_grade_level: Option<f32>,
+    min_reading_ease: Option<f32>,
+    statistics: StatThresholds,
+    language: Option<Language>,
+    custom_rules: Vec<CompiledRule>,
+    glossary: Vec<CompiledTerm>,
+    /// Applied after the analyzer-wide overrides, categories before rules.
+    severity_overrides: Vec<(RuleSelector, Severity)>,
 }
 
 impl ProfileRuntime {
-    fn compile(recipe: ProfileRecipe) -> anyhow::Result<Self> {
+    fn compile(
+        recipe: ProfileRecipe,
+        matching: &MatchingConfig,
+        default_language: Language,
+    ) -> anyhow::Result<Self> {
         let mut banned_heading_regexes = Vec::new();
         for pattern in &recipe.banned_headings {
             let regex = Regex::new(&format!("(?i){}", pattern))?;
@@ -860,24 +1140,22 @@ impl ProfileRuntime {
             let regex = Regex::new(&format!("(?i){}", pattern))?;
             forbidden_patterns.push(regex);
         }
-        let call_to_action_matcher = if recipe.call_to_action_phrases.is_empty() {
-            None
-        } else {
-            Some(
-                AhoCorasickBuilder::new()
-                    .ascii_case_insensitive(true)
-                    .build(recipe.call_to_action_phrases.clone()),
-            )
-        };
-        let confidence_matcher = if recipe.confidence_phrases.is_empty() {
-            None
-        } else {
-            Some(
-                AhoCorasickBuilder::new()
-                    .ascii_case_insensitive(true)
-                    .build(recipe.confidence_phrases.clone()),
-            )
-        };
+        let language = recipe.language.unwrap_or(default_language);
+        let call_to_action_matcher =
+            PhraseMatcher::new(&recipe.call_to_action_phrases, matching, language);
+        let broad_term_matcher = PhraseMatcher::new(&recipe.broad_terms, matching, language);
+        let confidence_matcher = PhraseMatcher::new(&recipe.confidence_phrases, matching, language);
+        let mut severity_overrides = Vec::new();
+        for (name, severity) in &recipe.severity_overrides {
+            let selector = RuleSelector::parse(name).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "unknown category or rule `{name}` in severity_overrides of profile `{}`",
+                    recipe.name
+                )
+            })?;
+            severity_overrides.push((selector, *severity));
+        }
+        severity_overrides.sort_by_key(|(selector, _)| selector.is_rule());
         Ok(Self {
             name: recipe.name,
             max_headings: recipe.max_headings,
@@ -889,7 +1167,7 @@ impl ProfileRuntime {
             max_duplicate_sentences: recipe.max_duplicate_sentences.unwrap_or(1),
             cadence_starts: recipe.cadence_starts,
             cadence_limit: recipe.cadence_limit.unwrap_or(2).max(1),
-            broad_terms: recipe.broad_terms,
+            broad_term_matcher,
             confidence_matcher,
             detect_percent_claims: true,
             max_heading_depth: recipe.max_heading_depth,
@@ -902,6 +1180,14 @@ impl ProfileRuntime {
             min_sentences_per_section: recipe.min_sentences_per_section,
             min_code_blocks: recipe.min_code_blocks,
             enable_triad_slop: recipe.enable_triad_slop,
+            max_subject_length: recipe.max_subject_length,
+            max_grade_level: recipe.max_grade_level,
+            min_reading_ease: recipe.min_reading_ease,
+            statistics: recipe.statistics,
+            language: recipe.language,
+            custom_rules: Vec::new(),
+            glossary: Vec::new(),
+            severity_overrides,
         })
     }
 }
@@ -916,8 +1202,15 @@ struct HeadingCapture {
     line: usize,
     column: usize,
     offset: usize,
+    /// End of the heading's source span (after any setext underline).
+    end: usize,
+    /// Length of the heading's first source line.
     len: usize,
+    level: usize,
+    /// First source line, markers included.
     text: String,
+    /// Plain heading text.
+    content: String,
     lower: String,
 }
 
@@ -927,6 +1220,7 @@ struct PhraseHit {
     end: usize,
     snippet: String,
     suggestion: Option<String>,
+    fix: Option<Fix>,
     sentence_idx: usize,
 }
 
@@ -987,6 +1281,12 @@ pub enum Category {
     EmDash,
     Formatting,
     QuoteStyle,
+    Readability,
+    Terminology,
+    Spelling,
+    Links,
+    Directive,
+    Custom,
 }
 
 impl std::fmt::Display for Category {
@@ -1012,6 +1312,12 @@ impl std::fmt::Display for Category {
             Category::EmDash => "em-dash",
             Category::Formatting => "formatting",
             Category::QuoteStyle => "quote-style",
+            Category::Readability => "readability",
+            Category::Terminology => "terminology",
+            Category::Spelling => "spelling",
+            Category::Links => "links",
+            Category::Directive => "directive",
+            Category::Custom => "custom",
         };
         f.write_str(name)
     }
@@ -1040,6 +1346,12 @@ pub fn parse_category(name: &str) -> Option<Category> {
         "em-dash" | "emdash" => Some(Category::EmDash),
         "formatting" => Some(Category::Formatting),
         "quote-style" => Some(Category::QuoteStyle),
+        "readability" => Some(Category::Readability),
+        "terminology" => Some(Category::Terminology),
+        "spelling" => Some(Category::Spelling),
+        "links" | "link" => Some(Category::Links),
+        "directive" => Some(Category::Directive),
+        "custom" => Some(Category::Custom),
         _ => None,
     }
 }
@@ -1052,7 +1364,9 @@ pub struct Location {
 }
 
 /// Diagnostic severity levels matching LSP specification.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Ord, PartialOrd, JsonSchema,
+)]
 #[serde(rename_all = "kebab-case")]
 pub enum Severity {
     /// Hard slop signal - always flag
@@ -1082,16 +1396,136 @@ impl std::fmt::Display for Severity {
     }
 }
 
+/// How confident the analyzer is that a fix preserves meaning.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Ord, PartialOrd, JsonSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Applicability {
+    /// Mechanical swap that can be applied without review.
+    Safe,
+    /// Likely correct, but the surrounding sentence may need a human pass.
+    Unsafe,
+}
+
+impl std::fmt::Display for Applicability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Applicability::Safe => f.write_str("safe"),
+            Applicability::Unsafe => f.write_str("unsafe"),
+        }
+    }
+}
+
+/// Replace the bytes in `span` with `replacement`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TextEdit {
+    pub span: (usize, usize),
+    pub replacement: String,
+}
+
+/// Machine-applicable fix attached to a diagnostic.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Fix {
+    pub applicability: Applicability,
+    pub edits: Vec<TextEdit>,
+}
+
+impl Fix {
+    fn replace(
+        span: (usize, usize),
+        replacement: impl Into<String>,
+        applicability: Applicability,
+    ) -> Self {
+        Self {
+            applicability,
+            edits: vec![TextEdit {
+                span,
+                replacement: replacement.into(),
+            }],
+        }
+    }
+}
+
 /// Style diagnostic emitted by the analyzer.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Diagnostic {
     pub category: Category,
+    /// Stable rule ID such as `structure/max-bullet-items`; see [`rules::RULES`].
+    #[serde(default)]
+    pub rule: String,
     pub severity: Severity,
     pub message: String,
     pub suggestion: Option<String>,
     pub location: Location,
     pub span: (usize, usize),
     pub snippet: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+}
+
+/// Outcome of applying diagnostic fixes to a document.
+#[derive(Debug, Clone)]
+pub struct FixOutcome {
+    pub text: String,
+    pub applied: usize,
+    pub skipped: usize,
+}
+
+/// Apply the fixes carried by `diagnostics` to `text`.
+///
+/// Unsafe fixes are only applied when `include_unsafe` is set. Fixes whose edits
+/// overlap an edit that was already accepted are skipped so the result stays
+/// well-formed; rerunning the analyzer picks them up on the next pass. An empty
+/// span is an insertion; two insertions at the same offset count as
+/// overlapping.
+pub fn apply_fixes(text: &str, diagnostics: &[Diagnostic], include_unsafe: bool) -> FixOutcome {
+    let mut fixes: Vec<&Fix> = diagnostics
+        .iter()
+        .filter_map(|d| d.fix.as_ref())
+        .filter(|fix| include_unsafe || fix.applicability == Applicability::Safe)
+        .filter(|fix| !fix.edits.is_empty())
+        .collect();
+    fixes.sort_by_key(|fix| fix.edits.iter().map(|e| e.span.0).min().unwrap_or(0));
+
+    let mut accepted: Vec<&TextEdit> = Vec::new();
+    let mut applied = 0usize;
+    let mut skipped = 0usize;
+    for fix in fixes {
+        let valid = fix.edits.iter().all(|edit| {
+            edit.span.0 <= edit.span.1
+                && edit.span.1 <= text.len()
+                && text.is_char_boundary(edit.span.0)
+                && text.is_char_boundary(edit.span.1)
+        });
+        let overlaps = fix.edits.iter().any(|edit| {
+            accepted.iter().any(|prev| {
+                (edit.span.0 < prev.span.1 && prev.span.0 < edit.span.1) || edit.span == prev.span
+            })
+        });
+        if !valid || overlaps {
+            skipped += 1;
+            continue;
+        }
+        accepted.extend(fix.edits.iter());
+        applied += 1;
+    }
+
+    accepted.sort_by_key(|edit| edit.span);
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0usize;
+    for edit in accepted {
+        out.push_str(&text[cursor..edit.span.0]);
+        out.push_str(&edit.replacement);
+        cursor = edit.span.1;
+    }
+    out.push_str(&text[cursor..]);
+
+    FixOutcome {
+        text: out,
+        applied,
+        skipped,
+    }
 }
 
 /// Summary statistics for a document.
@@ -1101,32 +1535,80 @@ pub struct DocumentReport {
     pub diagnostics: Vec<Diagnostic>,
     pub category_counts: BTreeMap<Category, usize>,
     pub profile: String,
+    /// Readability of the document's prose.
+    pub metrics: Metrics,
+    /// The document split at its headings, in order.
+    pub sections: Vec<Section>,
 }
 
 impl DocumentReport {
     /// Style density = flags per 100 words (rounded up).
     pub fn density_per_100_words(&self) -> f32 {
-        if self.word_count == 0 {
-            return self.diagnostics.len() as f32;
+        per_100_words(self.diagnostics.len() as f32, self.word_count)
+    }
+
+    /// Sum of the diagnostics' severity weights.
+    pub fn weighted_count(&self, weights: &SeverityWeights) -> f32 {
+        self.diagnostics
+            .iter()
+            .map(|diag| weights.weight(diag.severity))
+            .sum()
+    }
+
+    /// Density with each diagnostic counted at its severity weight; this is
+    /// the value compared with the score thresholds.
+    pub fn weighted_density_per_100_words(&self, weights: &SeverityWeights) -> f32 {
+        per_100_words(self.weighted_count(weights), self.word_count)
+    }
+
+    /// Recompute each section's counts and density from the diagnostics,
+    /// after diagnostics have been filtered out.
+    pub fn recount_sections(&mut self) {
+        for section in &mut self.sections {
+            let (start, end) = section.span;
+            section.category_counts.clear();
+            let mut hits = 0;
+            for diag in &self.diagnostics {
+                if (start..end).contains(&diag.span.0) {
+                    *section.category_counts.entry(diag.category).or_default() += 1;
+                    hits += 1;
+                }
+            }
+            section.density_per_100_words = per_100_words(hits as f32, section.word_count);
         }
-        (self.diagnostics.len() as f32) * 100.0 / (self.word_count as f32)
     }
 }
 
+/// `count` per 100 of `words`; the bare count when there are no words.
+fn per_100_words(count: f32, words: usize) -> f32 {
+    if words == 0 {
+        return count;
+    }
+    count * 100.0 / words as f32
+}
+
+/// A profile runtime compiled for a document's frontmatter overrides, or
+/// why it did not compile.
+type DocumentRuntime = Result<Arc<ProfileRuntime>, String>;
+
 /// Analyzer encapsulates compiled rules for reuse across files.
 pub struct Analyzer {
     config: Config,
     allow_phrase_set: HashSet<String>,
-    puffery_matcher: Option<AhoCorasick>,
-    buzzword_matcher: Option<AhoCorasick>,
-    weasel_matcher: Option<AhoCorasick>,
-    transition_matcher: Option<AhoCorasick>,
-    marketing_matcher: Option<AhoCorasick>,
+    locales: HashMap<Language, LocaleRuntime>,
     base_template_regexes: Vec<Regex>,
     rule_of_three_regex: Regex,
     range_regex: Regex,
     profile_runtimes: HashMap<String, ProfileRuntime>,
+    /// Recipes of the runtimes, for documents that override rules in
+    /// their frontmatter.
+    profile_recipes: HashMap<String, ProfileRecipe>,
+    /// Runtimes compiled for frontmatter overrides, by profile name and
+    /// serialized overrides.
+    document_runtimes: Mutex<HashMap<(String, String), DocumentRuntime>>,
     profile_matchers: Vec<ProfileMatcher>,
+    severity_overrides: Vec<(RuleSelector, Severity)>,
+    spell_checker: Option<SpellChecker>,
     default_profile: String,
 }
 
@@ -1136,58 +1618,13 @@ impl Analyzer {
             .whitelist
             .allowed_phrases
             .iter()
-            .map(|s| s.to_lowercase())
+            .map(|s| phrases::fold(s, &config.matching))
             .collect();
 
-        let puffery_matcher = if config.puffery.ban.is_empty() {
-            None
-        } else {
-            Some(
-                AhoCorasickBuilder::new()
-                    .ascii_case_insensitive(true)
-                    .build(&config.puffery.ban),
-            )
-        };
-
-        let buzzword_matcher = if config.buzzwords.throttle.is_empty() {
-            None
-        } else {
-            Some(
-                AhoCorasickBuilder::new()
-                    .ascii_case_insensitive(true)
-                    .build(&config.buzzwords.throttle),
-            )
-        };
-
-        let weasel_matcher = if config.weasel.ban.is_empty() {
-            None
-        } else {
-            Some(
-                AhoCorasickBuilder::new()
-                    .ascii_case_insensitive(true)
-                    .build(&config.weasel.ban),
-            )
-        };
-
-        let transition_matcher = if config.transitions.throttle.is_empty() {
-            None
-        } else {
-            Some(
-                AhoCorasickBuilder::new()
-                    .ascii_case_insensitive(true)
-                    .build(&config.transitions.throttle),
-            )
-        };
-
-        let marketing_matcher = if config.marketing_cliches.ban.is_empty() {
-            None
-        } else {
-            Some(
-                AhoCorasickBuilder::new()
-                    .ascii_case_insensitive(true)
-                    .build(&config.marketing_cliches.ban),
-            )
-        };
+        let locales = Language::ALL
+            .into_iter()
+            .map(|language| (language, LocaleRuntime::compile(language, &config)))
+            .collect();
 
         let mut base_template_regexes = Vec::new();
         for pattern in &config.templates.ban {
@@ -1221,9 +1658,12 @@ impl Analyzer {
             &mut recipe_cache,
             &config.profile_defaults,
         )?;
-        let default_runtime = ProfileRuntime::compile(default_recipe.clone())?;
+        let default_runtime =
+            ProfileRuntime::compile(default_recipe.clone(), &config.matching, config.language)?;
         let mut profile_runtimes: HashMap<String, ProfileRuntime> = HashMap::new();
+        let mut profile_recipes: HashMap<String, ProfileRecipe> = HashMap::new();
         profile_runtimes.insert(default_runtime.name.clone(), default_runtime);
+        profile_recipes.insert(default_recipe.name.clone(), default_recipe.clone());
 
         let profile_names: Vec<String> = profile_config_map.keys().cloned().collect();
         for name in profile_names {
@@ -1233,8 +1673,34 @@ impl Analyzer {
                 &mut recipe_cache,
                 &config.profile_defaults,
             )?;
-            let runtime = ProfileRuntime::compile(recipe)?;
+            let runtime =
+                ProfileRuntime::compile(recipe.clone(), &config.matching, config.language)?;
             profile_runtimes.insert(runtime.name.clone(), runtime);
+            profile_recipes.insert(recipe.name.clone(), recipe);
+        }
+        if !profile_runtimes.contains_key(docstrings::CODE_DOCS_PROFILE) {
+            let mut recipe = default_recipe.clone_for(docstrings::CODE_DOCS_PROFILE);
+            recipe.relax_document_structure();
+            profile_runtimes.insert(
+                recipe.name.clone(),
+                ProfileRuntime::compile(recipe.clone(), &config.matching, config.language)?,
+            );
+            profile_recipes.insert(recipe.name.clone(), recipe);
+        }
+        if !profile_runtimes.contains_key(commits::COMMIT_MESSAGE_PROFILE) {
+            let mut recipe = default_recipe.clone_for(commits::COMMIT_MESSAGE_PROFILE);
+            recipe.relax_document_structure();
+            recipe.max_subject_length = Some(commits::DEFAULT_MAX_SUBJECT_LENGTH);
+            recipe.template_phrases.extend(
+                commits::COMMIT_TEMPLATE_PHRASES
+                    .iter()
+                    .map(|phrase| phrase.to_string()),
+            );
+            profile_runtimes.insert(
+                recipe.name.clone(),
+                ProfileRuntime::compile(recipe.clone(), &config.matching, config.language)?,
+            );
+            profile_recipes.insert(recipe.name.clone(), recipe);
         }
 
         let mut profile_matchers = Vec::new();
@@ -1264,23 +1730,120 @@ impl Analyzer {
             });
         }
 
+        let mut custom_rules: Vec<CompiledRule> = Vec::new();
+        for rule in &config.custom_rules {
+            let compiled = CompiledRule::compile(rule)?;
+            if custom_rules.iter().any(|prev| prev.id == compiled.id) {
+                anyhow::bail!("duplicate custom rule `{}`", compiled.id);
+            }
+            for name in compiled.profiles() {
+                if !profile_runtimes.contains_key(name) {
+                    anyhow::bail!(
+                        "custom rule `{}` names unknown profile `{name}`",
+                        compiled.id
+                    );
+                }
+            }
+            custom_rules.push(compiled);
+        }
+        let mut glossary = Vec::new();
+        for term in &config.glossary.terms {
+            let compiled = CompiledTerm::compile(term)?;
+            for name in compiled.profiles() {
+                if !profile_runtimes.contains_key(name) {
+                    anyhow::bail!(
+                        "glossary term `{}` names unknown profile `{name}`",
+                        compiled.name()
+                    );
+                }
+            }
+            glossary.push(compiled);
+        }
+        for (name, document_type) in &config.document_types {
+            if let Some(profile) = &document_type.profile {
+                if !profile_runtimes.contains_key(profile) {
+                    anyhow::bail!("document type `{name}` names unknown profile `{profile}`");
+                }
+            }
+        }
+        for runtime in profile_runtimes.values_mut() {
+            runtime.custom_rules = custom_rules
+                .iter()
+                .filter(|rule| rule.applies_to(&runtime.name))
+                .cloned()
+                .collect();
+            runtime.glossary = glossary
+                .iter()
+                .filter(|term| term.applies_to(&runtime.name))
+                .cloned()
+                .collect();
+        }
+
+        let is_known = |selector: &RuleSelector| match selector {
+            RuleSelector::Rule(id) if id.starts_with("custom/") => {
+                custom_rules.iter().any(|rule| &rule.id == id)
+            }
+            _ => true,
+        };
+        for runtime in profile_runtimes.values() {
+            if let Some((selector, _)) = runtime
+                .severity_overrides
+                .iter()
+                .find(|(selector, _)| !is_known(selector))
+            {
+                anyhow::bail!(
+                    "unknown category or rule `{selector}` in severity_overrides of profile `{}`",
+                    runtime.name
+                );
+            }
+        }
+
+        let mut severity_overrides = Vec::new();
+        for (name, severity) in &config.severity_overrides {
+            let selector = RuleSelector::parse(name).filter(is_known).ok_or_else(|| {
+                anyhow::anyhow!("unknown category or rule `{name}` in severity_overrides")
+            })?;
+            severity_overrides.push((selector, *severity));
+        }
+        // Apply category overrides first so rule-level entries win.
+        severity_overrides.sort_by_key(|(selector, _)| selector.is_rule());
+        let spell_checker = config
+            .spelling
+            .enabled
+            .then(|| SpellChecker::new(&config.spelling, &config.whitelist.allowed_typos));
+
         Ok(Self {
             config,
             allow_phrase_set,
-            puffery_matcher,
-            buzzword_matcher,
-            weasel_matcher,
-            transition_matcher,
-            marketing_matcher,
+            locales,
             base_template_regexes,
             rule_of_three_regex,
             range_regex,
             profile_runtimes,
+            profile_recipes,
+            document_runtimes: Mutex::new(HashMap::new()),
             profile_matchers,
+            severity_overrides,
+            spell_checker,
             default_profile: "default".into(),
         })
     }
 
+    /// Severity of a rule reported outside [`Analyzer::analyze`], such as
+    /// cross-file duplicates, after the config's and the default profile's
+    /// `severity_overrides`.
+    pub fn rule_severity(&self, category: Category, rule: &str, severity: Severity) -> Severity {
+        self.severity_overrides
+            .iter()
+            .chain(
+                self.profile_for_name(&self.default_profile)
+                    .into_iter()
+                    .flat_map(|profile| &profile.severity_overrides),
+            )
+            .filter(|(selector, _)| selector.matches(category, rule))
+            .fold(severity, |_, (_, severity)| *severity)
+    }
+
     pub fn default_profile(&self) -> &str {
         &self.default_profile
     }
@@ -1320,264 +1883,527 @@ impl Analyzer {
         self.analyze_with_profile(text, profile)
     }
 
-    pub(crate) fn analyze_with_profile(
+    /// Add the broken links and anchors of the document at `path` to its
+    /// report. Links depend on other files, so they are checked apart from
+    /// the text-only analysis and never cached with it.
+    pub fn check_links(
         &self,
+        checker: &LinkChecker,
+        path: &Path,
         text: &str,
+        report: &mut DocumentReport,
+    ) {
+        let profile = self
+            .profile_for_name(&report.profile)
+            .or_else(|| self.profile_for_name(&self.default_profile))
+            .expect("default profile missing");
+        let doc = MarkdownDoc::parse(text);
+        let directives = directives::parse(text, &doc);
+        let filtered = DisabledRanges::with_directives(text, &doc, &directives);
+        let mut diagnostics = checker.check_with(path, text, &doc, &filtered);
+        // `analyze` does not check links, so directives that only hide
+        // links look unused to it.
+        let used = filtered.used(&directives);
+        if !used.is_empty() {
+            report.diagnostics.retain(|diag| {
+                let stale = diag.rule == directives::UNUSED_RULE && used.contains(&diag.span);
+                if stale {
+                    if let Some(count) = report.category_counts.get_mut(&diag.category) {
+                        *count -= 1;
+                    }
+                }
+                !stale
+            });
+            report.category_counts.retain(|_, count| *count > 0);
+            report.recount_sections();
+        }
+        if diagnostics.is_empty() {
+            return;
+        }
+        let settings = DocumentSettings::read(text, &doc, &self.config);
+        diagnostics.retain(|diag| !settings.disables(diag));
+        self.apply_severity_overrides(&mut diagnostics, profile, &settings);
+        for diag in diagnostics {
+            *report.category_counts.entry(diag.category).or_default() += 1;
+            report.diagnostics.push(diag);
+        }
+        report.recount_sections();
+    }
+
+    fn apply_severity_overrides(
+        &self,
+        diagnostics: &mut [Diagnostic],
         profile: &ProfileRuntime,
-    ) -> DocumentReport {
-        let filtered = DisabledRanges::new(text);
-        let mut diagnostics = Vec::new();
-        let mut category_counts: BTreeMap<Category, usize> = BTreeMap::new();
-        let sentences = split_sentences_with_offset(text);
+        document: &DocumentSettings,
+    ) {
+        for diag in diagnostics {
+            for (selector, severity) in self
+                .severity_overrides
+                .iter()
+                .chain(&profile.severity_overrides)
+                .chain(&document.severity_overrides)
+            {
+                if selector.matches(diag.category, &diag.rule) {
+                    diag.severity = *severity;
+                }
+            }
+        }
+    }
 
-        self.detect_puffery(
-            text,
-            &sentences,
-            &filtered,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_buzzwords(
-            text,
-            &sentences,
-            &filtered,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_transitions(
-            text,
-            &sentences,
-            &filtered,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_marketing(text, &filtered, &mut diagnostics, &mut category_counts);
-        self.detect_templates(
-            text,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_ranges(text, &filtered, &mut diagnostics, &mut category_counts);
+    /// The profile a document's frontmatter asks for. Unknown profiles
+    /// leave the profile as it was; the frontmatter diagnostics report them.
+    fn document_base<'a>(
+        &'a self,
+        profile: &'a ProfileRuntime,
+        settings: &DocumentSettings,
+    ) -> &'a ProfileRuntime {
+        settings
+            .profile
+            .as_deref()
+            .and_then(|name| self.profile_for_name(name))
+            .unwrap_or(profile)
+    }
 
-        self.detect_connectors(
-            text,
-            &sentences,
-            &filtered,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_sentence_length(
-            text,
-            &sentences,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_question_lead(
-            text,
-            &sentences,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_mid_sentence_questions(
-            text,
-            &sentences,
-            &filtered,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_cadence(
-            text,
-            &sentences,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_broad_terms(
-            text,
-            &sentences,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_repetition(
-            text,
-            &sentences,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_exclamation_density(
-            text,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
+    /// `base` with the document's rule overrides compiled in, or `None`
+    /// when it has none. Runtimes are compiled once per profile and set of
+    /// overrides; the error names what did not compile.
+    fn document_runtime(
+        &self,
+        base: &ProfileRuntime,
+        settings: &DocumentSettings,
+    ) -> Result<Option<Arc<ProfileRuntime>>, String> {
+        let Some(recipe) = self
+            .profile_recipes
+            .get(&base.name)
+            .filter(|_| !settings.rules.is_empty())
+        else {
+            return Ok(None);
+        };
+        let key = (
+            base.name.clone(),
+            serde_json::to_string(&settings.rules).unwrap_or_default(),
         );
+        let mut runtimes = self
+            .document_runtimes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let runtime = runtimes.entry(key).or_insert_with(|| {
+            let mut recipe = recipe.clone();
+            for rules in &settings.rules {
+                recipe.extend_with(rules);
+            }
+            let mut runtime =
+                ProfileRuntime::compile(recipe, &self.config.matching, self.config.language)
+                    .map_err(|err| err.to_string())?;
+            runtime.custom_rules = base.custom_rules.clone();
+            runtime.glossary = base.glossary.clone();
+            Ok(Arc::new(runtime))
+        });
+        runtime.clone().map(Some)
+    }
 
-        self.detect_rule_of_three(text, &filtered, &mut diagnostics, &mut category_counts);
-        self.detect_em_dash(text, &filtered, &mut diagnostics, &mut category_counts);
-        self.detect_bold_spans(text, &filtered, &mut diagnostics, &mut category_counts);
-        self.detect_headings(
-            text,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_bullet_items(
-            text,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_emoji_bullets(text, &filtered, &mut diagnostics, &mut category_counts);
-        self.detect_bold_lead_bullets(text, &filtered, &mut diagnostics, &mut category_counts);
-        self.detect_call_to_action(
-            text,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_confidence(
-            text,
-            &sentences,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_section_density(
-            text,
-            &sentences,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_triad_slop(
-            text,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_min_code_blocks(
-            text,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_required_patterns(
-            text,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_forbidden_patterns(
-            text,
-            &filtered,
-            profile,
-            &mut diagnostics,
-            &mut category_counts,
-        );
-        self.detect_quotes(text, &filtered, &mut diagnostics, &mut category_counts);
-        self.detect_statistical_slop(
+    pub(crate) fn analyze_with_profile(
+        &self,
+        text: &str,
+        profile: &ProfileRuntime,
+    ) -> DocumentReport {
+        let doc = MarkdownDoc::parse(text);
+        let mut settings = DocumentSettings::read(text, &doc, &self.config);
+        let base = self.document_base(profile, &settings);
+        let compiled = self
+            .document_runtime(base, &settings)
+            .unwrap_or_else(|error| {
+                settings.report_compile_error(text, &doc, &error);
+                None
+            });
+        let profile = compiled.as_deref().unwrap_or(base);
+        let directives = directives::parse(text, &doc);
+        let filtered = DisabledRanges::with_directives(text, &doc, &directives);
+        let language = doc
+            .frontmatter
+            .and_then(|(start, end)| locale::frontmatter_language(&text[start..end]))
+            .or(profile.language)
+            .unwrap_or(self.config.language);
+        let locale = self.locale(language);
+        let sentences = split_sentences(text, &doc, locale);
+        let words = Words::new(text, &self.config.matching, language);
+        let metrics = self.prose_metrics(&sentences, &filtered, (0, text.len()), language);
+        let section_bounds = sections::segment(text, &doc);
+        let section_metrics: Vec<Metrics> = section_bounds
+            .iter()
+            .map(|section| self.prose_metrics(&sentences, &filtered, section.span, language))
+            .collect();
+
+        let overrides = std::mem::take(&mut settings.diagnostics);
+        // Every detector, with `filtered` masking the document and recording
+        // which directives hid something.
+        let (mut diagnostics, mut category_counts) = {
+            let filtered = &filtered;
+            let mut diagnostics = Vec::new();
+            let mut category_counts: BTreeMap<Category, usize> = BTreeMap::new();
+            self.detect_puffery(
+                &words,
+                &sentences,
+                filtered,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_buzzwords(
+                &words,
+                &sentences,
+                filtered,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_transitions(
+                &words,
+                &sentences,
+                filtered,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_marketing(&words, filtered, &mut diagnostics, &mut category_counts);
+            self.detect_templates(
+                text,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_ranges(text, filtered, &mut diagnostics, &mut category_counts);
+
+            self.detect_connectors(
+                text,
+                &sentences,
+                filtered,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_sentence_length(
+                text,
+                &sentences,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_question_lead(
+                text,
+                &sentences,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_mid_sentence_questions(
+                text,
+                &sentences,
+                filtered,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_cadence(
+                text,
+                &sentences,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_broad_terms(
+                &words,
+                &sentences,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_repetition(
+                text,
+                &sentences,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_exclamation_density(
+                text,
+                &doc,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+
+            self.detect_rule_of_three(text, &doc, filtered, &mut diagnostics, &mut category_counts);
+            self.detect_em_dash(text, &doc, filtered, &mut diagnostics, &mut category_counts);
+            self.detect_bold_spans(text, &doc, filtered, &mut diagnostics, &mut category_counts);
+            self.detect_headings(
+                text,
+                &doc,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_bullet_items(
+                text,
+                &doc,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_subject_length(
+                text,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_emoji_bullets(text, &doc, filtered, &mut diagnostics, &mut category_counts);
+            self.detect_bold_lead_bullets(
+                text,
+                &doc,
+                filtered,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_call_to_action(
+                &words,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_confidence(
+                &words,
+                &sentences,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_section_density(
+                text,
+                &doc,
+                &sentences,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_triad_slop(
+                text,
+                &doc,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_min_code_blocks(
+                text,
+                &doc,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_required_patterns(
+                text,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_forbidden_patterns(
+                text,
+                filtered,
+                profile,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            self.detect_quotes(text, filtered, &mut diagnostics, &mut category_counts);
+            custom::detect(
+                &profile.custom_rules,
+                text,
+                &doc,
+                &sentences,
+                filtered,
+                &mut diagnostics,
+                &mut category_counts,
+            );
+            let terminology = glossary::detect(
+                &profile.glossary,
+                self.config.glossary.consistency,
+                text,
+                &doc,
+                &sentences,
+                filtered,
+            );
+            // The bundled word list is English only.
+            let spelling = match &self.spell_checker {
+                Some(checker) if language == Language::En => {
+                    spelling::detect(checker, text, &sentences, filtered)
+                }
+                _ => Vec::new(),
+            };
+            let statistical = self.statistical_diagnostics(
+                &words,
+                &sentences,
+                &section_bounds,
+                filtered,
+                profile,
+            );
+            let readability = self.readability_diagnostics(
+                text,
+                &section_bounds,
+                &section_metrics,
+                filtered,
+                profile,
+                &metrics,
+            );
+            for diag in terminology
+                .into_iter()
+                .chain(spelling)
+                .chain(statistical)
+                .chain(readability)
+            {
+                *category_counts.entry(diag.category).or_default() += 1;
+                diagnostics.push(diag);
+            }
+
+            diagnostics.retain(|diag| {
+                let ignored =
+                    filtered.is_rule_disabled(diag.span.0, &diag.rule) || settings.disables(diag);
+                if ignored {
+                    if let Some(count) = category_counts.get_mut(&diag.category) {
+                        *count -= 1;
+                    }
+                }
+                !ignored
+            });
+            (diagnostics, category_counts)
+        };
+        let mut notes = overrides;
+        notes.extend(directives::check(
             text,
-            &sentences,
-            &filtered,
-            &mut diagnostics,
-            &mut category_counts,
-        );
+            &directives,
+            &filtered.used(&directives),
+            self.config.duplicates.enabled,
+        ));
+        for diag in notes {
+            if !settings.disables(&diag) {
+                *category_counts.entry(diag.category).or_default() += 1;
+                diagnostics.push(diag);
+            }
+        }
+        category_counts.retain(|_, count| *count > 0);
+        self.apply_severity_overrides(&mut diagnostics, profile, &settings);
 
         let word_count = count_words(text);
+        let sections = section_bounds
+            .into_iter()
+            .zip(section_metrics)
+            .map(|(bounds, metrics)| Section {
+                heading_path: bounds.heading_path,
+                level: bounds.level,
+                span: bounds.span,
+                location: byte_to_location(text, bounds.span.0),
+                word_count: count_words(&text[bounds.span.0..bounds.span.1]),
+                density_per_100_words: 0.0,
+                category_counts: BTreeMap::new(),
+                metrics,
+            })
+            .collect();
 
-        DocumentReport {
+        let mut report = DocumentReport {
             word_count,
             diagnostics,
             category_counts,
             profile: profile.name.clone(),
-        }
+            metrics,
+            sections,
+        };
+        report.recount_sections();
+        report
+    }
+
+    fn locale(&self, language: Language) -> &LocaleRuntime {
+        &self.locales[&language]
+    }
+
+    /// Whitelisted phrases are compared after the same folding as matching.
+    fn is_allowed_phrase(&self, snippet: &str) -> bool {
+        self.allow_phrase_set
+            .contains(&phrases::fold(snippet, &self.config.matching))
     }
 
     fn detect_puffery(
         &self,
-        text: &str,
-        sentences: &[(String, usize)],
+        words: &Words,
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
-        if let Some(matcher) = &self.puffery_matcher {
-            for mat in matcher.find_iter(text.as_bytes()) {
-                if filtered.is_category_disabled(mat.start(), Category::Puffery) {
+        let text = words.source();
+        if let Some(matcher) = &self.locale(words.language()).puffery {
+            for mat in matcher.find_iter(words) {
+                let snippet = slice_snippet(text, mat.start, mat.end);
+                if self.is_allowed_phrase(&snippet) {
                     continue;
                 }
-                if !has_word_boundary(text, mat.start(), mat.end()) {
+                if filtered.is_category_disabled(mat.start, Category::Puffery) {
                     continue;
                 }
-                let snippet = slice_snippet(text, mat.start(), mat.end());
-                if self.allow_phrase_set.contains(&snippet.to_lowercase()) {
-                    continue;
-                }
-                let location = byte_to_location(text, mat.start());
+                let location = byte_to_location(text, mat.start);
                 if filtered.is_line_ignored(location.line) {
                     continue;
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Puffery,
+                    rule: "puffery/phrase".into(),
                     severity: Severity::Error,
                     message: format!("Puffery phrase detected: `{snippet}`"),
                     suggestion: Some("Replace with a concrete fact.".into()),
                     location,
-                    span: (mat.start(), mat.end()),
+                    span: (mat.start, mat.end),
                     snippet,
+                    fix: None,
                 });
                 *counts.entry(Category::Puffery).or_default() += 1;
             }
         }
 
-        if let Some(matcher) = &self.weasel_matcher {
-            for mat in matcher.find_iter(text.as_bytes()) {
-                if filtered.is_category_disabled(mat.start(), Category::Weasel) {
-                    continue;
-                }
-                if !has_word_boundary(text, mat.start(), mat.end()) {
-                    continue;
-                }
-                let snippet = slice_snippet(text, mat.start(), mat.end());
-                if self.allow_phrase_set.contains(&snippet.to_lowercase()) {
+        if let Some(matcher) = &self.locale(words.language()).weasel {
+            for mat in matcher.find_iter(words) {
+                let snippet = slice_snippet(text, mat.start, mat.end);
+                if self.is_allowed_phrase(&snippet) {
                     continue;
                 }
                 let sentence_idx =
-                    sentence_index_for_offset(sentences, mat.start()).unwrap_or(usize::MAX);
+                    sentence_index_for_offset(sentences, mat.start).unwrap_or(usize::MAX);
                 if sentence_idx != usize::MAX && CITATION_RE.is_match(&sentences[sentence_idx].0) {
                     continue;
                 }
-                let location = byte_to_location(text, mat.start());
+                if filtered.is_category_disabled(mat.start, Category::Weasel) {
+                    continue;
+                }
+                let location = byte_to_location(text, mat.start);
                 if filtered.is_line_ignored(location.line) {
                     continue;
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Weasel,
+                    rule: "weasel/vague-attribution".into(),
                     severity: Severity::Warning,
                     message: format!("Vague attribution: `{snippet}`"),
                     suggestion: Some("Name the specific source or remove.".into()),
                     location,
-                    span: (mat.start(), mat.end()),
+                    span: (mat.start, mat.end),
                     snippet,
+                    fix: None,
                 });
                 *counts.entry(Category::Weasel).or_default() += 1;
             }
@@ -1586,46 +2412,56 @@ impl Analyzer {
 
     fn detect_buzzwords(
         &self,
-        text: &str,
-        sentences: &[(String, usize)],
+        words: &Words,
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
-        if let Some(matcher) = &self.buzzword_matcher {
+        let text = words.source();
+        let locale = self.locale(words.language());
+        if let Some(matcher) = &locale.buzzwords {
             let mut hits: Vec<PhraseHit> = Vec::new();
-            for mat in matcher.find_iter(text.as_bytes()) {
-                if filtered.is_category_disabled(mat.start(), Category::Buzzword) {
-                    continue;
-                }
-                if !has_word_boundary(text, mat.start(), mat.end()) {
+            for mat in matcher.find_iter(words) {
+                let snippet = slice_snippet(text, mat.start, mat.end);
+                if self.is_allowed_phrase(&snippet) {
                     continue;
                 }
-                let snippet = slice_snippet(text, mat.start(), mat.end());
-                if self.allow_phrase_set.contains(&snippet.to_lowercase()) {
+                if filtered.is_category_disabled(mat.start, Category::Buzzword) {
                     continue;
                 }
-                let suggestion = replacement_for(&snippet.to_lowercase());
+                let phrase = matcher.phrase(mat.phrase);
+                let suggestion = locale.replacement_for(phrase);
+                // Swapping the replacement into an inflected form ("leveraging"
+                // -> "use") would break the sentence, so only the listed form
+                // gets a fix.
+                let listed_form = phrases::fold(&text[mat.start..mat.end], &self.config.matching)
+                    == phrases::fold(phrase, &self.config.matching);
+                let fix = suggestion
+                    .as_deref()
+                    .filter(|_| listed_form)
+                    .and_then(|replacement| buzzword_fix(text, mat.start, mat.end, replacement));
                 let sentence_idx =
-                    sentence_index_for_offset(sentences, mat.start()).unwrap_or(usize::MAX);
+                    sentence_index_for_offset(sentences, mat.start).unwrap_or(usize::MAX);
                 hits.push(PhraseHit {
-                    start: mat.start(),
-                    end: mat.end(),
+                    start: mat.start,
+                    end: mat.end,
                     snippet,
                     suggestion,
+                    fix,
                     sentence_idx,
                 });
             }
             if hits.is_empty() {
                 return;
             }
-            let mut grouped: HashMap<usize, Vec<PhraseHit>> = HashMap::new();
+            let mut grouped: BTreeMap<usize, Vec<PhraseHit>> = BTreeMap::new();
             for hit in hits {
                 grouped.entry(hit.sentence_idx).or_default().push(hit);
             }
             for (sentence_idx, group) in grouped {
                 let has_specifics = if sentence_idx != usize::MAX {
-                    SPECIFICITY_RE.is_match(&sentences[sentence_idx].0)
+                    locale.specificity.is_match(&sentences[sentence_idx].0)
                 } else {
                     false
                 };
@@ -1645,12 +2481,14 @@ impl Analyzer {
                     };
                     diagnostics.push(Diagnostic {
                         category: Category::Buzzword,
+                        rule: "buzzword/phrase".into(),
                         severity: sev,
                         message: format!("Buzzword detected: `{}`", hit.snippet),
                         suggestion: hit.suggestion.clone(),
                         location,
                         span: (hit.start, hit.end),
                         snippet: hit.snippet.clone(),
+                        fix: hit.fix.clone(),
                     });
                     *counts.entry(Category::Buzzword).or_default() += 1;
                 }
@@ -1660,45 +2498,45 @@ impl Analyzer {
 
     fn detect_transitions(
         &self,
-        text: &str,
-        sentences: &[(String, usize)],
+        words: &Words,
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
-        if let Some(matcher) = &self.transition_matcher {
+        let text = words.source();
+        let locale = self.locale(words.language());
+        if let Some(matcher) = &locale.transitions {
             let mut hits: Vec<PhraseHit> = Vec::new();
-            for mat in matcher.find_iter(text.as_bytes()) {
-                if filtered.is_category_disabled(mat.start(), Category::Transition) {
-                    continue;
-                }
-                if !has_word_boundary(text, mat.start(), mat.end()) {
+            for mat in matcher.find_iter(words) {
+                let snippet = slice_snippet(text, mat.start, mat.end);
+                if self.is_allowed_phrase(&snippet) {
                     continue;
                 }
-                let snippet = slice_snippet(text, mat.start(), mat.end());
-                if self.allow_phrase_set.contains(&snippet.to_lowercase()) {
+                if filtered.is_category_disabled(mat.start, Category::Transition) {
                     continue;
                 }
                 let sentence_idx =
-                    sentence_index_for_offset(sentences, mat.start()).unwrap_or(usize::MAX);
+                    sentence_index_for_offset(sentences, mat.start).unwrap_or(usize::MAX);
                 hits.push(PhraseHit {
-                    start: mat.start(),
-                    end: mat.end(),
+                    start: mat.start,
+                    end: mat.end,
                     snippet,
                     suggestion: Some("Trim or replace with a simple connector.".into()),
+                    fix: None,
                     sentence_idx,
                 });
             }
             if hits.is_empty() {
                 return;
             }
-            let mut grouped: HashMap<usize, Vec<PhraseHit>> = HashMap::new();
+            let mut grouped: BTreeMap<usize, Vec<PhraseHit>> = BTreeMap::new();
             for hit in hits {
                 grouped.entry(hit.sentence_idx).or_default().push(hit);
             }
             for (sentence_idx, group) in grouped {
                 let has_specifics = if sentence_idx != usize::MAX {
-                    SPECIFICITY_RE.is_match(&sentences[sentence_idx].0)
+                    locale.specificity.is_match(&sentences[sentence_idx].0)
                 } else {
                     false
                 };
@@ -1718,12 +2556,14 @@ impl Analyzer {
                     };
                     diagnostics.push(Diagnostic {
                         category: Category::Transition,
+                        rule: "transition/filler".into(),
                         severity: sev,
                         message: format!("Transitional filler detected: `{}`", hit.snippet),
                         suggestion: hit.suggestion.clone(),
                         location,
                         span: (hit.start, hit.end),
                         snippet: hit.snippet.clone(),
+                        fix: None,
                     });
                     *counts.entry(Category::Transition).or_default() += 1;
                 }
@@ -1733,35 +2573,35 @@ impl Analyzer {
 
     fn detect_marketing(
         &self,
-        text: &str,
+        words: &Words,
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
-        if let Some(matcher) = &self.marketing_matcher {
-            for mat in matcher.find_iter(text.as_bytes()) {
-                if filtered.is_category_disabled(mat.start(), Category::Marketing) {
-                    continue;
-                }
-                if !has_word_boundary(text, mat.start(), mat.end()) {
+        let text = words.source();
+        if let Some(matcher) = &self.locale(words.language()).marketing {
+            for mat in matcher.find_iter(words) {
+                let snippet = slice_snippet(text, mat.start, mat.end);
+                if self.is_allowed_phrase(&snippet) {
                     continue;
                 }
-                let snippet = slice_snippet(text, mat.start(), mat.end());
-                if self.allow_phrase_set.contains(&snippet.to_lowercase()) {
+                if filtered.is_category_disabled(mat.start, Category::Marketing) {
                     continue;
                 }
-                let location = byte_to_location(text, mat.start());
+                let location = byte_to_location(text, mat.start);
                 if filtered.is_line_ignored(location.line) {
                     continue;
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Marketing,
+                    rule: "marketing/cliche".into(),
                     severity: Severity::Error,
                     message: format!("Marketing cliché detected: `{snippet}`"),
                     suggestion: Some("Swap for factual language.".into()),
                     location,
-                    span: (mat.start(), mat.end()),
+                    span: (mat.start, mat.end),
                     snippet,
+                    fix: None,
                 });
                 *counts.entry(Category::Marketing).or_default() += 1;
             }
@@ -1783,13 +2623,13 @@ impl Analyzer {
         for regex in iterators.into_iter().flatten() {
             for mat in regex.find_iter(text) {
                 let snippet = slice_snippet(text, mat.start(), mat.end());
-                if self.allow_phrase_set.contains(&snippet.to_lowercase()) {
+                if self.is_allowed_phrase(&snippet) {
                     continue;
                 }
-                let cat = if snippet.to_lowercase().contains("not") {
-                    Category::NegativeParallel
+                let (cat, rule) = if snippet.to_lowercase().contains("not") {
+                    (Category::NegativeParallel, "negative-parallelism/phrase")
                 } else {
-                    Category::Template
+                    (Category::Template, "template/phrase")
                 };
                 if filtered.is_category_disabled(mat.start(), cat) {
                     continue;
@@ -1800,12 +2640,14 @@ impl Analyzer {
                 }
                 diagnostics.push(Diagnostic {
                     category: cat,
+                    rule: rule.into(),
                     severity: Severity::Error,
                     message: format!("Template phrasing detected: `{snippet}`"),
                     suggestion: Some("Rewrite with direct language.".into()),
                     location,
                     span: (mat.start(), mat.end()),
                     snippet,
+                    fix: None,
                 });
                 *counts.entry(cat).or_default() += 1;
             }
@@ -1815,7 +2657,7 @@ impl Analyzer {
     fn detect_connectors(
         &self,
         text: &str,
-        sentences: &[(String, usize)],
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
@@ -1835,22 +2677,23 @@ impl Analyzer {
             "at the same time",
         ];
 
-        for (sentence, offset) in sentences {
-            if filtered.is_category_disabled(*offset, Category::ConnectorGlut) {
-                continue;
-            }
+        for (sentence, offset, _) in sentences {
             let lower = sentence.to_lowercase();
             let mut count = 0;
             for connector in connectors.iter() {
                 count += lower.matches(connector).count();
             }
             if count > self.config.limits.connectors_per_sentence {
+                if filtered.is_category_disabled(*offset, Category::ConnectorGlut) {
+                    continue;
+                }
                 let location = byte_to_location(text, *offset);
                 if filtered.is_line_ignored(location.line) {
                     continue;
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::ConnectorGlut,
+                    rule: "connector-glut/per-sentence".into(),
                     severity: Severity::Warning,
                     message: format!(
                         "Sentence uses {} connectors; limit is {}.",
@@ -1860,6 +2703,7 @@ impl Analyzer {
                     location,
                     span: (*offset, *offset + sentence.len()),
                     snippet: sentence.trim().to_string(),
+                    fix: None,
                 });
                 *counts.entry(Category::ConnectorGlut).or_default() += 1;
             }
@@ -1884,12 +2728,14 @@ impl Analyzer {
             }
             diagnostics.push(Diagnostic {
                 category: Category::Weasel,
+                rule: "weasel/exaggerated-range".into(),
                 severity: Severity::Warning,
                 message: format!("Exaggerated range detected: `{snippet}`"),
                 suggestion: Some("List the specific items or tighten the range.".into()),
                 location,
                 span: (mat.start(), mat.end()),
                 snippet,
+                fix: None,
             });
             *counts.entry(Category::Weasel).or_default() += 1;
         }
@@ -1898,7 +2744,7 @@ impl Analyzer {
     fn detect_question_lead(
         &self,
         text: &str,
-        sentences: &[(String, usize)],
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
@@ -1907,36 +2753,38 @@ impl Analyzer {
         let Some(limit) = profile.question_lead_limit else {
             return;
         };
-        let mut question_count = 0usize;
-        let mut first_question_offset = None;
-        let mut first_question_snippet = String::new();
-
-        for (sentence, offset) in sentences {
-            if filtered.is_category_disabled(*offset, Category::Tone) {
-                continue;
-            }
+        let mut questions = Vec::new();
+        for (sentence, offset, kind) in sentences {
             let trimmed = sentence.trim();
             if trimmed.is_empty() {
                 continue;
             }
-            if trimmed.starts_with('#') || trimmed.starts_with('-') || trimmed.starts_with('*') {
+            if *kind != BlockKind::Paragraph {
                 continue;
             }
             if trimmed.len() < 2 {
                 continue;
             }
-            let is_question = trimmed.ends_with('?');
-            if is_question {
-                question_count += 1;
-                if first_question_offset.is_none() {
-                    first_question_offset = Some(*offset);
-                    first_question_snippet = sentence.trim().to_string();
-                }
+            if trimmed.ends_with('?') {
+                questions.push((*offset, trimmed));
+                continue;
+            }
+            if filtered.is_category_masked(*offset, Category::Tone) {
                 continue;
             }
             break;
         }
 
+        let offsets: Vec<usize> = questions.iter().map(|(offset, _)| *offset).collect();
+        let question_count = filtered.count_checked(&offsets, Category::Tone, |n| n > limit);
+        let first_question = questions
+            .iter()
+            .find(|(offset, _)| !filtered.is_category_masked(*offset, Category::Tone));
+        let first_question_offset = first_question.map(|(offset, _)| *offset);
+        let first_question_snippet = first_question
+            .map(|(_, snippet)| snippet.to_string())
+            .unwrap_or_default();
+
         if question_count > limit {
             if let Some(start) = first_question_offset {
                 let location = byte_to_location(text, start);
@@ -1945,6 +2793,7 @@ impl Analyzer {
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Tone,
+                    rule: "tone/question-lead".into(),
                     severity: Severity::Hint,
                     message: format!(
                         "Intro uses {} consecutive questions; limit is {}.",
@@ -1954,6 +2803,7 @@ impl Analyzer {
                     location,
                     span: (start, start + first_question_snippet.len()),
                     snippet: first_question_snippet.clone(),
+                    fix: None,
                 });
                 *counts.entry(Category::Tone).or_default() += 1;
             }
@@ -1963,7 +2813,7 @@ impl Analyzer {
     fn detect_mid_sentence_questions(
         &self,
         text: &str,
-        sentences: &[(String, usize)],
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
@@ -1981,12 +2831,14 @@ impl Analyzer {
                 .unwrap_or_else(|| slice_snippet(text, mat.start(), mat.end()));
             diagnostics.push(Diagnostic {
                 category: Category::Tone,
+                rule: "tone/mid-sentence-question".into(),
                 severity: Severity::Hint,
                 message: "Mid-sentence question detected.".into(),
                 suggestion: Some("Rewrite as a statement or split into two sentences.".into()),
                 location,
                 span: (mat.start(), mat.end()),
                 snippet,
+                fix: None,
             });
             *counts.entry(Category::Tone).or_default() += 1;
         }
@@ -1995,6 +2847,7 @@ impl Analyzer {
     fn detect_exclamation_density(
         &self,
         text: &str,
+        doc: &MarkdownDoc,
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
@@ -2007,81 +2860,36 @@ impl Analyzer {
             return;
         }
 
-        let mut offset = 0usize;
-        let mut paragraph = String::new();
-        let mut paragraph_start = 0usize;
-
-        for segment in text.split_inclusive('\n') {
-            let trimmed = segment.trim();
-            let is_blank = trimmed.is_empty();
-            if paragraph.is_empty() {
-                paragraph_start = offset;
-            }
-            if !is_blank {
-                paragraph.push_str(segment);
-            }
-            if is_blank {
-                self.flush_paragraph_exclamations(
-                    text,
-                    &paragraph,
-                    paragraph_start,
-                    filtered,
-                    limit,
-                    diagnostics,
-                    counts,
-                );
-                paragraph.clear();
+        for (paragraph, start) in prose_blocks(text, doc) {
+            // Skip image openers (`![alt](...)`) and marks inside code.
+            let marks: Vec<usize> = paragraph
+                .match_indices('!')
+                .map(|(idx, _)| start + idx)
+                .filter(|pos| {
+                    !filtered.is_disabled(*pos) && text.as_bytes().get(pos + 1) != Some(&b'[')
+                })
+                .collect();
+            if marks.len() <= limit || filtered.is_category_disabled(start, Category::Tone) {
+                continue;
             }
-            offset += segment.len();
-        }
-
-        if !paragraph.trim().is_empty() {
-            self.flush_paragraph_exclamations(
-                text,
-                &paragraph,
-                paragraph_start,
-                filtered,
-                limit,
-                diagnostics,
-                counts,
-            );
-        }
-    }
-
-    fn flush_paragraph_exclamations(
-        &self,
-        text: &str,
-        paragraph: &str,
-        start: usize,
-        filtered: &DisabledRanges,
-        limit: usize,
-        diagnostics: &mut Vec<Diagnostic>,
-        counts: &mut BTreeMap<Category, usize>,
-    ) {
-        if paragraph.trim().is_empty() {
-            return;
-        }
-        if filtered.is_category_disabled(start, Category::Tone) {
-            return;
-        }
-        let count = paragraph.matches('!').count();
-        if count > limit {
-            let relative = paragraph.find('!').unwrap_or(0);
-            let location = byte_to_location(text, start + relative);
+            let location = byte_to_location(text, marks[0]);
             if filtered.is_line_ignored(location.line) {
-                return;
+                continue;
             }
             diagnostics.push(Diagnostic {
                 category: Category::Tone,
+                rule: "tone/exclamation-density".into(),
                 severity: Severity::Hint,
                 message: format!(
                     "Paragraph contains {} exclamation marks; limit is {}.",
-                    count, limit
+                    marks.len(),
+                    limit
                 ),
                 suggestion: Some("Reduce promotional punctuation.".into()),
                 location,
                 span: (start, start + paragraph.len()),
                 snippet: paragraph.trim().to_string(),
+                fix: None,
             });
             *counts.entry(Category::Tone).or_default() += 1;
         }
@@ -2090,7 +2898,7 @@ impl Analyzer {
     fn detect_sentence_length(
         &self,
         text: &str,
-        sentences: &[(String, usize)],
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
@@ -2099,18 +2907,19 @@ impl Analyzer {
         let Some(limit) = profile.max_sentence_length else {
             return;
         };
-        for (sentence, offset) in sentences {
-            if filtered.is_category_disabled(*offset, Category::SentenceLength) {
-                continue;
-            }
+        for (sentence, offset, _) in sentences {
             let word_count = sentence.split_whitespace().count();
             if word_count > limit {
+                if filtered.is_category_disabled(*offset, Category::SentenceLength) {
+                    continue;
+                }
                 let location = byte_to_location(text, *offset);
                 if filtered.is_line_ignored(location.line) {
                     continue;
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::SentenceLength,
+                    rule: "sentence-length/max-words".into(),
                     severity: Severity::Hint,
                     message: format!(
                         "Sentence length {} exceeds limit of {} words.",
@@ -2120,6 +2929,7 @@ impl Analyzer {
                     location,
                     span: (*offset, *offset + sentence.len()),
                     snippet: sentence.trim().to_string(),
+                    fix: None,
                 });
                 *counts.entry(Category::SentenceLength).or_default() += 1;
             }
@@ -2129,7 +2939,7 @@ impl Analyzer {
     fn detect_repetition(
         &self,
         text: &str,
-        sentences: &[(String, usize)],
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
@@ -2139,14 +2949,18 @@ impl Analyzer {
             return;
         }
         let mut seen: HashMap<String, usize> = HashMap::new();
-        for (sentence, offset) in sentences {
-            if filtered.is_category_disabled(*offset, Category::Repetition) {
-                continue;
-            }
+        for (sentence, offset, _) in sentences {
             let normalised = normalize_sentence(sentence);
             if normalised.len() < 12 {
                 continue;
             }
+            let repeat = seen.get(&normalised).copied().unwrap_or_default()
+                >= profile.max_duplicate_sentences;
+            if (repeat && filtered.is_category_disabled(*offset, Category::Repetition))
+                || filtered.is_category_masked(*offset, Category::Repetition)
+            {
+                continue;
+            }
             let entry = seen.entry(normalised).and_modify(|c| *c += 1).or_insert(1);
             if *entry > profile.max_duplicate_sentences {
                 let location = byte_to_location(text, *offset);
@@ -2155,12 +2969,14 @@ impl Analyzer {
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Repetition,
+                    rule: "repetition/duplicate-sentence".into(),
                     severity: Severity::Warning,
                     message: "Sentence repeats earlier phrasing.".into(),
                     suggestion: Some("Introduce new detail or remove duplicates.".into()),
                     location,
                     span: (*offset, *offset + sentence.len()),
                     snippet: sentence.trim().to_string(),
+                    fix: None,
                 });
                 *counts.entry(Category::Repetition).or_default() += 1;
             }
@@ -2170,7 +2986,7 @@ impl Analyzer {
     fn detect_cadence(
         &self,
         text: &str,
-        sentences: &[(String, usize)],
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
@@ -2179,111 +2995,117 @@ impl Analyzer {
         if profile.cadence_starts.is_empty() || profile.cadence_limit == 0 {
             return;
         }
-        let mut previous: Option<String> = None;
-        let mut streak = 0usize;
-
-        for (sentence, offset) in sentences {
-            if filtered.is_category_disabled(*offset, Category::Cadence) {
-                continue;
+        let opening = |sentence: &str, kind: &BlockKind| {
+            if *kind != BlockKind::Paragraph {
+                return None;
             }
-            let trimmed = sentence.trim_start();
-            if trimmed.is_empty() {
-                previous = None;
-                streak = 0;
+            let cleaned = sentence
+                .split_whitespace()
+                .next()?
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase();
+            profile.cadence_starts.contains(&cleaned).then_some(cleaned)
+        };
+        // Streak length after a sentence with `opening`; `None` breaks it.
+        fn step(streak: &mut (Option<String>, usize), opening: Option<String>) -> usize {
+            *streak = match (streak.0.take(), opening) {
+                (Some(prev), Some(cleaned)) if prev == cleaned => (Some(prev), streak.1 + 1),
+                (_, Some(cleaned)) => (Some(cleaned), 1),
+                (_, None) => (None, 0),
+            };
+            streak.1
+        }
+        let mut streak = (None, 0usize);
+        // The streak as it would run with nothing hidden, so a directive is
+        // marked used only when it hides a sentence that would be reported.
+        let mut unhidden = (None, 0usize);
+
+        for (sentence, offset, kind) in sentences {
+            let opening = opening(sentence, kind);
+            let would_report = step(&mut unhidden, opening.clone()) > profile.cadence_limit;
+            if (would_report && filtered.is_category_disabled(*offset, Category::Cadence))
+                || filtered.is_category_masked(*offset, Category::Cadence)
+            {
                 continue;
             }
-            if trimmed.starts_with('#') || trimmed.starts_with('-') || trimmed.starts_with('*') {
-                previous = None;
-                streak = 0;
+            if step(&mut streak, opening) <= profile.cadence_limit {
                 continue;
             }
-            if let Some(first) = trimmed.split_whitespace().next() {
-                let cleaned = first
-                    .trim_matches(|c: char| !c.is_alphanumeric())
-                    .to_lowercase();
-                if profile.cadence_starts.contains(&cleaned) {
-                    if let Some(prev) = &previous {
-                        if prev == &cleaned {
-                            streak += 1;
-                        } else {
-                            previous = Some(cleaned.clone());
-                            streak = 1;
-                        }
-                    } else {
-                        previous = Some(cleaned.clone());
-                        streak = 1;
-                    }
-                    if streak > profile.cadence_limit {
-                        let location = byte_to_location(text, *offset);
-                        if filtered.is_line_ignored(location.line) {
-                            continue;
-                        }
-                        diagnostics.push(Diagnostic {
-                            category: Category::Cadence,
-                            severity: Severity::Hint,
-                            message: format!(
-                                "Cadence repeats opening `{}` more than {} times in a row.",
-                                first.trim_matches(|c: char| !c.is_alphanumeric()),
-                                profile.cadence_limit
-                            ),
-                            suggestion: Some("Vary sentence openings to avoid monotony.".into()),
-                            location,
-                            span: (*offset, *offset + sentence.len()),
-                            snippet: sentence.trim().to_string(),
-                        });
-                        *counts.entry(Category::Cadence).or_default() += 1;
-                    }
-                    continue;
-                }
+            let location = byte_to_location(text, *offset);
+            if filtered.is_line_ignored(location.line) {
+                continue;
             }
-            previous = None;
-            streak = 0;
+            let first = sentence.split_whitespace().next().unwrap_or_default();
+            diagnostics.push(Diagnostic {
+                category: Category::Cadence,
+                rule: "cadence/opening-streak".into(),
+                severity: Severity::Hint,
+                message: format!(
+                    "Cadence repeats opening `{}` more than {} times in a row.",
+                    first.trim_matches(|c: char| !c.is_alphanumeric()),
+                    profile.cadence_limit
+                ),
+                suggestion: Some("Vary sentence openings to avoid monotony.".into()),
+                location,
+                span: (*offset, *offset + sentence.len()),
+                snippet: sentence.trim().to_string(),
+                fix: None,
+            });
+            *counts.entry(Category::Cadence).or_default() += 1;
         }
     }
 
     fn detect_broad_terms(
         &self,
-        text: &str,
-        sentences: &[(String, usize)],
+        words: &Words,
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
-        if profile.broad_terms.is_empty() {
+        let Some(matcher) = &profile.broad_term_matcher else {
             return;
-        }
-        for (sentence, offset) in sentences {
-            if filtered.is_category_disabled(*offset, Category::BroadTerm) {
-                continue;
-            }
-            let lower = sentence.to_lowercase();
-            if !lower.chars().any(|c| c.is_alphabetic()) {
+        };
+        let text = words.source();
+        let specificity = &self.locale(words.language()).specificity;
+        let hits = matcher.find_iter(words);
+        for (sentence, offset, kind) in sentences {
+            if !sentence.chars().any(|c| c.is_alphabetic()) {
                 continue;
             }
-            if lower.starts_with('#') {
+            if *kind == BlockKind::Heading {
                 continue;
             }
-            if SPECIFICITY_RE.is_match(sentence) {
+            if specificity.is_match(sentence) {
                 continue;
             }
-            if let Some(term) = profile
-                .broad_terms
+            let end = *offset + sentence.len();
+            // Report the first listed term found in the sentence.
+            if let Some(term) = hits
                 .iter()
-                .find(|term| find_term_with_boundary(&lower, term).is_some())
+                .filter(|hit| hit.start >= *offset && hit.end <= end)
+                .map(|hit| hit.phrase)
+                .min()
+                .map(|idx| matcher.phrase(idx))
             {
+                if filtered.is_category_disabled(*offset, Category::BroadTerm) {
+                    continue;
+                }
                 let location = byte_to_location(text, *offset);
                 if filtered.is_line_ignored(location.line) {
                     continue;
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::BroadTerm,
+                    rule: "broad-term/unspecific".into(),
                     severity: Severity::Hint,
                     message: format!("Broad term `{}` detected without specifics.", term),
                     suggestion: Some("Replace with a concrete description.".into()),
                     location,
                     span: (*offset, *offset + sentence.len()),
                     snippet: sentence.trim().to_string(),
+                    fix: None,
                 });
                 *counts.entry(Category::BroadTerm).or_default() += 1;
             }
@@ -2292,33 +3114,33 @@ impl Analyzer {
 
     fn detect_call_to_action(
         &self,
-        text: &str,
+        words: &Words,
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
+        let text = words.source();
         if let Some(matcher) = &profile.call_to_action_matcher {
-            for mat in matcher.find_iter(text.as_bytes()) {
-                if filtered.is_category_disabled(mat.start(), Category::CallToAction) {
-                    continue;
-                }
-                if !has_word_boundary(text, mat.start(), mat.end()) {
+            for mat in matcher.find_iter(words) {
+                if filtered.is_category_disabled(mat.start, Category::CallToAction) {
                     continue;
                 }
-                let snippet = slice_snippet(text, mat.start(), mat.end());
-                let location = byte_to_location(text, mat.start());
+                let snippet = slice_snippet(text, mat.start, mat.end);
+                let location = byte_to_location(text, mat.start);
                 if filtered.is_line_ignored(location.line) {
                     continue;
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::CallToAction,
+                    rule: "call-to-action/phrase".into(),
                     severity: Severity::Warning,
                     message: format!("Call-to-action template detected: `{snippet}`"),
                     suggestion: Some("Use a direct statement instead of marketing CTA.".into()),
                     location,
-                    span: (mat.start(), mat.end()),
+                    span: (mat.start, mat.end),
                     snippet,
+                    fix: None,
                 });
                 *counts.entry(Category::CallToAction).or_default() += 1;
             }
@@ -2327,24 +3149,20 @@ impl Analyzer {
 
     fn detect_confidence(
         &self,
-        text: &str,
-        sentences: &[(String, usize)],
+        words: &Words,
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
+        let text = words.source();
         let mut flagged: HashSet<usize> = HashSet::new();
 
         if let Some(matcher) = &profile.confidence_matcher {
-            for mat in matcher.find_iter(text.as_bytes()) {
-                let start = mat.start();
-                if filtered.is_category_disabled(start, Category::Confidence)
-                    || flagged.contains(&start)
-                {
-                    continue;
-                }
-                if !has_word_boundary(text, start, mat.end()) {
+            for mat in matcher.find_iter(words) {
+                let start = mat.start;
+                if flagged.contains(&start) {
                     continue;
                 }
                 let sentence_idx =
@@ -2352,19 +3170,24 @@ impl Analyzer {
                 if sentence_idx != usize::MAX && CITATION_RE.is_match(&sentences[sentence_idx].0) {
                     continue;
                 }
-                let snippet = slice_snippet(text, start, mat.end());
+                if filtered.is_category_disabled(start, Category::Confidence) {
+                    continue;
+                }
+                let snippet = slice_snippet(text, start, mat.end);
                 let location = byte_to_location(text, start);
                 if filtered.is_line_ignored(location.line) {
                     continue;
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Confidence,
+                    rule: "confidence/unsupported-claim".into(),
                     severity: Severity::Warning,
                     message: format!("Confidence claim `{snippet}` detected without evidence."),
                     suggestion: Some("Provide a source or remove the claim.".into()),
                     location,
-                    span: (start, mat.end()),
+                    span: (start, mat.end),
                     snippet,
+                    fix: None,
                 });
                 *counts.entry(Category::Confidence).or_default() += 1;
                 flagged.insert(start);
@@ -2374,9 +3197,7 @@ impl Analyzer {
         if profile.detect_percent_claims {
             for mat in CONFIDENCE_PERCENT_RE.find_iter(text) {
                 let start = mat.start();
-                if filtered.is_category_disabled(start, Category::Confidence)
-                    || flagged.contains(&start)
-                {
+                if flagged.contains(&start) {
                     continue;
                 }
                 let sentence_idx =
@@ -2389,6 +3210,9 @@ impl Analyzer {
                 {
                     continue;
                 }
+                if filtered.is_category_disabled(start, Category::Confidence) {
+                    continue;
+                }
                 let end = mat.end();
                 let snippet = slice_snippet(text, start, end);
                 let location = byte_to_location(text, start);
@@ -2397,6 +3221,7 @@ impl Analyzer {
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Confidence,
+                    rule: "confidence/numeric-claim".into(),
                     severity: Severity::Warning,
                     message: format!(
                         "Numeric confidence `{snippet}` detected without supporting context."
@@ -2405,6 +3230,7 @@ impl Analyzer {
                     location,
                     span: (start, end),
                     snippet,
+                    fix: None,
                 });
                 *counts.entry(Category::Confidence).or_default() += 1;
                 flagged.insert(start);
@@ -2420,6 +3246,14 @@ impl Analyzer {
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
+        let missing: Vec<&Regex> = profile
+            .required_patterns
+            .iter()
+            .filter(|regex| regex.find(text).is_none())
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
         let Some(anchor) = analysis_anchor_offset(filtered, text) else {
             return;
         };
@@ -2430,21 +3264,21 @@ impl Analyzer {
         if filtered.is_line_ignored(location.line) {
             return;
         }
-        for regex in &profile.required_patterns {
-            if regex.find(text).is_none() {
-                diagnostics.push(Diagnostic {
-                    category: Category::Structure,
-                    severity: Severity::Warning,
-                    message: format!("Required pattern `{}` not found.", regex.as_str()),
-                    suggestion: Some("Add the missing section or reference.".into()),
-                    location: location.clone(),
-                    span: (anchor, anchor),
-                    snippet: String::new(),
-                });
-                *counts.entry(Category::Structure).or_default() += 1;
-            }
-        }
-    }
+        for regex in missing {
+            diagnostics.push(Diagnostic {
+                category: Category::Structure,
+                rule: "structure/required-pattern".into(),
+                severity: Severity::Warning,
+                message: format!("Required pattern `{}` not found.", regex.as_str()),
+                suggestion: Some("Add the missing section or reference.".into()),
+                location: location.clone(),
+                span: (anchor, anchor),
+                snippet: String::new(),
+                fix: None,
+            });
+            *counts.entry(Category::Structure).or_default() += 1;
+        }
+    }
 
     fn detect_forbidden_patterns(
         &self,
@@ -2465,12 +3299,14 @@ impl Analyzer {
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Structure,
+                    rule: "structure/forbidden-pattern".into(),
                     severity: Severity::Warning,
                     message: format!("Forbidden pattern `{}` detected.", regex.as_str()),
                     suggestion: Some("Remove or rewrite the offending section.".into()),
                     location,
                     span: (mat.start(), mat.end()),
                     snippet: slice_snippet(text, mat.start(), mat.end()),
+                    fix: None,
                 });
                 *counts.entry(Category::Structure).or_default() += 1;
             }
@@ -2480,6 +3316,7 @@ impl Analyzer {
     fn detect_bullet_items(
         &self,
         text: &str,
+        doc: &MarkdownDoc,
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
@@ -2489,128 +3326,109 @@ impl Analyzer {
             return;
         };
 
-        let mut current = 0usize;
-        let mut start_line = 0usize;
-        let mut start_offset = 0usize;
-
-        for (idx, line) in text.lines().enumerate() {
-            let trimmed = line.trim_start();
-            let is_bullet =
-                trimmed.starts_with("- ") || trimmed.starts_with("* ") || is_numbered_list(trimmed);
-            if is_bullet {
-                let offset = line_offset(text, idx);
-                if filtered.is_category_disabled(offset, Category::Structure) {
-                    continue;
-                }
-                if current == 0 {
-                    start_line = idx + 1;
-                    start_offset = offset;
-                }
-                current += 1;
-            } else if current > 0 {
-                if current > limit {
-                    let location = byte_to_location(text, start_offset);
-                    if filtered.is_line_ignored(location.line) {
-                        current = 0;
-                        continue;
-                    }
-                    diagnostics.push(Diagnostic {
-                        category: Category::Structure,
-                        severity: Severity::Hint,
-                        message: format!("List contains {} items; limit is {}.", current, limit),
-                        suggestion: Some("Break long lists into sub-sections.".into()),
-                        location,
-                        span: (start_offset, start_offset + line.len()),
-                        snippet: text
-                            .lines()
-                            .skip(start_line - 1)
-                            .take(current)
-                            .collect::<Vec<_>>()
-                            .join("\n"),
-                    });
-                    *counts.entry(Category::Structure).or_default() += 1;
-                }
-                current = 0;
+        // Nested lists are separate entries, so each level is judged on its own items.
+        for list in &doc.lists {
+            let offsets: Vec<usize> = list.items.iter().map(|item| item.span.0).collect();
+            let items = filtered.count_checked(&offsets, Category::Structure, |n| n > limit);
+            if items <= limit {
+                continue;
             }
-        }
-
-        if current > limit {
-            let location = byte_to_location(text, start_offset);
+            let (start, end) = list.span;
+            let location = byte_to_location(text, start);
             if filtered.is_line_ignored(location.line) {
-                return;
+                continue;
             }
             diagnostics.push(Diagnostic {
                 category: Category::Structure,
+                rule: "structure/max-bullet-items".into(),
                 severity: Severity::Hint,
-                message: format!("List contains {} items; limit is {}.", current, limit),
+                message: format!("List contains {} items; limit is {}.", items, limit),
                 suggestion: Some("Break long lists into sub-sections.".into()),
                 location,
-                span: (start_offset, start_offset + text.len()),
-                snippet: String::new(),
+                span: (start, end),
+                snippet: slice_snippet(text, start, end),
+                fix: None,
             });
             *counts.entry(Category::Structure).or_default() += 1;
         }
     }
 
+    fn detect_subject_length(
+        &self,
+        text: &str,
+        filtered: &DisabledRanges,
+        profile: &ProfileRuntime,
+        diagnostics: &mut Vec<Diagnostic>,
+        counts: &mut BTreeMap<Category, usize>,
+    ) {
+        let Some(limit) = profile.max_subject_length else {
+            return;
+        };
+        let start = text.len() - text.trim_start().len();
+        let subject = text[start..].lines().next().unwrap_or_default().trim_end();
+        let length = subject.chars().count();
+        if length <= limit || filtered.is_category_disabled(start, Category::Structure) {
+            return;
+        }
+        let location = byte_to_location(text, start);
+        if filtered.is_line_ignored(location.line) {
+            return;
+        }
+        let end = start + subject.len();
+        diagnostics.push(Diagnostic {
+            category: Category::Structure,
+            rule: "structure/subject-length".into(),
+            severity: Severity::Warning,
+            message: format!("Subject line is {length} characters; limit is {limit}."),
+            suggestion: Some("Shorten the subject and move detail into the body.".into()),
+            location,
+            span: (start, end),
+            snippet: slice_snippet(text, start, end),
+            fix: None,
+        });
+        *counts.entry(Category::Structure).or_default() += 1;
+    }
+
     fn detect_emoji_bullets(
         &self,
         text: &str,
+        doc: &MarkdownDoc,
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
-        for (idx, line) in text.lines().enumerate() {
-            let offset = line_offset(text, idx);
+        for item in doc.lists.iter().flat_map(|list| &list.items) {
+            let offset = item.span.0;
+            if !item.lead_char.is_some_and(|ch| EMOJI_HINTS.contains(&ch)) {
+                continue;
+            }
             if filtered.is_category_disabled(offset, Category::Formatting) {
                 continue;
             }
-            let trimmed = line.trim_start();
-            let content = if trimmed.starts_with("- ") || trimmed.starts_with("* ") {
-                trimmed[2..].trim_start()
-            } else if is_numbered_list(trimmed) {
-                let mut chars = trimmed.char_indices();
-                let mut start = None;
-                while let Some((i, ch)) = chars.next() {
-                    if ch.is_ascii_digit() || ch == '.' || ch == ')' {
-                        continue;
-                    }
-                    if ch.is_whitespace() {
-                        continue;
-                    }
-                    start = Some(i);
-                    break;
-                }
-                match start {
-                    Some(i) => &trimmed[i..],
-                    None => continue,
-                }
-            } else {
+            let location = byte_to_location(text, offset);
+            if filtered.is_line_ignored(location.line) {
                 continue;
-            };
-            if let Some(first) = content.trim_start().chars().next() {
-                if EMOJI_HINTS.contains(&first) {
-                    let location = byte_to_location(text, offset);
-                    if filtered.is_line_ignored(location.line) {
-                        continue;
-                    }
-                    diagnostics.push(Diagnostic {
-                        category: Category::Formatting,
-                        severity: Severity::Hint,
-                        message: "Emoji-led bullet detected.".into(),
-                        suggestion: Some("Use plain text bullets to reduce stylized noise.".into()),
-                        location,
-                        span: (offset, offset + line.len()),
-                        snippet: line.to_string(),
-                    });
-                    *counts.entry(Category::Formatting).or_default() += 1;
-                }
             }
+            let line = text[offset..].lines().next().unwrap_or("");
+            diagnostics.push(Diagnostic {
+                category: Category::Formatting,
+                rule: "formatting/emoji-bullet".into(),
+                severity: Severity::Hint,
+                message: "Emoji-led bullet detected.".into(),
+                suggestion: Some("Use plain text bullets to reduce stylized noise.".into()),
+                location,
+                span: (offset, offset + line.len()),
+                snippet: line.to_string(),
+                fix: None,
+            });
+            *counts.entry(Category::Formatting).or_default() += 1;
         }
     }
 
     fn detect_bold_lead_bullets(
         &self,
         text: &str,
+        doc: &MarkdownDoc,
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
@@ -2620,182 +3438,76 @@ impl Analyzer {
             return;
         }
 
-        let mut in_list = false;
-        let mut bold_leads = 0usize;
-        let mut list_start = 0usize;
-        let mut list_end = 0usize;
-        let mut snippet_lines: Vec<String> = Vec::new();
-
-        let flush = |in_list: &mut bool,
-                     bold_leads: &mut usize,
-                     list_start: &mut usize,
-                     list_end: &mut usize,
-                     snippet_lines: &mut Vec<String>,
-                     filtered: &DisabledRanges,
-                     diagnostics: &mut Vec<Diagnostic>,
-                     counts: &mut BTreeMap<Category, usize>,
-                     text: &str,
-                     limit: usize| {
-            if *in_list && *bold_leads >= limit {
-                if !filtered.is_category_disabled(*list_start, Category::Formatting) {
-                    let location = byte_to_location(text, *list_start);
-                    if !filtered.is_line_ignored(location.line) {
-                        diagnostics.push(Diagnostic {
-                            category: Category::Formatting,
-                            severity: Severity::Hint,
-                            message: format!(
-                                "List uses {} bold-led bullets; limit is {}.",
-                                *bold_leads, limit
-                            ),
-                            suggestion: Some("Use plain bullets or reduce bold lead-ins.".into()),
-                            location,
-                            span: (*list_start, (*list_end).max(*list_start)),
-                            snippet: snippet_lines.join("\n"),
-                        });
-                        *counts.entry(Category::Formatting).or_default() += 1;
-                    }
-                }
-            }
-            *in_list = false;
-            *bold_leads = 0;
-            *list_start = 0;
-            *list_end = 0;
-            snippet_lines.clear();
-        };
-
-        for (idx, line) in text.lines().enumerate() {
-            let offset = line_offset(text, idx);
-            if filtered.is_category_disabled(offset, Category::Formatting) {
-                flush(
-                    &mut in_list,
-                    &mut bold_leads,
-                    &mut list_start,
-                    &mut list_end,
-                    &mut snippet_lines,
-                    filtered,
-                    diagnostics,
-                    counts,
-                    text,
-                    limit,
-                );
-                continue;
-            }
-
-            let trimmed_full = line.trim();
-            if trimmed_full.starts_with("<!--")
-                && trimmed_full.ends_with("-->")
-                && trimmed_full.contains("dwg:")
-            {
-                continue;
-            }
-
-            let trimmed = line.trim_start();
-            if trimmed.is_empty() {
-                flush(
-                    &mut in_list,
-                    &mut bold_leads,
-                    &mut list_start,
-                    &mut list_end,
-                    &mut snippet_lines,
-                    filtered,
-                    diagnostics,
-                    counts,
-                    text,
-                    limit,
-                );
+        for list in &doc.lists {
+            let (start, end) = list.span;
+            let offsets: Vec<usize> = list
+                .items
+                .iter()
+                .filter(|item| {
+                    item.bold_lead
+                        && !filtered.is_line_ignored(byte_to_location(text, item.span.0).line)
+                })
+                .map(|item| item.span.0)
+                .collect();
+            let bold_leads = filtered.count_checked(&offsets, Category::Formatting, |n| n >= limit);
+            if bold_leads < limit || filtered.is_category_disabled(start, Category::Formatting) {
                 continue;
             }
-
-            let content = if trimmed.starts_with("- ") || trimmed.starts_with("* ") {
-                Some(trimmed[2..].trim_start())
-            } else if is_numbered_list(trimmed) {
-                let mut chars = trimmed.char_indices();
-                let mut start = None;
-                while let Some((i, ch)) = chars.next() {
-                    if ch.is_ascii_digit() || ch == '.' || ch == ')' {
-                        continue;
-                    }
-                    if ch.is_whitespace() {
-                        continue;
-                    }
-                    start = Some(i);
-                    break;
-                }
-                start.map(|i| trimmed[i..].trim_start())
-            } else {
-                None
-            };
-
-            let Some(content) = content else {
-                flush(
-                    &mut in_list,
-                    &mut bold_leads,
-                    &mut list_start,
-                    &mut list_end,
-                    &mut snippet_lines,
-                    filtered,
-                    diagnostics,
-                    counts,
-                    text,
-                    limit,
-                );
+            let location = byte_to_location(text, start);
+            if filtered.is_line_ignored(location.line) {
                 continue;
-            };
-
-            if !in_list {
-                in_list = true;
-                list_start = offset;
-                snippet_lines.clear();
-                bold_leads = 0;
-            }
-            list_end = offset + line.len();
-            if snippet_lines.len() < 8 {
-                snippet_lines.push(line.to_string());
-            }
-            let content_trimmed = content.trim_start();
-            if !filtered.is_line_ignored(idx + 1)
-                && (content_trimmed.starts_with("**") || content_trimmed.starts_with("__"))
-            {
-                bold_leads += 1;
             }
+            diagnostics.push(Diagnostic {
+                category: Category::Formatting,
+                rule: "formatting/bold-lead-bullets".into(),
+                severity: Severity::Hint,
+                message: format!(
+                    "List uses {} bold-led bullets; limit is {}.",
+                    bold_leads, limit
+                ),
+                suggestion: Some("Use plain bullets or reduce bold lead-ins.".into()),
+                location,
+                span: (start, end),
+                snippet: text[start..end]
+                    .lines()
+                    .take(8)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                fix: None,
+            });
+            *counts.entry(Category::Formatting).or_default() += 1;
         }
-
-        flush(
-            &mut in_list,
-            &mut bold_leads,
-            &mut list_start,
-            &mut list_end,
-            &mut snippet_lines,
-            filtered,
-            diagnostics,
-            counts,
-            text,
-            limit,
-        );
     }
 
     fn detect_rule_of_three(
         &self,
         text: &str,
+        doc: &MarkdownDoc,
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
-        for (paragraph, offset) in split_paragraphs_with_offset(text) {
-            if filtered.is_category_disabled(offset, Category::RuleOfThree) {
-                continue;
-            }
+        for (paragraph, offset) in prose_blocks(text, doc) {
             if paragraph.trim().is_empty() {
                 continue;
             }
+            let limit = self.config.limits.rule_of_three_per_paragraph;
+            let paragraph_masked = filtered.is_category_masked(offset, Category::RuleOfThree);
+            let matches: Vec<_> = self.rule_of_three_regex.find_iter(paragraph).collect();
+            let hidden = |mat: &regex::Match| {
+                paragraph_masked
+                    || filtered.is_category_masked(offset + mat.start(), Category::RuleOfThree)
+            };
+            // A hidden triplet past the limit would have been reported.
+            for mat in matches.iter().skip(limit).filter(|mat| hidden(mat)) {
+                filtered.mark_used(offset, Category::RuleOfThree);
+                filtered.mark_used(offset + mat.start(), Category::RuleOfThree);
+            }
             let mut seen = 0;
-            for mat in self.rule_of_three_regex.find_iter(paragraph) {
+            for mat in matches.iter().filter(|mat| !hidden(mat)) {
                 let m_start = offset + mat.start();
-                if filtered.is_category_disabled(m_start, Category::RuleOfThree) {
-                    continue;
-                }
                 seen += 1;
-                if seen > self.config.limits.rule_of_three_per_paragraph {
+                if seen > limit {
                     let snippet = slice_snippet(text, m_start, m_start + mat.as_str().len());
                     let location = byte_to_location(text, m_start);
                     if filtered.is_line_ignored(location.line) {
@@ -2803,12 +3515,14 @@ impl Analyzer {
                     }
                     diagnostics.push(Diagnostic {
                         category: Category::RuleOfThree,
+                        rule: "rule-of-three/triplet".into(),
                         severity: Severity::Warning,
                         message: format!("Rule-of-three phrasing detected: `{snippet}`"),
                         suggestion: Some("Reduce to the single concrete item that matters.".into()),
                         location,
                         span: (m_start, m_start + mat.as_str().len()),
                         snippet,
+                        fix: None,
                     });
                     *counts.entry(Category::RuleOfThree).or_default() += 1;
                 }
@@ -2819,25 +3533,27 @@ impl Analyzer {
     fn detect_em_dash(
         &self,
         text: &str,
+        doc: &MarkdownDoc,
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
-        for (paragraph, offset) in split_paragraphs_with_offset(text) {
-            if filtered.is_category_disabled(offset, Category::EmDash) {
-                continue;
-            }
+        for (paragraph, offset) in prose_blocks(text, doc) {
             if paragraph.trim().is_empty() {
                 continue;
             }
             let occurrences = paragraph.matches('—').count();
             if occurrences > self.config.limits.em_dashes_per_paragraph {
+                if filtered.is_category_disabled(offset, Category::EmDash) {
+                    continue;
+                }
                 let location = byte_to_location(text, offset);
                 if filtered.is_line_ignored(location.line) {
                     continue;
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::EmDash,
+                    rule: "em-dash/density".into(),
                     severity: Severity::Hint,
                     message: format!(
                         "Paragraph contains {} em dashes; limit is {}.",
@@ -2847,6 +3563,7 @@ impl Analyzer {
                     location,
                     span: (offset, offset + paragraph.len()),
                     snippet: paragraph.trim().to_string(),
+                    fix: None,
                 });
                 *counts.entry(Category::EmDash).or_default() += 1;
             }
@@ -2856,6 +3573,7 @@ impl Analyzer {
     fn detect_bold_spans(
         &self,
         text: &str,
+        doc: &MarkdownDoc,
         filtered: &DisabledRanges,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
@@ -2864,34 +3582,33 @@ impl Analyzer {
         if limit == 0 {
             return;
         }
-        for (paragraph, offset) in split_paragraphs_with_offset(text) {
+        for (paragraph, offset) in prose_blocks(text, doc) {
             if paragraph.trim().is_empty() {
                 continue;
             }
-            if filtered.is_category_disabled(offset, Category::Formatting) {
-                continue;
-            }
-            let mut count = 0usize;
-            for mat in BOLD_SPAN_RE.find_iter(paragraph) {
-                let abs = offset + mat.start();
-                if filtered.is_category_disabled(abs, Category::Formatting) {
+            let spans: Vec<usize> = BOLD_SPAN_RE
+                .find_iter(paragraph)
+                .map(|mat| offset + mat.start())
+                .collect();
+            let count = filtered.count_checked(&spans, Category::Formatting, |n| n > limit);
+            if count > limit {
+                if filtered.is_category_disabled(offset, Category::Formatting) {
                     continue;
                 }
-                count += 1;
-            }
-            if count > limit {
                 let location = byte_to_location(text, offset);
                 if filtered.is_line_ignored(location.line) {
                     continue;
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Formatting,
+                    rule: "formatting/bold-spans".into(),
                     severity: Severity::Hint,
                     message: format!("Paragraph uses {} bold spans; limit is {}.", count, limit),
                     suggestion: Some("Use bold sparingly or convert to plain labels.".into()),
                     location,
                     span: (offset, offset + paragraph.len()),
                     snippet: paragraph.trim().to_string(),
+                    fix: None,
                 });
                 *counts.entry(Category::Formatting).or_default() += 1;
             }
@@ -2901,52 +3618,48 @@ impl Analyzer {
     fn detect_headings(
         &self,
         text: &str,
+        doc: &MarkdownDoc,
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
         counts: &mut BTreeMap<Category, usize>,
     ) {
-        let mut captures: Vec<HeadingCapture> = Vec::new();
+        // Every heading is checked on its own, so directives that hide one
+        // are marked used; the counts only see the headings left checked.
+        let all_captures = heading_captures(text, doc);
+        let captures: Vec<HeadingCapture> = all_captures
+            .iter()
+            .filter(|cap| !filtered.is_masked(cap.offset))
+            .cloned()
+            .collect();
 
-        for (idx, line) in text.lines().enumerate() {
-            let offset = line_offset(text, idx);
-            if filtered.is_disabled(offset) {
-                continue;
-            }
-            if !line.trim_start().starts_with('#') {
-                continue;
-            }
-            let content = line.trim_start_matches('#').trim();
-            if content.is_empty() {
-                continue;
-            }
-            let level = line.chars().take_while(|c| *c == '#').count();
-            let capture = HeadingCapture {
-                line: idx + 1,
-                column: 1,
-                offset,
-                len: line.len(),
-                text: line.to_string(),
-                lower: content.to_lowercase(),
+        for capture in &all_captures {
+            let offset = capture.offset;
+            let span = (offset, offset + capture.len);
+            let location = Location {
+                line: capture.line,
+                column: capture.column,
             };
-            captures.push(capture);
+            let content = capture.content.as_str();
 
             if let Some(max_depth) = profile.max_heading_depth {
-                if level > max_depth
+                if capture.level > max_depth
                     && !filtered.is_category_disabled(offset, Category::Structure)
-                    && !filtered.is_line_ignored(idx + 1)
+                    && !filtered.is_line_ignored(capture.line)
                 {
                     diagnostics.push(Diagnostic {
                         category: Category::Structure,
+                        rule: "structure/max-heading-depth".into(),
                         severity: Severity::Hint,
-                        message: format!("Heading depth {} exceeds limit {}.", level, max_depth),
+                        message: format!(
+                            "Heading depth {} exceeds limit {}.",
+                            capture.level, max_depth
+                        ),
                         suggestion: Some("Flatten heading structure or use fewer levels.".into()),
-                        location: Location {
-                            line: idx + 1,
-                            column: 1,
-                        },
-                        span: (offset, offset + line.len()),
-                        snippet: line.to_string(),
+                        location: location.clone(),
+                        span,
+                        snippet: capture.text.clone(),
+                        fix: None,
                     });
                     *counts.entry(Category::Structure).or_default() += 1;
                 }
@@ -2955,57 +3668,36 @@ impl Analyzer {
             if profile.forbid_rhetorical_headings
                 && content.ends_with('?')
                 && !filtered.is_category_disabled(offset, Category::Structure)
-                && !filtered.is_line_ignored(idx + 1)
+                && !filtered.is_line_ignored(capture.line)
             {
                 diagnostics.push(Diagnostic {
                     category: Category::Structure,
+                    rule: "structure/rhetorical-heading".into(),
                     severity: Severity::Hint,
                     message: format!("Rhetorical heading detected: `{}`", content),
                     suggestion: Some("Use a declarative heading.".into()),
-                    location: Location {
-                        line: idx + 1,
-                        column: 1,
-                    },
-                    span: (offset, offset + line.len()),
-                    snippet: line.to_string(),
+                    location: location.clone(),
+                    span,
+                    snippet: capture.text.clone(),
+                    fix: None,
                 });
                 *counts.entry(Category::Structure).or_default() += 1;
             }
 
             if content.chars().any(|ch| EMOJI_HINTS.contains(&ch))
                 && !filtered.is_category_disabled(offset, Category::Formatting)
-                && !filtered.is_line_ignored(idx + 1)
+                && !filtered.is_line_ignored(capture.line)
             {
                 diagnostics.push(Diagnostic {
                     category: Category::Formatting,
+                    rule: "formatting/heading-emoji".into(),
                     severity: Severity::Hint,
                     message: format!("Emoji found in heading: `{content}`"),
                     suggestion: Some("Remove emoji from headings.".into()),
-                    location: Location {
-                        line: idx + 1,
-                        column: 1,
-                    },
-                    span: (offset, offset + line.len()),
-                    snippet: line.to_string(),
-                });
-                *counts.entry(Category::Formatting).or_default() += 1;
-            }
-
-            if matches_bold_list(line)
-                && !filtered.is_category_disabled(offset, Category::Formatting)
-                && !filtered.is_line_ignored(idx + 1)
-            {
-                diagnostics.push(Diagnostic {
-                    category: Category::Formatting,
-                    severity: Severity::Hint,
-                    message: "Bold list heading detected".into(),
-                    suggestion: Some("Use plain bullet labels instead of bold sentences.".into()),
-                    location: Location {
-                        line: idx + 1,
-                        column: 1,
-                    },
-                    span: (offset, offset + line.len()),
-                    snippet: line.to_string(),
+                    location: location.clone(),
+                    span,
+                    snippet: capture.text.clone(),
+                    fix: None,
                 });
                 *counts.entry(Category::Formatting).or_default() += 1;
             }
@@ -3013,19 +3705,18 @@ impl Analyzer {
             if self.config.heading_style == HeadingStyle::SentenceCase
                 && appears_title_case(content)
                 && !filtered.is_category_disabled(offset, Category::Formatting)
-                && !filtered.is_line_ignored(idx + 1)
+                && !filtered.is_line_ignored(capture.line)
             {
                 diagnostics.push(Diagnostic {
                     category: Category::Formatting,
+                    rule: "formatting/heading-case".into(),
                     severity: Severity::Hint,
                     message: format!("Heading should be sentence case: `{content}`"),
                     suggestion: Some("Lowercase the remaining words.".into()),
-                    location: Location {
-                        line: idx + 1,
-                        column: 1,
-                    },
-                    span: (offset, offset + line.len()),
-                    snippet: line.to_string(),
+                    location,
+                    span,
+                    snippet: capture.text.clone(),
+                    fix: None,
                 });
                 *counts.entry(Category::Formatting).or_default() += 1;
             }
@@ -3041,8 +3732,11 @@ impl Analyzer {
                         line: 1,
                         column: 1,
                         offset: 0,
+                        end: 0,
                         len: 0,
+                        level: 0,
                         text: String::new(),
+                        content: String::new(),
                         lower: String::new(),
                     });
                 if !filtered.is_category_disabled(capture.offset, Category::Structure)
@@ -3050,6 +3744,7 @@ impl Analyzer {
                 {
                     diagnostics.push(Diagnostic {
                         category: Category::Structure,
+                        rule: "structure/max-headings".into(),
                         severity: Severity::Warning,
                         message: format!(
                             "Document has {} headings; limit is {}.",
@@ -3063,6 +3758,7 @@ impl Analyzer {
                         },
                         span: (capture.offset, capture.offset + capture.len),
                         snippet: capture.text,
+                        fix: None,
                     });
                     *counts.entry(Category::Structure).or_default() += 1;
                 }
@@ -3084,19 +3780,21 @@ impl Analyzer {
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Structure,
+                    rule: "structure/required-heading".into(),
                     severity: Severity::Warning,
                     message: format!("Required heading `{required}` is missing."),
                     suggestion: Some("Add the required section heading.".into()),
                     location,
                     span: (anchor, anchor),
                     snippet: String::new(),
+                    fix: None,
                 });
                 *counts.entry(Category::Structure).or_default() += 1;
             }
         }
 
         for regex in &profile.banned_heading_regexes {
-            for capture in &captures {
+            for capture in &all_captures {
                 if regex.is_match(&capture.text) {
                     if filtered.is_category_disabled(capture.offset, Category::Structure)
                         || filtered.is_line_ignored(capture.line)
@@ -3105,6 +3803,7 @@ impl Analyzer {
                     }
                     diagnostics.push(Diagnostic {
                         category: Category::Structure,
+                        rule: "structure/banned-heading".into(),
                         severity: Severity::Warning,
                         message: format!(
                             "Heading `{}` matches disallowed pattern `{}`.",
@@ -3118,6 +3817,7 @@ impl Analyzer {
                         },
                         span: (capture.offset, capture.offset + capture.len),
                         snippet: capture.text.clone(),
+                        fix: None,
                     });
                     *counts.entry(Category::Structure).or_default() += 1;
                 }
@@ -3137,25 +3837,32 @@ impl Analyzer {
         }
         let curly_chars = ['“', '”', '‘', '’'];
         for (idx, ch) in text.char_indices() {
+            if !curly_chars.contains(&ch) {
+                continue;
+            }
             if filtered.is_category_disabled(idx, Category::QuoteStyle) {
                 continue;
             }
-            if curly_chars.contains(&ch) {
-                let location = byte_to_location(text, idx);
-                if filtered.is_line_ignored(location.line) {
-                    continue;
-                }
-                diagnostics.push(Diagnostic {
-                    category: Category::QuoteStyle,
-                    severity: Severity::Hint,
-                    message: "Curly quotation detected; prefer straight quotes".into(),
-                    suggestion: Some("Replace with ' or \".".into()),
-                    location,
-                    span: (idx, idx + ch.len_utf8()),
-                    snippet: ch.to_string(),
-                });
-                *counts.entry(Category::QuoteStyle).or_default() += 1;
+            let location = byte_to_location(text, idx);
+            if filtered.is_line_ignored(location.line) {
+                continue;
             }
+            diagnostics.push(Diagnostic {
+                category: Category::QuoteStyle,
+                rule: "quote-style/curly-quotes".into(),
+                severity: Severity::Hint,
+                message: "Curly quotation detected; prefer straight quotes".into(),
+                suggestion: Some("Replace with ' or \".".into()),
+                location,
+                span: (idx, idx + ch.len_utf8()),
+                snippet: ch.to_string(),
+                fix: Some(Fix::replace(
+                    (idx, idx + ch.len_utf8()),
+                    straight_quote_for(ch),
+                    Applicability::Safe,
+                )),
+            });
+            *counts.entry(Category::QuoteStyle).or_default() += 1;
         }
     }
 
@@ -3163,169 +3870,268 @@ impl Analyzer {
     /// - Low sentence length variance (AI writes very uniform sentences)
     /// - Repeated sentence openings (AI recycles structures)
     /// - High passive voice density
-    fn detect_statistical_slop(
+    ///
+    /// Each titled section is checked on its own and flagged at its heading.
+    /// The whole document is checked as well, for patterns spread thinly
+    /// across sections, and flagged at its title unless a section already
+    /// reported the same rule.
+    fn statistical_diagnostics(
         &self,
-        text: &str,
-        sentences: &[(String, usize)],
+        words: &Words,
+        sentences: &[(String, usize, BlockKind)],
+        sections: &[SectionBounds],
         filtered: &DisabledRanges,
-        diagnostics: &mut Vec<Diagnostic>,
-        counts: &mut BTreeMap<Category, usize>,
-    ) {
-        // Need at least 5 sentences for meaningful statistics
-        if sentences.len() < 5 {
-            return;
+        profile: &ProfileRuntime,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let text = words.source();
+        let passive_re = &self.locale(words.language()).passive_voice;
+        let mut flagged: HashSet<&'static str> = HashSet::new();
+        for section in sections {
+            let Some((start, end)) = section.heading else {
+                continue;
+            };
+            let in_section = sentences_in(sentences, section.span);
+            let location = byte_to_location(text, start);
+            for signal in
+                statistical_signals(&in_section, filtered, passive_re, &profile.statistics)
+            {
+                if filtered.is_category_disabled(start, signal.category)
+                    || filtered.is_line_ignored(location.line)
+                {
+                    continue;
+                }
+                flagged.insert(signal.rule);
+                diagnostics.push(signal.into_diagnostic(
+                    location.clone(),
+                    (start, end),
+                    text[start..end].to_string(),
+                ));
+            }
         }
 
-        let Some(anchor) = analysis_anchor_offset(filtered, text) else {
-            return;
+        let Some((start, end)) = document_anchor(text, sections, filtered) else {
+            return diagnostics;
         };
-        let anchor_location = byte_to_location(text, anchor);
+        let location = byte_to_location(text, start);
+        let all = sentences_in(sentences, (0, text.len()));
+        for signal in statistical_signals(&all, filtered, passive_re, &profile.statistics) {
+            if flagged.contains(signal.rule)
+                || filtered.is_category_disabled(start, signal.category)
+                || filtered.is_line_ignored(location.line)
+            {
+                continue;
+            }
+            diagnostics.push(signal.into_diagnostic(
+                location.clone(),
+                (start, end),
+                text[start..end].to_string(),
+            ));
+        }
+        diagnostics
+    }
 
-        let tone_enabled = !filtered.is_category_disabled(anchor, Category::Tone)
-            && !filtered.is_line_ignored(anchor_location.line);
-        let cadence_enabled = !filtered.is_category_disabled(anchor, Category::Cadence)
-            && !filtered.is_line_ignored(anchor_location.line);
+    /// `statistics:` values for the default profile under which none of
+    /// `samples` trips a statistical check. Each limit starts from the
+    /// profile's resolved value and is loosened just enough, never
+    /// tightened. The sentence and count minimums only decide whether a
+    /// check runs, and `triad_min_headings` is not measured by the samples,
+    /// so those keep their resolved values.
+    pub fn suggest_statistics(&self, samples: &[StatisticsSample]) -> StatisticsRules {
+        let current = self
+            .profile_for_name(&self.default_profile)
+            .map_or_else(StatThresholds::default, |profile| profile.statistics);
+        let checked: Vec<&StatisticsSample> = samples
+            .iter()
+            .filter(|sample| sample.sentences >= current.min_sentences.max(1))
+            .collect();
+        let uniform_max_cv = checked
+            .iter()
+            .filter(|sample| sample.uniform_sentences >= current.uniform_min_sentences.max(2))
+            .map(|sample| sample.sentence_length_cv * 0.9)
+            .fold(current.uniform_max_cv, f32::min);
+        let passive_max_ratio = checked
+            .iter()
+            .filter(|sample| sample.sentences >= current.passive_min_sentences)
+            .map(|sample| (sample.passive_ratio + 0.05).min(1.0))
+            .fold(current.passive_max_ratio, f32::max);
+        let opening_max_ratio = checked
+            .iter()
+            .filter(|sample| sample.top_opening_count >= current.opening_min_count)
+            .map(|sample| (sample.top_opening_ratio + 0.05).min(1.0))
+            .fold(current.opening_max_ratio, f32::max);
+        StatisticsRules {
+            min_sentences: Some(current.min_sentences),
+            uniform_max_cv: Some((uniform_max_cv * 100.0).floor() / 100.0),
+            uniform_min_sentences: Some(current.uniform_min_sentences),
+            passive_max_ratio: Some((passive_max_ratio * 100.0).ceil() / 100.0),
+            passive_min_sentences: Some(current.passive_min_sentences),
+            opening_max_ratio: Some((opening_max_ratio * 100.0).ceil() / 100.0),
+            opening_min_count: Some(current.opening_min_count),
+            triad_min_headings: Some(current.triad_min_headings),
+            readability_min_sentences: Some(current.readability_min_sentences),
+        }
+    }
 
-        if tone_enabled {
-            // Calculate sentence length statistics
-            let lengths: Vec<usize> = sentences
-                .iter()
-                .filter(|(s, off)| {
-                    !filtered.is_category_disabled(*off, Category::Tone)
-                        && !s.trim().starts_with('#')
-                })
-                .map(|(s, _)| s.split_whitespace().count())
-                .filter(|&len| len >= 3) // Skip very short "sentences"
-                .collect();
+    /// The values behind the statistical checks, for the whole of `text`
+    /// and for each titled section, as the default profile sees them.
+    /// Used to derive thresholds from a reference corpus.
+    pub fn statistics_samples(&self, text: &str) -> Vec<StatisticsSample> {
+        let doc = MarkdownDoc::parse(text);
+        let filtered = DisabledRanges::new(text, &doc);
+        let language = doc
+            .frontmatter
+            .and_then(|(start, end)| locale::frontmatter_language(&text[start..end]))
+            .unwrap_or(self.config.language);
+        let locale = self.locale(language);
+        let sentences = split_sentences(text, &doc, locale);
+        let mut samples = vec![measure_statistics(
+            &sentences_in(&sentences, (0, text.len())),
+            &filtered,
+            &locale.passive_voice,
+        )];
+        for section in sections::segment(text, &doc) {
+            if section.heading.is_some() {
+                samples.push(measure_statistics(
+                    &sentences_in(&sentences, section.span),
+                    &filtered,
+                    &locale.passive_voice,
+                ));
+            }
+        }
+        samples
+    }
 
-            if lengths.len() >= 5 {
-                let avg_len = lengths.iter().sum::<usize>() as f32 / lengths.len() as f32;
-                let variance = lengths
-                    .iter()
-                    .map(|&len| {
-                        let diff = len as f32 - avg_len;
-                        diff * diff
-                    })
-                    .sum::<f32>()
-                    / lengths.len() as f32;
-                let std_dev = variance.sqrt();
-
-                // Coefficient of variation: std_dev / mean
-                // AI-generated text typically has CV < 0.25 (very uniform)
-                // Human text typically has CV > 0.35
-                let cv = if avg_len > 0.0 {
-                    std_dev / avg_len
-                } else {
-                    1.0
-                };
+    /// Readability of the prose sentences starting within `range`.
+    fn prose_metrics(
+        &self,
+        sentences: &[(String, usize, BlockKind)],
+        filtered: &DisabledRanges,
+        range: (usize, usize),
+        language: Language,
+    ) -> Metrics {
+        let prose = sentences
+            .iter()
+            .filter(|(_, off, kind)| {
+                (range.0..range.1).contains(off)
+                    && matches!(kind, BlockKind::Paragraph | BlockKind::ListItem)
+                    && !filtered.is_masked(*off)
+            })
+            .map(|(sentence, _, _)| sentence.as_str());
+        Metrics::compute(prose, &self.locale(language).passive_voice, language)
+    }
 
-                // Very low variance is a strong AI signal
-                if cv < 0.20 && lengths.len() >= 8 {
-                    diagnostics.push(Diagnostic {
-                        category: Category::Tone,
-                        severity: Severity::Warning,
-                        message: format!(
-                            "Suspiciously uniform sentence lengths (CV={:.2}). AI-generated text typically has low variance.",
-                            cv
+    /// Check the profile's reading targets for the whole document and, when
+    /// it has more than one heading, for each section.
+    fn readability_diagnostics(
+        &self,
+        text: &str,
+        sections: &[SectionBounds],
+        section_metrics: &[Metrics],
+        filtered: &DisabledRanges,
+        profile: &ProfileRuntime,
+        metrics: &Metrics,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if profile.max_grade_level.is_none() && profile.min_reading_ease.is_none() {
+            return diagnostics;
+        }
+        let findings = |metrics: &Metrics| {
+            let mut found = Vec::new();
+            if metrics.sentences < profile.statistics.readability_min_sentences {
+                return found;
+            }
+            if let Some(max) = profile.max_grade_level {
+                if metrics.flesch_kincaid_grade > max {
+                    found.push((
+                        "readability/grade-level",
+                        format!(
+                            "reads at grade level {:.1}; the target is {max} or lower.",
+                            metrics.flesch_kincaid_grade
                         ),
-                        suggestion: Some(
-                            "Vary your sentence lengths for more natural rhythm.".into(),
+                    ));
+                }
+            }
+            if let Some(min) = profile.min_reading_ease {
+                if metrics.flesch_reading_ease < min {
+                    found.push((
+                        "readability/reading-ease",
+                        format!(
+                            "has reading ease {:.1}; the target is {min} or higher.",
+                            metrics.flesch_reading_ease
                         ),
-                        location: anchor_location.clone(),
-                        span: (anchor, (anchor + 100).min(text.len())),
-                        snippet: "Document-level analysis".into(),
-                    });
-                    *counts.entry(Category::Tone).or_default() += 1;
+                    ));
                 }
             }
-
-            // Check for passive voice density
-            let passive_re = &*PASSIVE_VOICE_RE;
-            let passive_total = sentences
-                .iter()
-                .filter(|(_, off)| !filtered.is_category_disabled(*off, Category::Tone))
-                .count();
-            if passive_total > 0 {
-                let passive_count = sentences
-                    .iter()
-                    .filter(|(s, off)| {
-                        !filtered.is_category_disabled(*off, Category::Tone)
-                            && passive_re.is_match(s)
-                    })
-                    .count();
-                let passive_ratio = passive_count as f32 / passive_total as f32;
-
-                // >50% passive voice is a strong AI signal
-                if passive_ratio > 0.5 && passive_total >= 6 {
+            found
+        };
+        let suggestion = "Split long sentences and prefer shorter, common words.";
+
+        if let Some((start, end)) = document_anchor(text, sections, filtered) {
+            let location = byte_to_location(text, start);
+            let found = findings(metrics);
+            if !found.is_empty()
+                && !filtered.is_category_disabled(start, Category::Readability)
+                && !filtered.is_line_ignored(location.line)
+            {
+                for (rule, message) in found {
                     diagnostics.push(Diagnostic {
-                        category: Category::Tone,
-                        severity: Severity::Hint,
-                        message: format!(
-                            "High passive voice density ({:.0}% of sentences). Consider using active voice.",
-                            passive_ratio * 100.0
-                        ),
-                        suggestion: Some(
-                            "Rewrite passive constructions as active statements.".into(),
-                        ),
-                        location: anchor_location.clone(),
-                        span: (anchor, (anchor + 100).min(text.len())),
-                        snippet: "Document-level analysis".into(),
+                        category: Category::Readability,
+                        rule: rule.into(),
+                        severity: Severity::Warning,
+                        message: format!("Document {message}"),
+                        suggestion: Some(suggestion.into()),
+                        location: location.clone(),
+                        span: (start, end),
+                        snippet: text[start..end].to_string(),
+                        fix: None,
                     });
-                    *counts.entry(Category::Tone).or_default() += 1;
                 }
             }
         }
 
-        if cadence_enabled {
-            // Check for repeated sentence openings (first 2-3 words)
-            let mut opening_counts: HashMap<String, usize> = HashMap::new();
-            let mut opening_total = 0usize;
-            for (sentence, off) in sentences {
-                if filtered.is_category_disabled(*off, Category::Cadence) {
-                    continue;
-                }
-                let trimmed = sentence.trim();
-                if trimmed.starts_with('#') || trimmed.starts_with('-') || trimmed.starts_with('*')
-                {
-                    continue;
-                }
-                let words: Vec<&str> = trimmed.split_whitespace().take(3).collect();
-                if words.len() >= 2 {
-                    opening_total += 1;
-                    let opening = words[..2.min(words.len())].join(" ").to_lowercase();
-                    *opening_counts.entry(opening).or_default() += 1;
-                }
+        if sections
+            .iter()
+            .filter(|section| section.heading.is_some())
+            .count()
+            < 2
+        {
+            return diagnostics;
+        }
+        for (section, metrics) in sections.iter().zip(section_metrics) {
+            let Some((start, end)) = section.heading else {
+                continue;
+            };
+            let location = byte_to_location(text, start);
+            let found = findings(metrics);
+            if found.is_empty()
+                || filtered.is_category_disabled(start, Category::Readability)
+                || filtered.is_line_ignored(location.line)
+            {
+                continue;
             }
-
-            // If any opening appears in >40% of sentences, that's suspicious
-            if opening_total > 0 {
-                for (opening, count) in opening_counts {
-                    let ratio = count as f32 / opening_total as f32;
-                    if ratio > 0.4 && count >= 4 {
-                        diagnostics.push(Diagnostic {
-                            category: Category::Cadence,
-                            severity: Severity::Hint,
-                            message: format!(
-                                "Repetitive sentence opening `{}...` used in {:.0}% of sentences.",
-                                opening,
-                                ratio * 100.0
-                            ),
-                            suggestion: Some("Vary your sentence openings for better flow.".into()),
-                            location: anchor_location.clone(),
-                            span: (anchor, (anchor + 100).min(text.len())),
-                            snippet: "Document-level analysis".into(),
-                        });
-                        *counts.entry(Category::Cadence).or_default() += 1;
-                        break; // Only report once
-                    }
-                }
+            let title = section.heading_path.last().map_or("", String::as_str);
+            for (rule, message) in found {
+                diagnostics.push(Diagnostic {
+                    category: Category::Readability,
+                    rule: rule.into(),
+                    severity: Severity::Hint,
+                    message: format!("Section `{title}` {message}"),
+                    suggestion: Some(suggestion.into()),
+                    location: location.clone(),
+                    span: (start, end),
+                    snippet: text[start..end].to_string(),
+                    fix: None,
+                });
             }
         }
+        diagnostics
     }
 
     fn detect_min_code_blocks(
         &self,
         text: &str,
+        doc: &MarkdownDoc,
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
@@ -3337,12 +4143,7 @@ impl Analyzer {
         if min_blocks == 0 {
             return;
         }
-        let mut blocks = 0usize;
-        for line in text.lines() {
-            if line.trim_start().starts_with("```") {
-                blocks += 1;
-            }
-        }
+        let blocks = doc.code_blocks.len();
         if blocks < min_blocks {
             let Some(anchor) = analysis_anchor_offset(filtered, text) else {
                 return;
@@ -3356,15 +4157,17 @@ impl Analyzer {
             }
             diagnostics.push(Diagnostic {
                 category: Category::Structure,
+                rule: "structure/min-code-blocks".into(),
                 severity: Severity::Warning,
                 message: format!(
-                    "Document has {} code block fences; minimum is {}.",
+                    "Document has {} code blocks; minimum is {}.",
                     blocks, min_blocks
                 ),
                 suggestion: Some("Add runnable examples or configuration snippets.".into()),
                 location,
                 span: (anchor, anchor),
                 snippet: String::new(),
+                fix: None,
             });
             *counts.entry(Category::Structure).or_default() += 1;
         }
@@ -3373,6 +4176,7 @@ impl Analyzer {
     fn detect_triad_slop(
         &self,
         text: &str,
+        doc: &MarkdownDoc,
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
@@ -3382,37 +4186,19 @@ impl Analyzer {
             return;
         }
         let triad: [&str; 3] = ["future development", "summary", "conclusion"];
-        let mut present: Vec<HeadingCapture> = Vec::new();
-        for (idx, line) in text.lines().enumerate() {
-            if !line.trim_start().starts_with('#') {
-                continue;
-            }
-            let content = line.trim_start_matches('#').trim().to_lowercase();
-            if triad.iter().any(|t| content == *t) {
-                let offset = line_offset(text, idx);
-                if filtered.is_category_disabled(offset, Category::Structure) {
-                    continue;
-                }
-                if filtered.is_line_ignored(idx + 1) {
-                    continue;
-                }
-                present.push(HeadingCapture {
-                    line: idx + 1,
-                    column: 1,
-                    offset,
-                    len: line.len(),
-                    text: line.to_string(),
-                    lower: content,
-                });
-            }
-        }
-        if present.len() >= 2 {
+        let present: Vec<HeadingCapture> = heading_captures(text, doc)
+            .into_iter()
+            .filter(|cap| triad.contains(&cap.lower.as_str()))
+            .filter(|cap| {
+                !filtered.is_category_disabled(cap.offset, Category::Structure)
+                    && !filtered.is_line_ignored(cap.line)
+            })
+            .collect();
+        if present.len() >= profile.statistics.triad_min_headings.max(1) {
             let cap = &present[0];
-            if filtered.is_line_ignored(cap.line) {
-                return;
-            }
             diagnostics.push(Diagnostic {
                 category: Category::Structure,
+                rule: "structure/template-triad".into(),
                 severity: Severity::Warning,
                 message: "Slop template triad detected (summary/conclusion/future development)."
                     .into(),
@@ -3423,6 +4209,7 @@ impl Analyzer {
                 },
                 span: (cap.offset, cap.offset + cap.len),
                 snippet: cap.text.clone(),
+                fix: None,
             });
             *counts.entry(Category::Structure).or_default() += 1;
         }
@@ -3431,7 +4218,8 @@ impl Analyzer {
     fn detect_section_density(
         &self,
         text: &str,
-        sentences: &[(String, usize)],
+        doc: &MarkdownDoc,
+        sentences: &[(String, usize, BlockKind)],
         filtered: &DisabledRanges,
         profile: &ProfileRuntime,
         diagnostics: &mut Vec<Diagnostic>,
@@ -3443,57 +4231,16 @@ impl Analyzer {
         if min_sents == 0 {
             return;
         }
-        // collect headings
-        let mut heads: Vec<HeadingCapture> = Vec::new();
-        for (idx, line) in text.lines().enumerate() {
-            if !line.trim_start().starts_with('#') {
-                continue;
-            }
-            let offset = line_offset(text, idx);
-            if filtered.is_category_disabled(offset, Category::Structure) {
-                continue;
-            }
-            let content = line.trim_start_matches('#').trim();
-            if content.is_empty() {
-                continue;
-            }
-            heads.push(HeadingCapture {
-                line: idx + 1,
-                column: 1,
-                offset,
-                len: line.len(),
-                text: line.to_string(),
-                lower: content.to_lowercase(),
-            });
-        }
+        let heads: Vec<HeadingCapture> = heading_captures(text, doc)
+            .into_iter()
+            .filter(|cap| !filtered.is_category_disabled(cap.offset, Category::Structure))
+            .collect();
         if heads.is_empty() {
             return;
         }
-        // compute code fence ranges to ignore
-        let mut fences: Vec<(usize, usize)> = Vec::new();
-        let mut in_fence = false;
-        let mut fence_start = 0usize;
-        let mut cursor = 0usize;
-        for seg in text.split_inclusive('\n') {
-            let line = seg;
-            if line.trim_start().starts_with("```") {
-                if !in_fence {
-                    in_fence = true;
-                    fence_start = cursor;
-                } else {
-                    in_fence = false;
-                    fences.push((fence_start, cursor + line.len()));
-                }
-            }
-            cursor += line.len();
-        }
         // evaluate each section
         for (i, cap) in heads.iter().enumerate() {
-            let start = if let Some(rel) = text[cap.offset..].find('\n') {
-                (cap.offset + rel + 1).min(text.len())
-            } else {
-                text.len()
-            };
+            let start = cap.end;
             let end = if i + 1 < heads.len() {
                 heads[i + 1].offset
             } else {
@@ -3503,23 +4250,18 @@ impl Analyzer {
                 continue;
             }
             let mut count = 0usize;
-            for (sent, off) in sentences.iter() {
+            for (sent, off, kind) in sentences.iter() {
                 if *off < start || *off >= end {
                     continue;
                 }
-                if filtered.is_category_disabled(*off, Category::Structure) {
+                if filtered.is_category_masked(*off, Category::Structure) {
                     continue;
                 }
                 // ignore bullets and empty
-                let trimmed = sent.trim();
-                if trimmed.is_empty() {
-                    continue;
-                }
-                if trimmed.starts_with('-') || trimmed.starts_with('*') {
+                if sent.trim().is_empty() {
                     continue;
                 }
-                // ignore sentences starting inside code fences
-                if fences.iter().any(|(a, b)| *off >= *a && *off < *b) {
+                if *kind == BlockKind::ListItem {
                     continue;
                 }
                 count += 1;
@@ -3530,6 +4272,7 @@ impl Analyzer {
                 }
                 diagnostics.push(Diagnostic {
                     category: Category::Structure,
+                    rule: "structure/thin-section".into(),
                     severity: Severity::Hint,
                     message: format!(
                         "Section `{}` is thin: {} sentences; minimum {}.",
@@ -3542,8 +4285,9 @@ impl Analyzer {
                         line: cap.line,
                         column: cap.column,
                     },
-                    span: (cap.offset, end),
+                    span: (cap.offset, cap.offset + cap.len),
                     snippet: cap.text.clone(),
+                    fix: None,
                 });
                 *counts.entry(Category::Structure).or_default() += 1;
             }
@@ -3551,174 +4295,298 @@ impl Analyzer {
     }
 }
 
-/// Precomputed disabled regions guarded by `<!-- dwg:off -->` ... `<!-- dwg:on -->` markers.
+/// A statistical finding, anchored by the caller to a heading or to the
+/// start of the document.
+struct StatSignal {
+    category: Category,
+    rule: &'static str,
+    severity: Severity,
+    message: String,
+    suggestion: &'static str,
+}
+
+impl StatSignal {
+    fn into_diagnostic(
+        self,
+        location: Location,
+        span: (usize, usize),
+        snippet: String,
+    ) -> Diagnostic {
+        Diagnostic {
+            category: self.category,
+            rule: self.rule.into(),
+            severity: self.severity,
+            message: self.message,
+            suggestion: Some(self.suggestion.into()),
+            location,
+            span,
+            snippet,
+            fix: None,
+        }
+    }
+}
+
+/// Raw values behind the statistical checks for one stretch of text.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StatisticsSample {
+    pub sentences: usize,
+    /// Sentences of three or more words, which the uniformity check uses.
+    pub uniform_sentences: usize,
+    /// Coefficient of variation of their lengths.
+    pub sentence_length_cv: f32,
+    pub passive_ratio: f32,
+    /// Paragraph sentences with at least two words.
+    pub opening_sentences: usize,
+    /// The most common two-word opening, with its count and share.
+    pub top_opening: Option<String>,
+    pub top_opening_count: usize,
+    pub top_opening_ratio: f32,
+}
+
+fn sentences_in(
+    sentences: &[(String, usize, BlockKind)],
+    range: (usize, usize),
+) -> Vec<&(String, usize, BlockKind)> {
+    sentences
+        .iter()
+        .filter(|(_, off, _)| (range.0..range.1).contains(off))
+        .collect()
+}
+
+/// Measure `sentences`, skipping sentences where the relevant category is
+/// disabled.
+fn measure_statistics(
+    sentences: &[&(String, usize, BlockKind)],
+    filtered: &DisabledRanges,
+    passive_re: &Regex,
+) -> StatisticsSample {
+    let mut sample = StatisticsSample {
+        sentences: sentences.len(),
+        ..Default::default()
+    };
+
+    // Calculate sentence length statistics
+    let lengths: Vec<usize> = sentences
+        .iter()
+        .filter(|(_, off, kind)| {
+            !filtered.is_category_masked(*off, Category::Tone) && *kind != BlockKind::Heading
+        })
+        .map(|(s, _, _)| s.split_whitespace().count())
+        .filter(|&len| len >= 3) // Skip very short "sentences"
+        .collect();
+    sample.uniform_sentences = lengths.len();
+    if !lengths.is_empty() {
+        let avg_len = lengths.iter().sum::<usize>() as f32 / lengths.len() as f32;
+        let variance = lengths
+            .iter()
+            .map(|&len| {
+                let diff = len as f32 - avg_len;
+                diff * diff
+            })
+            .sum::<f32>()
+            / lengths.len() as f32;
+        // Coefficient of variation: std_dev / mean
+        // AI-generated text typically has CV < 0.25 (very uniform)
+        // Human text typically has CV > 0.35
+        sample.sentence_length_cv = variance.sqrt() / avg_len;
+    }
+
+    // Passive voice share
+    let passive_total = sentences
+        .iter()
+        .filter(|(_, off, _)| !filtered.is_category_masked(*off, Category::Tone))
+        .count();
+    if passive_total > 0 {
+        let passive_count = sentences
+            .iter()
+            .filter(|(s, off, _)| {
+                !filtered.is_category_masked(*off, Category::Tone) && passive_re.is_match(s)
+            })
+            .count();
+        sample.passive_ratio = passive_count as f32 / passive_total as f32;
+    }
+
+    // Repeated sentence openings (first two words)
+    let mut opening_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for (sentence, off, kind) in sentences.iter().copied() {
+        if filtered.is_category_masked(*off, Category::Cadence) {
+            continue;
+        }
+        if *kind != BlockKind::Paragraph {
+            continue;
+        }
+        let words: Vec<&str> = sentence.split_whitespace().take(2).collect();
+        if words.len() == 2 {
+            sample.opening_sentences += 1;
+            *opening_counts
+                .entry(words.join(" ").to_lowercase())
+                .or_default() += 1;
+        }
+    }
+    for (opening, count) in opening_counts {
+        if count > sample.top_opening_count {
+            sample.top_opening_count = count;
+            sample.top_opening = Some(opening);
+        }
+    }
+    if sample.opening_sentences > 0 {
+        sample.top_opening_ratio =
+            sample.top_opening_count as f32 / sample.opening_sentences as f32;
+    }
+    sample
+}
+
+/// Statistical signals over `sentences` that cross `thresholds`.
+fn statistical_signals(
+    sentences: &[&(String, usize, BlockKind)],
+    filtered: &DisabledRanges,
+    passive_re: &Regex,
+    thresholds: &StatThresholds,
+) -> Vec<StatSignal> {
+    let mut signals = Vec::new();
+    if sentences.len() < thresholds.min_sentences.max(1) {
+        return signals;
+    }
+    let sample = measure_statistics(sentences, filtered, passive_re);
+
+    // Very low variance is a strong AI signal
+    if sample.uniform_sentences >= thresholds.uniform_min_sentences.max(2)
+        && sample.sentence_length_cv < thresholds.uniform_max_cv
+    {
+        signals.push(StatSignal {
+            category: Category::Tone,
+            rule: "tone/uniform-sentence-length",
+            severity: Severity::Warning,
+            message: format!(
+                "Suspiciously uniform sentence lengths (CV={:.2}). AI-generated text typically has low variance.",
+                sample.sentence_length_cv
+            ),
+            suggestion: "Vary your sentence lengths for more natural rhythm.",
+        });
+    }
+
+    // Mostly passive voice is a strong AI signal
+    if sample.sentences >= thresholds.passive_min_sentences
+        && sample.passive_ratio > thresholds.passive_max_ratio
+    {
+        signals.push(StatSignal {
+            category: Category::Tone,
+            rule: "tone/passive-density",
+            severity: Severity::Hint,
+            message: format!(
+                "High passive voice density ({:.0}% of sentences). Consider using active voice.",
+                sample.passive_ratio * 100.0
+            ),
+            suggestion: "Rewrite passive constructions as active statements.",
+        });
+    }
+
+    // One opening used for a large share of sentences is suspicious
+    if let Some(opening) = &sample.top_opening {
+        if sample.top_opening_count >= thresholds.opening_min_count
+            && sample.top_opening_ratio > thresholds.opening_max_ratio
+        {
+            signals.push(StatSignal {
+                category: Category::Cadence,
+                rule: "cadence/repetitive-openings",
+                severity: Severity::Hint,
+                message: format!(
+                    "Repetitive sentence opening `{}...` used in {:.0}% of sentences.",
+                    opening,
+                    sample.top_opening_ratio * 100.0
+                ),
+                suggestion: "Vary your sentence openings for better flow.",
+            });
+        }
+    }
+    signals
+}
+
+/// A range a directive disables, with the directive's index.
+type DirectiveRange = (usize, (usize, usize));
+
 /// Precomputed disabled regions for the analyzer.
 /// Supports:
-/// - `<!-- dwg:off -->` ... `<!-- dwg:on -->` - disable all checks
-/// - `<!-- dwg:ignore category -->` ... `<!-- dwg:end-ignore -->` - disable specific category
-/// - `<!-- dwg:ignore-line -->` - disable the current line
-/// - Code fences, inline code, URLs, frontmatter
+/// - The directives in [`directives`]: `dwg:off`, `dwg:ignore` and
+///   `dwg:ignore-line` comments
+/// - Code blocks, inline code, HTML blocks, link destinations, reference
+///   definitions and frontmatter, as found by the Markdown parser
+/// - Raw URLs
+///
+/// The `is_*` checks record which directives they matched, so after the
+/// detectors run [`DisabledRanges::used`] tells which directives hid
+/// something. Checks that only mask text, such as metrics, use the
+/// `*_masked` variants, which record nothing.
 struct DisabledRanges {
-    /// Ranges where all checks are disabled
+    /// Ranges that are not prose, where all checks are disabled
     global_ranges: Vec<(usize, usize)>,
+    /// Directive ranges where all checks are disabled, with the directive index
+    directive_ranges: Vec<DirectiveRange>,
     /// Ranges where specific categories are disabled
-    category_ranges: HashMap<Category, Vec<(usize, usize)>>,
+    category_ranges: HashMap<Category, Vec<DirectiveRange>>,
+    /// Ranges where specific rule IDs are disabled
+    rule_ranges: HashMap<String, Vec<DirectiveRange>>,
     /// Line numbers where all checks are disabled via ignore-line
-    ignored_lines: HashSet<usize>,
+    ignored_lines: HashMap<usize, Vec<usize>>,
+    /// Per directive, whether a check has matched it
+    used: Vec<Cell<bool>>,
 }
 
-/// Regex to match inline ignore comments: <!-- dwg:ignore category -->
-static INLINE_IGNORE_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"<!--\s*dwg:ignore\s+([a-z-,\s]+)\s*-->").expect("valid inline ignore regex")
-});
-
-/// Regex to match end-ignore comments: <!-- dwg:end-ignore -->
-static END_IGNORE_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"<!--\s*dwg:end-ignore\s*-->").expect("valid end ignore regex"));
-
-/// Regex to match ignore-line comments: <!-- dwg:ignore-line -->
-static IGNORE_LINE_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"<!--\s*dwg:ignore-line\s*-->").expect("valid ignore line regex"));
-
 impl DisabledRanges {
-    fn new(text: &str) -> Self {
-        let mut global_ranges = Vec::new();
-        let mut category_ranges: HashMap<Category, Vec<(usize, usize)>> = HashMap::new();
-        let mut ignored_lines: HashSet<usize> = HashSet::new();
-
-        // Explicit dwg:off ranges (global disable).
-        let mut cursor = 0;
-        let bytes = text.as_bytes();
-        while let Some(start_idx) = find_subsequence(bytes, b"<!-- dwg:off -->", cursor) {
-            let search_from = start_idx + "<!-- dwg:off -->".len();
-            if let Some(end_idx) = find_subsequence(bytes, b"<!-- dwg:on -->", search_from) {
-                global_ranges.push((start_idx, end_idx + "<!-- dwg:on -->".len()));
-                cursor = end_idx + "<!-- dwg:on -->".len();
-            } else {
-                global_ranges.push((start_idx, text.len()));
-                break;
-            }
-        }
+    fn new(text: &str, doc: &MarkdownDoc) -> Self {
+        Self::with_directives(text, doc, &directives::parse(text, doc))
+    }
 
-        // Category-specific ignores: <!-- dwg:ignore category --> ... <!-- dwg:end-ignore -->
-        for cap in INLINE_IGNORE_RE.captures_iter(text) {
-            let full_match = cap.get(0).unwrap();
-            let categories = cap.get(1).unwrap().as_str();
-            let start = full_match.start();
-
-            // Find the end-ignore marker
-            let search_from = full_match.end();
-            if let Some(end_match) = END_IGNORE_RE.find(&text[search_from..]) {
-                let end = search_from + end_match.end();
-                let mut found_any = false;
-                for raw in categories.split(',') {
-                    let name = raw.trim();
-                    if name.is_empty() {
-                        continue;
-                    }
-                    if let Some(cat) = parse_category(name) {
-                        category_ranges.entry(cat).or_default().push((start, end));
-                        found_any = true;
-                    }
-                }
-                if !found_any {
-                    global_ranges.push((start, end));
-                }
-            } else {
-                // If no end marker, apply to rest of document
-                let mut found_any = false;
-                for raw in categories.split(',') {
-                    let name = raw.trim();
-                    if name.is_empty() {
-                        continue;
-                    }
-                    if let Some(cat) = parse_category(name) {
-                        category_ranges
-                            .entry(cat)
-                            .or_default()
-                            .push((start, text.len()));
-                        found_any = true;
+    /// Disabled regions from the given directives only; with none, just the
+    /// regions that are not prose.
+    fn with_directives(text: &str, doc: &MarkdownDoc, directives: &[Directive]) -> Self {
+        let mut global_ranges = Vec::new();
+        let mut directive_ranges = Vec::new();
+        let mut category_ranges: HashMap<Category, Vec<DirectiveRange>> = HashMap::new();
+        let mut rule_ranges: HashMap<String, Vec<DirectiveRange>> = HashMap::new();
+        let mut ignored_lines: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for (index, directive) in directives.iter().enumerate() {
+            // The comments themselves are never prose.
+            global_ranges.push(directive.span);
+            global_ranges.extend(directive.close);
+            match &directive.scope {
+                Scope::All => directive_ranges.push((index, directive.range)),
+                Scope::Selectors(selectors) => {
+                    for selector in selectors {
+                        match selector {
+                            RuleSelector::Category(cat) => {
+                                category_ranges
+                                    .entry(*cat)
+                                    .or_default()
+                                    .push((index, directive.range));
+                            }
+                            RuleSelector::Rule(id) => {
+                                rule_ranges
+                                    .entry(id.clone())
+                                    .or_default()
+                                    .push((index, directive.range));
+                            }
+                        }
                     }
                 }
-                if !found_any {
-                    global_ranges.push((start, text.len()));
-                }
-            }
-        }
-
-        // Ignore-line markers: <!-- dwg:ignore-line -->
-        let mut line_num = 1usize;
-        for line in text.lines() {
-            if IGNORE_LINE_RE.is_match(line) {
-                ignored_lines.insert(line_num);
-                if line.trim() == "<!-- dwg:ignore-line -->" {
-                    ignored_lines.insert(line_num + 1);
-                }
-            }
-            line_num += 1;
-        }
-
-        // YAML frontmatter at the top of the file.
-        if let Some(first_line) = text.lines().next() {
-            if first_line.trim() == "---" {
-                let mut end = 0usize;
-                for line in text.split_inclusive('\n') {
-                    end += line.len();
-                    let trimmed = line.trim();
-                    if trimmed == "---" || trimmed == "..." {
-                        global_ranges.push((0, end));
-                        break;
+                Scope::Lines(lines) => {
+                    for line in lines {
+                        ignored_lines.entry(*line).or_default().push(index);
                     }
                 }
             }
         }
 
-        // Fenced code blocks (``` or ~~~).
-        let mut in_fence = false;
-        let mut fence_start = 0usize;
-        let mut offset = 0usize;
-        for line in text.split_inclusive('\n') {
-            let trimmed = line.trim_start();
-            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
-                if !in_fence {
-                    in_fence = true;
-                    fence_start = offset;
-                } else {
-                    in_fence = false;
-                    global_ranges.push((fence_start, offset + line.len()));
-                }
-            }
-            offset += line.len();
-        }
-        if in_fence {
-            global_ranges.push((fence_start, text.len()));
-        }
-
-        // Inline code spans on a single line.
-        let bytes = text.as_bytes();
-        let mut i = 0usize;
-        while i < bytes.len() {
-            if bytes[i] == b'`' && !is_in_ranges(&global_ranges, i) {
-                if bytes.get(i + 1) == Some(&b'`') && bytes.get(i + 2) == Some(&b'`') {
-                    i += 3;
-                    continue;
-                }
-                let mut j = i + 1;
-                while j < bytes.len() && bytes[j] != b'\n' {
-                    if bytes[j] == b'`' {
-                        global_ranges.push((i, j + 1));
-                        i = j + 1;
-                        break;
-                    }
-                    j += 1;
-                }
-                if j >= bytes.len() || bytes[j] == b'\n' {
-                    i += 1;
-                }
-            } else {
-                i += 1;
-            }
-        }
+        // Non-prose regions from the parsed document: frontmatter, fenced and
+        // indented code, inline code, HTML blocks, link destinations and
+        // reference definitions.
+        global_ranges.extend(doc.frontmatter);
+        global_ranges.extend(doc.code_blocks.iter().copied());
+        global_ranges.extend(doc.inline_code.iter().copied());
+        global_ranges.extend(doc.html_blocks.iter().copied());
+        global_ranges.extend(doc.link_destinations.iter().copied());
+        global_ranges.extend(doc.reference_definitions.iter().copied());
 
         // Raw URLs.
         for mat in URL_RE.find_iter(text) {
@@ -3730,39 +4598,120 @@ impl DisabledRanges {
 
         Self {
             global_ranges,
+            directive_ranges,
             category_ranges,
+            rule_ranges,
             ignored_lines,
+            used: directives.iter().map(|_| Cell::new(false)).collect(),
+        }
+    }
+
+    /// Whether any of `ranges` contains `byte_offset`; marks every
+    /// directive that does.
+    fn mark(&self, ranges: &[DirectiveRange], byte_offset: usize) -> bool {
+        let mut hit = false;
+        for (index, (start, end)) in ranges {
+            if byte_offset >= *start && byte_offset < *end {
+                self.used[*index].set(true);
+                hit = true;
+            }
         }
+        hit
     }
 
     /// Check if a byte offset is disabled globally (all checks).
     fn is_disabled(&self, byte_offset: usize) -> bool {
-        self.global_ranges
-            .iter()
-            .any(|(start, end)| byte_offset >= *start && byte_offset < *end)
+        is_in_ranges(&self.global_ranges, byte_offset)
+            || self.mark(&self.directive_ranges, byte_offset)
+    }
+
+    /// [`DisabledRanges::is_disabled`] without marking directives used.
+    fn is_masked(&self, byte_offset: usize) -> bool {
+        is_in_ranges(&self.global_ranges, byte_offset)
+            || self
+                .directive_ranges
+                .iter()
+                .any(|(_, (start, end))| byte_offset >= *start && byte_offset < *end)
     }
 
     /// Check if a specific category is disabled at the given byte offset.
+    /// Nested directives that both apply are all marked.
     fn is_category_disabled(&self, byte_offset: usize, category: Category) -> bool {
-        // First check global disables
-        if self.is_disabled(byte_offset) {
-            return true;
-        }
-        // Then check category-specific disables
+        let global = self.is_disabled(byte_offset);
+        let category = self
+            .category_ranges
+            .get(&category)
+            .is_some_and(|ranges| self.mark(ranges, byte_offset));
+        global || category
+    }
+
+    /// Marks the directives that disable `category` at `byte_offset` as
+    /// used, for a finding that they suppressed without it being checked
+    /// directly.
+    fn mark_used(&self, byte_offset: usize, category: Category) {
+        self.mark(&self.directive_ranges, byte_offset);
         if let Some(ranges) = self.category_ranges.get(&category) {
-            if ranges
-                .iter()
-                .any(|(start, end)| byte_offset >= *start && byte_offset < *end)
-            {
-                return true;
+            self.mark(ranges, byte_offset);
+        }
+    }
+
+    /// [`DisabledRanges::is_category_disabled`] without marking directives
+    /// used.
+    fn is_category_masked(&self, byte_offset: usize, category: Category) -> bool {
+        self.is_masked(byte_offset)
+            || self.category_ranges.get(&category).is_some_and(|ranges| {
+                ranges
+                    .iter()
+                    .any(|(_, (start, end))| byte_offset >= *start && byte_offset < *end)
+            })
+    }
+
+    /// How many of `offsets` are left checked for `category`. `is_hit` is
+    /// the check's limit test; the directives hiding the other offsets are
+    /// marked used only when hiding them is what keeps the count in bounds.
+    fn count_checked(
+        &self,
+        offsets: &[usize],
+        category: Category,
+        is_hit: impl Fn(usize) -> bool,
+    ) -> usize {
+        let (hidden, checked): (Vec<usize>, Vec<usize>) = offsets
+            .iter()
+            .partition(|offset| self.is_category_masked(**offset, category));
+        if !is_hit(checked.len()) && is_hit(offsets.len()) {
+            for offset in hidden {
+                self.mark_used(offset, category);
             }
         }
-        false
+        checked.len()
+    }
+
+    /// Check if a specific rule ID is disabled at the given byte offset.
+    fn is_rule_disabled(&self, byte_offset: usize, rule: &str) -> bool {
+        self.rule_ranges
+            .get(rule)
+            .is_some_and(|ranges| self.mark(ranges, byte_offset))
     }
 
     /// Check if a specific line number is ignored via <!-- dwg:ignore-line -->.
     fn is_line_ignored(&self, line_num: usize) -> bool {
-        self.ignored_lines.contains(&line_num)
+        let Some(indices) = self.ignored_lines.get(&line_num) else {
+            return false;
+        };
+        for index in indices {
+            self.used[*index].set(true);
+        }
+        true
+    }
+
+    /// Opening spans of the directives that some check matched.
+    fn used(&self, directives: &[Directive]) -> Vec<(usize, usize)> {
+        directives
+            .iter()
+            .zip(&self.used)
+            .filter(|(_, used)| used.get())
+            .map(|(directive, _)| directive.span)
+            .collect()
     }
 }
 
@@ -3771,13 +4720,67 @@ fn analysis_anchor_offset(filtered: &DisabledRanges, text: &str) -> Option<usize
         return Some(0);
     }
     for (idx, _) in text.char_indices() {
-        if !filtered.is_disabled(idx) {
+        if !filtered.is_masked(idx) {
             return Some(idx);
         }
     }
     None
 }
 
+/// Where diagnostics about the whole document attach: its title (the first
+/// level-1 heading, else the first heading), or the first checked line when
+/// it has no headings.
+fn document_anchor(
+    text: &str,
+    sections: &[SectionBounds],
+    filtered: &DisabledRanges,
+) -> Option<(usize, usize)> {
+    let headings: Vec<(usize, (usize, usize))> = sections
+        .iter()
+        .filter_map(|section| Some((section.level, section.heading?)))
+        .filter(|(_, (start, _))| !filtered.is_masked(*start))
+        .collect();
+    let title = headings
+        .iter()
+        .find(|(level, _)| *level == 1)
+        .or_else(|| headings.first());
+    if let Some((_, span)) = title {
+        return Some(*span);
+    }
+    let start = analysis_anchor_offset(filtered, text)?;
+    let end = text[start..]
+        .find('\n')
+        .map_or(text.len(), |len| start + len);
+    Some((start, end))
+}
+
+/// Optimal string alignment distance: insertions, deletions, substitutions
+/// and swaps of neighbouring characters each cost one.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
 fn find_subsequence(buf: &[u8], needle: &[u8], start: usize) -> Option<usize> {
     if needle.is_empty() {
         return Some(start);
@@ -3799,72 +4802,10 @@ const EMOJI_HINTS: [char; 28] = [
     '✅', '❗', '⚡', '📈', '🎯', '📌', '👉', '⚠', '💥', '⭐', '🎉', '🧠',
 ];
 
-const ALLOWED_SUFFIXES: [&str; 6] = ["s", "es", "ed", "ing", "ly", "d"];
-
 fn is_word_char(ch: char) -> bool {
     ch.is_alphanumeric() || matches!(ch, '_' | '-' | '\'')
 }
 
-fn has_word_boundary(text: &str, start: usize, end: usize) -> bool {
-    let prev = text[..start].chars().rev().next();
-    if let Some(ch) = prev {
-        if is_word_char(ch) {
-            return false;
-        }
-    }
-
-    let rest = &text[end..];
-    let next = rest.chars().next();
-    let Some(next_ch) = next else {
-        return true;
-    };
-    if !is_word_char(next_ch) {
-        return true;
-    }
-    if !next_ch.is_ascii_alphabetic() {
-        return false;
-    }
-
-    for suffix in ALLOWED_SUFFIXES {
-        if let Some(prefix) = rest.get(..suffix.len()) {
-            if prefix.eq_ignore_ascii_case(suffix) {
-                let after = rest[suffix.len()..].chars().next();
-                if after.map_or(true, |ch| !is_word_char(ch)) {
-                    return true;
-                }
-            }
-        }
-    }
-
-    false
-}
-
-fn find_term_with_boundary(haystack: &str, needle: &str) -> Option<usize> {
-    let needle = needle.trim();
-    if needle.is_empty() {
-        return None;
-    }
-    let mut start = 0usize;
-    while start < haystack.len() {
-        let slice = &haystack[start..];
-        let Some(rel) = slice.find(needle) else {
-            return None;
-        };
-        let abs = start + rel;
-        let end = abs + needle.len();
-        if end <= haystack.len() && has_word_boundary(haystack, abs, end) {
-            return Some(abs);
-        }
-        start = abs + 1;
-    }
-    None
-}
-
-fn matches_bold_list(line: &str) -> bool {
-    let trimmed = line.trim_start();
-    trimmed.starts_with("- **") || trimmed.starts_with("* **")
-}
-
 fn appears_title_case(content: &str) -> bool {
     let words: Vec<&str> = content
         .split_whitespace()
@@ -3889,127 +4830,81 @@ fn slice_snippet(text: &str, start: usize, end: usize) -> String {
     text.get(start..end).unwrap_or("").trim().to_string()
 }
 
-fn line_offset(text: &str, line_idx: usize) -> usize {
-    let mut offset = 0;
-    for (idx, line) in text.lines().enumerate() {
-        if idx == line_idx {
-            break;
-        }
-        offset += line.len() + 1; // include newline
-    }
-    offset
+/// Headings with their source line and position, in document order.
+fn heading_captures(text: &str, doc: &MarkdownDoc) -> Vec<HeadingCapture> {
+    doc.headings
+        .iter()
+        .filter(|heading| !heading.text.is_empty())
+        .map(|heading| {
+            let (offset, end) = heading.span;
+            let line = text[offset..end].lines().next().unwrap_or("");
+            let location = byte_to_location(text, offset);
+            HeadingCapture {
+                line: location.line,
+                column: location.column,
+                offset,
+                end,
+                len: line.len(),
+                level: heading.level,
+                text: line.to_string(),
+                content: heading.text.clone(),
+                lower: heading.text.to_lowercase(),
+            }
+        })
+        .collect()
 }
 
-fn split_paragraphs_with_offset(text: &str) -> Vec<(&str, usize)> {
-    if text.is_empty() {
-        return vec![(text, 0)];
-    }
-    let mut result = Vec::new();
-    let mut last = 0;
-    for (idx, _) in text.match_indices("\n\n") {
-        let para = &text[last..idx];
-        result.push((para, last));
-        last = idx + 2; // skip separator
-    }
-    if last <= text.len() {
-        let para = &text[last..];
-        result.push((para, last));
-    }
-    if result.is_empty() {
-        result.push((text, 0));
-    }
-    result
+/// Prose blocks (paragraphs, headings, list items, table cells) with their offsets.
+fn prose_blocks<'a>(text: &'a str, doc: &'a MarkdownDoc) -> impl Iterator<Item = (&'a str, usize)> {
+    doc.blocks
+        .iter()
+        .map(move |block| (&text[block.span.0..block.span.1], block.span.0))
 }
 
-fn split_sentences_with_offset(text: &str) -> Vec<(String, usize)> {
+/// Split each prose block into sentences at `.`, `!` or `?` followed by
+/// whitespace, except after the locale's abbreviations and ordinals. Offsets
+/// point at the first non-whitespace byte of a sentence.
+fn split_sentences(
+    text: &str,
+    doc: &MarkdownDoc,
+    locale: &LocaleRuntime,
+) -> Vec<(String, usize, BlockKind)> {
     let mut sentences = Vec::new();
-    let mut sentence = String::new();
-    let mut start_byte = 0;
-    let mut active = false;
-    let mut chars = text.char_indices().peekable();
-
-    while let Some((idx, ch)) = chars.next() {
-        if !active {
-            start_byte = idx;
-            active = true;
-        }
-        sentence.push(ch);
-
-        let mut should_flush = false;
-
-        if matches!(ch, '.' | '!' | '?') {
-            should_flush = match chars.peek() {
-                Some((_, next_ch)) => next_ch.is_whitespace(),
-                None => true,
-            };
-        }
-
-        if ch == '\n' {
-            let trimmed_current = sentence.trim_start();
-            if trimmed_current.starts_with('#')
-                || trimmed_current.starts_with("- ")
-                || trimmed_current.starts_with("* ")
-                || is_numbered_list(trimmed_current)
-            {
-                should_flush = true;
-            } else {
-                let mut temp = chars.clone();
-                let mut saw_blank_line = false;
-                let mut next_non_ws = None;
-                while let Some(&(next_idx, next_ch)) = temp.peek() {
-                    if next_ch == '\r' {
-                        temp.next();
-                        continue;
-                    }
-                    if next_ch == '\n' {
-                        saw_blank_line = true;
-                        temp.next();
-                        continue;
-                    }
-                    if next_ch.is_whitespace() {
-                        temp.next();
-                        continue;
-                    }
-                    next_non_ws = Some((next_idx, next_ch));
-                    break;
-                }
-                if saw_blank_line {
-                    should_flush = true;
-                } else if let Some((next_idx, _)) = next_non_ws {
-                    let rest = &text[next_idx..];
-                    let rest_line = rest.lines().next().unwrap_or("").trim_start();
-                    if rest_line.starts_with('#')
-                        || rest_line.starts_with("- ")
-                        || rest_line.starts_with("* ")
-                        || is_numbered_list(rest_line)
-                    {
-                        should_flush = true;
-                    }
-                } else {
-                    should_flush = true;
-                }
-            }
-        }
-
-        if should_flush {
-            let trimmed = sentence.trim();
+    for block in &doc.blocks {
+        let (base, body) = (block.span.0, &text[block.span.0..block.span.1]);
+        let mut push = |start: usize, end: usize| {
+            let raw = &body[start..end];
+            let lead = raw.len() - raw.trim_start().len();
+            let trimmed = raw.trim();
             if !trimmed.is_empty() {
-                sentences.push((trimmed.to_string(), start_byte));
+                sentences.push((trimmed.to_string(), base + start + lead, block.kind));
+            }
+        };
+        let mut start = 0usize;
+        let mut chars = body.char_indices().peekable();
+        while let Some((idx, ch)) = chars.next() {
+            if !matches!(ch, '.' | '!' | '?') {
+                continue;
+            }
+            if ch == '.' && locale.is_abbreviation_dot(&body[..idx]) {
+                continue;
+            }
+            if chars.peek().is_none_or(|(_, next)| next.is_whitespace()) {
+                let end = idx + ch.len_utf8();
+                push(start, end);
+                start = end;
             }
-            sentence.clear();
-            active = false;
         }
+        push(start, body.len());
     }
-
-    if !sentence.trim().is_empty() {
-        sentences.push((sentence.trim().to_string(), start_byte));
-    }
-
     sentences
 }
 
-fn sentence_index_for_offset(sentences: &[(String, usize)], offset: usize) -> Option<usize> {
-    for (idx, (sentence, start)) in sentences.iter().enumerate() {
+fn sentence_index_for_offset(
+    sentences: &[(String, usize, BlockKind)],
+    offset: usize,
+) -> Option<usize> {
+    for (idx, (sentence, start, _)) in sentences.iter().enumerate() {
         if offset >= *start && offset < *start + sentence.len() {
             return Some(idx);
         }
@@ -4034,99 +4929,15 @@ fn normalize_sentence(sentence: &str) -> String {
     normalised.trim().to_string()
 }
 
-fn is_numbered_list(line: &str) -> bool {
-    let mut chars = line.chars().peekable();
-    let mut saw_digit = false;
-    while let Some(ch) = chars.next() {
-        if ch.is_ascii_digit() {
-            saw_digit = true;
-            continue;
-        }
-        if ch == '.' || ch == ')' {
-            return saw_digit;
-        }
-        if ch.is_whitespace() {
-            if saw_digit {
-                continue;
-            } else {
-                return false;
-            }
-        }
-        return false;
-    }
-    false
-}
-
 static CONFIDENCE_PERCENT_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)\b\d{2,}%").expect("valid percent regex"));
 
 static MID_SENTENCE_QUESTION_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\?\s+[a-z]").expect("valid question regex"));
 
-/// Detect passive voice constructions.
-/// Matches patterns like "is/was/were/been/being + past participle"
-static PASSIVE_VOICE_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(
-        r"(?ix)
-        \b(?:is|are|was|were|be|been|being)\s+
-        (?:
-            \w+ed\b |           # Regular past participles (created, handled)
-            \w+en\b |           # Irregular (written, taken, given)
-            made\b | done\b | said\b | seen\b | known\b | shown\b |
-            built\b | sent\b | left\b | found\b | told\b | thought\b |
-            used\b | called\b | considered\b | designed\b | intended\b
-        )
-        ",
-    )
-    .expect("valid passive voice regex")
-});
-
 static BOLD_SPAN_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?s)(\*\*[^*]+\*\*|__[^_]+__)").expect("valid bold span regex"));
 
-static SPECIFICITY_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(
-        r"(?x)
-        # Semantic versions and simple versions
-        \bv\d+(?:\.\d+)*\b |                   # v1, v2, v1.0, v1.2.3
-        \b\d+\.\d+(?:\.\d+)+\b |               # 1.2.3 (at least 2 dots)
-        
-        # Issue/ticket references
-        \#\d{2,}\b |                           # GitHub issues (#123)
-        \b[A-Z]{2,}-\d+\b |                    # Jira tickets (PROJ-123)
-        
-        # Code identifiers (case-sensitive)
-        \b[a-z]{2,}[A-Z][A-Za-z0-9]{2,}\b |    # camelCase
-        \b[a-z]{2,}_[a-z0-9_]{2,}\b |          # snake_case
-        \b[A-Z]{2}[A-Z0-9_]{2,}\b |            # CONSTANTS (min 4 uppercase)
-        
-        # File paths with slash
-        /[\w./-]+ |
-        
-        # URLs
-        https?://\S+ |
-        www\.\S+ |
-        
-        # Figure/Table/Listing references (case insensitive)
-        (?i)\b(?:Figure|Fig|Table|Tbl|Listing|Example|Appendix)\s+\d+(?-i) |
-        
-        # Technical references
-        (?i)\b(?:RFC|ISO|IEEE)\s*\d+(?-i) |
-        \bport\s+\d{2,5}\b |
-        \$[A-Z][A-Z0-9_]+ |
-        
-        # Function/method calls
-        \b[a-zA-Z_]\w*\(\) |
-        \b\w+::\w+ |
-        
-        # Quantities with units (case insensitive for units)
-        \b\d+\s*(?i)(?:ms|sec|min|hours?|KB|MB|GB|TB|bytes?)(?-i)\b |
-        \b\d{2,}%\s+of\b
-    ",
-    )
-    .expect("valid specificity regex")
-});
-
 static URL_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)\bhttps?://[^\s<>()]+|\bwww\.[^\s<>()]+").expect("valid url regex")
 });
@@ -4189,38 +5000,34 @@ fn byte_to_location(text: &str, byte_offset: usize) -> Location {
     Location { line, column }
 }
 
-fn replacement_for(phrase: &str) -> Option<String> {
-    let mut map = HashMap::new();
-    map.insert("delve into", "look at");
-    map.insert("navigate the landscape", "map the area");
-    map.insert("delve", "look at");
-    map.insert("deep dive", "look closely");
-    map.insert("underscores", "shows");
-    map.insert("showcasing", "showing");
-    map.insert("pivotal", "important");
-    map.insert("realm", "field");
-    map.insert("meticulous", "detailed");
-    map.insert("leverage", "use");
-    map.insert("utilise", "use");
-    map.insert("utilize", "use");
-    map.insert("facilitate", "help");
-    map.insert("optimise", "improve");
-    map.insert("optimize", "improve");
-    map.insert("embark", "start");
-    map.insert("embark on a journey", "start");
-    map.insert("underscore", "highlight");
-    map.insert("aims to explore", "studies");
-    map.insert("aligns", "fits");
-    map.insert("seamless", "smooth");
-    map.insert("seamlessly", "smoothly");
-    map.insert("robust", "solid");
-    map.insert("robustly", "solidly");
-    map.insert("innovative", "new");
-    map.insert("transformative", "changing");
-    map.insert("unprecedented", "new");
-    map.insert("plethora", "many");
-    map.insert("empower", "help");
-    map.get(phrase).map(|s| s.to_string())
+fn straight_quote_for(ch: char) -> &'static str {
+    match ch {
+        '“' | '”' => "\"",
+        _ => "'",
+    }
+}
+
+/// Build a swap for a throttled phrase. Inflected matches ("leveraging") are
+/// left alone because the replacement would need its own inflection.
+fn buzzword_fix(text: &str, start: usize, end: usize, replacement: &str) -> Option<Fix> {
+    if text[end..].chars().next().is_some_and(is_word_char) {
+        return None;
+    }
+    let original = text.get(start..end)?;
+    let replacement = match_leading_case(original, replacement);
+    Some(Fix::replace((start, end), replacement, Applicability::Safe))
+}
+
+fn match_leading_case(original: &str, replacement: &str) -> String {
+    let starts_upper = original.chars().next().is_some_and(|c| c.is_uppercase());
+    if !starts_upper {
+        return replacement.to_string();
+    }
+    let mut chars = replacement.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
 }
 
 #[cfg(test)]
@@ -4231,6 +5038,13 @@ mod tests {
         Analyzer::new(Config::default()).unwrap()
     }
 
+    #[test]
+    fn edit_distance_counts_swaps_once() {
+        assert_eq!(edit_distance("recieve", "receive"), 1);
+        assert_eq!(edit_distance("analye", "analyze"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
     fn analyze_default(analyzer: &Analyzer, text: &str) -> DocumentReport {
         let profile = analyzer.profile_for_name("default").unwrap();
         analyzer.analyze_with_profile(text, profile)
@@ -4440,6 +5254,72 @@ mod tests {
             .any(|d| d.category == Category::Tone));
     }
 
+    #[test]
+    fn buzzword_swap_is_machine_applicable() {
+        let a = analyzer();
+        let text = "Leverage the cache and leverage the queue.";
+        let report = analyze_default(&a, text);
+        let outcome = apply_fixes(text, &report.diagnostics, false);
+        assert_eq!(outcome.text, "Use the cache and use the queue.");
+        assert_eq!(outcome.applied, 2);
+    }
+
+    #[test]
+    fn inflected_buzzword_has_no_fix() {
+        let a = analyzer();
+        let report = analyze_default(&a, "We utilized the cache.");
+        let diag = report
+            .diagnostics
+            .iter()
+            .find(|d| d.category == Category::Buzzword)
+            .expect("buzzword diagnostic");
+        assert!(diag.fix.is_none());
+    }
+
+    #[test]
+    fn unsafe_fixes_require_opt_in() {
+        let text = "one two";
+        let diag = Diagnostic {
+            category: Category::Buzzword,
+            rule: "buzzword/phrase".into(),
+            severity: Severity::Hint,
+            message: String::new(),
+            suggestion: None,
+            location: Location { line: 1, column: 1 },
+            span: (0, 3),
+            snippet: "one".into(),
+            fix: Some(Fix::replace((0, 3), "1", Applicability::Unsafe)),
+        };
+        let diags = [diag];
+        assert_eq!(apply_fixes(text, &diags, false).text, "one two");
+        assert_eq!(apply_fixes(text, &diags, true).text, "1 two");
+    }
+
+    #[test]
+    fn insertions_apply_once_per_offset() {
+        let text = "one two";
+        let diag = |span, replacement: &str| Diagnostic {
+            category: Category::Buzzword,
+            rule: "buzzword/phrase".into(),
+            severity: Severity::Hint,
+            message: String::new(),
+            suggestion: None,
+            location: Location { line: 1, column: 1 },
+            span,
+            snippet: String::new(),
+            fix: Some(Fix::replace(span, replacement, Applicability::Safe)),
+        };
+        let diags = [
+            diag((3, 7), " three"),
+            diag((3, 3), ","),
+            diag((7, 7), "."),
+            diag((7, 7), "."),
+        ];
+        let outcome = apply_fixes(text, &diags, false);
+        assert_eq!(outcome.text, "one, three.");
+        assert_eq!((outcome.applied, outcome.skipped), (3, 1));
+    }
+
     #[test]
     fn matches_common_suffix_buzzwords() {
         let a = analyzer();
@@ -4449,4 +5329,16 @@ mod tests {
             .iter()
             .any(|d| d.category == Category::Buzzword));
     }
+
+    #[test]
+    fn sentences_do_not_end_at_locale_abbreviations() {
+        let a = analyzer();
+        let text = "Das gilt z. B. am 3. Mai. Danach nicht mehr.";
+        let german = split_sentences(text, &MarkdownDoc::parse(text), a.locale(Language::De));
+        assert_eq!(german.len(), 2);
+        assert_eq!(german[1].0, "Danach nicht mehr.");
+        let text = "Use a tool, e.g. grep. Then stop.";
+        let english = split_sentences(text, &MarkdownDoc::parse(text), a.locale(Language::En));
+        assert_eq!(english.len(), 2);
+    }
 }
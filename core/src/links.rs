@@ -0,0 +1,847 @@
+//! Offline checks of Markdown links, images and heading anchors.
+//!
+//! Relative destinations are resolved against the file system: a missing
+//! file is reported as `links/broken-link` and a `#anchor` that matches no
+//! heading of the target Markdown file (or of the document itself) as
+//! `links/broken-anchor`, each with the nearest existing path or anchor as
+//! an unsafe fix. Anchors follow GitHub's slug rules: lowercase, punctuation
+//! dropped, spaces turned into hyphens, and `-1`, `-2`, ... appended to
+//! repeated headings. HTML `id` and `name` attributes count as anchors too.
+//!
+//! Nothing is fetched. External URLs are only listed in the link graph, and
+//! links that leave the repository root are not checked.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use once_cell::sync::{Lazy, OnceCell};
+use pulldown_cmark::{Event, LinkType, Parser, Tag};
+use rayon::prelude::*;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::blueprint::BlueprintError;
+use crate::markdown::{parser_options, MarkdownDoc};
+use crate::{
+    byte_to_location, edit_distance, Applicability, Category, Diagnostic, DisabledRanges, Fix,
+    Severity,
+};
+
+static SCHEME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z][A-Za-z0-9+.-]*:").expect("valid scheme regex"));
+
+static HTML_ANCHOR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)<[a-z][^>]*?\s(?:id|name)\s*=\s*["']([^"']+)["']"#)
+        .expect("valid html anchor regex")
+});
+
+/// The `links:` section.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LinkConfig {
+    pub enabled: bool,
+    /// Check `#anchor` parts against the target's headings.
+    pub anchors: bool,
+    /// Destinations to skip, as globs matched against the link as written
+    /// (`generated/**`, `../../issues/*`).
+    pub ignore: Vec<String>,
+}
+
+impl Default for LinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            anchors: true,
+            ignore: Vec::new(),
+        }
+    }
+}
+
+/// What a local link points at.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkKind {
+    /// Another Markdown document.
+    Doc,
+    /// Any other file or a directory.
+    File,
+    Image,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkNode {
+    pub path: String,
+    /// Text of the first heading.
+    pub title: Option<String>,
+    pub anchors: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkEdge {
+    pub from: String,
+    /// Root-relative target, when it exists.
+    pub to: Option<String>,
+    pub to_raw: String,
+    pub anchor: Option<String>,
+    pub kind: LinkKind,
+    pub line: usize,
+    /// The target exists and so does its anchor, if one was checked.
+    pub resolved: bool,
+}
+
+/// A link to another site, listed but not fetched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalLink {
+    pub from: String,
+    pub url: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LinkStats {
+    pub files_scanned: usize,
+    pub edges: usize,
+    pub edges_resolved: usize,
+    pub external: usize,
+    pub by_kind: BTreeMap<String, usize>,
+}
+
+/// Links between the scanned Markdown documents, shaped like the
+/// `flow blueprint` report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkGraph {
+    pub nodes: Vec<LinkNode>,
+    pub edges: Vec<LinkEdge>,
+    pub external: Vec<ExternalLink>,
+    pub stats: LinkStats,
+    pub errors: Vec<BlueprintError>,
+}
+
+/// A link or image destination found in a document.
+struct RawLink {
+    destination: String,
+    /// Where the destination is written; the whole link when it is not
+    /// written verbatim (escapes, entities).
+    span: (usize, usize),
+    verbatim: bool,
+    /// Start of the link itself, which ignore comments are checked against;
+    /// for reference links this is the use, not the definition.
+    start: usize,
+    image: bool,
+}
+
+/// How a local destination resolved.
+enum Target {
+    /// External, ignored, empty, or outside the repository.
+    Unchecked,
+    /// The document itself (`None`) or an existing path.
+    Found {
+        path: Option<PathBuf>,
+        broken_anchor: Option<BrokenAnchor>,
+    },
+    Missing(PathBuf),
+}
+
+struct BrokenAnchor {
+    anchor: String,
+    nearest: Option<String>,
+}
+
+/// Resolves links against the repository under `root`. Anchors of target
+/// documents and the file list used for suggestions are read once and
+/// reused, so create one checker per run.
+pub struct LinkChecker {
+    root: PathBuf,
+    anchors: bool,
+    ignore: Option<GlobSet>,
+    documents: Mutex<HashMap<PathBuf, Vec<String>>>,
+    files: OnceCell<Vec<PathBuf>>,
+}
+
+impl LinkChecker {
+    pub fn new(root: &Path, config: &LinkConfig) -> anyhow::Result<Self> {
+        let ignore = if config.ignore.is_empty() {
+            None
+        } else {
+            let mut builder = GlobSetBuilder::new();
+            for pattern in &config.ignore {
+                builder.add(Glob::new(pattern).map_err(|e| {
+                    anyhow::anyhow!("invalid glob `{pattern}` in links.ignore: {e}")
+                })?);
+            }
+            Some(builder.build()?)
+        };
+        Ok(Self {
+            root: canonical(root),
+            anchors: config.anchors,
+            ignore,
+            documents: Mutex::new(HashMap::new()),
+            files: OnceCell::new(),
+        })
+    }
+
+    /// Broken links and anchors in the Markdown document `text` at `path`.
+    pub fn check(&self, path: &Path, text: &str) -> Vec<Diagnostic> {
+        let doc = MarkdownDoc::parse(text);
+        self.check_with(path, text, &doc, &DisabledRanges::new(text, &doc))
+    }
+
+    /// [`LinkChecker::check`] with the disabled regions given.
+    pub(crate) fn check_with(
+        &self,
+        path: &Path,
+        text: &str,
+        doc: &MarkdownDoc,
+        filtered: &DisabledRanges,
+    ) -> Vec<Diagnostic> {
+        let own_anchors = anchors_in(text, doc);
+        let base = base_dir(path);
+        let mut diagnostics = Vec::new();
+        for link in extract_links(text) {
+            let diagnostic = match self.resolve(&base, &own_anchors, &link.destination) {
+                Target::Unchecked
+                | Target::Found {
+                    broken_anchor: None,
+                    ..
+                } => continue,
+                Target::Missing(wanted) => self.broken_link(text, &base, &link, wanted),
+                Target::Found {
+                    path,
+                    broken_anchor: Some(broken),
+                } => self.broken_anchor(text, &link, path.as_deref(), broken),
+            };
+            if filtered.is_category_disabled(link.start, Category::Links)
+                || filtered.is_line_ignored(diagnostic.location.line)
+                || filtered.is_rule_disabled(link.start, &diagnostic.rule)
+            {
+                continue;
+            }
+            diagnostics.push(diagnostic);
+        }
+        diagnostics
+    }
+
+    /// The graph of links between `files`. Links to other sites are listed
+    /// in `external`; links inside a document are left out.
+    pub fn graph(&self, files: &[PathBuf]) -> LinkGraph {
+        type FileLinks = (LinkNode, Vec<LinkEdge>, Vec<ExternalLink>);
+        let scans: Vec<Result<FileLinks, BlueprintError>> = files
+            .par_iter()
+            .map(|path| {
+                let display = self.display(&canonical(path));
+                let text = fs::read_to_string(path).map_err(|err| BlueprintError {
+                    path: display.clone(),
+                    message: format!("{err}"),
+                })?;
+                let doc = MarkdownDoc::parse(&text);
+                let own_anchors = anchors_in(&text, &doc);
+                let base = base_dir(path);
+                let mut edges = Vec::new();
+                let mut external = Vec::new();
+                for link in extract_links(&text) {
+                    let line = byte_to_location(&text, link.span.0).line;
+                    if is_external(&link.destination) {
+                        external.push(ExternalLink {
+                            from: display.clone(),
+                            url: link.destination,
+                            line,
+                        });
+                        continue;
+                    }
+                    let (to, resolved, target) =
+                        match self.resolve(&base, &own_anchors, &link.destination) {
+                            Target::Unchecked | Target::Found { path: None, .. } => continue,
+                            Target::Found {
+                                path: Some(target),
+                                broken_anchor,
+                            } => (Some(self.display(&target)), broken_anchor.is_none(), target),
+                            Target::Missing(target) => (None, false, target),
+                        };
+                    let kind = if link.image {
+                        LinkKind::Image
+                    } else if is_markdown(&target) {
+                        LinkKind::Doc
+                    } else {
+                        LinkKind::File
+                    };
+                    edges.push(LinkEdge {
+                        from: display.clone(),
+                        to,
+                        anchor: link
+                            .destination
+                            .split_once('#')
+                            .map(|(_, anchor)| anchor.to_string()),
+                        to_raw: link.destination,
+                        kind,
+                        line,
+                        resolved,
+                    });
+                }
+                let node = LinkNode {
+                    path: display,
+                    title: doc.headings.first().map(|heading| heading.text.clone()),
+                    anchors: own_anchors.len(),
+                };
+                Ok((node, edges, external))
+            })
+            .collect();
+
+        let mut graph = LinkGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            external: Vec::new(),
+            stats: LinkStats::default(),
+            errors: Vec::new(),
+        };
+        for scan in scans {
+            match scan {
+                Ok((node, edges, external)) => {
+                    graph.nodes.push(node);
+                    graph.edges.extend(edges);
+                    graph.external.extend(external);
+                }
+                Err(error) => graph.errors.push(error),
+            }
+        }
+        graph.nodes.sort_by(|a, b| a.path.cmp(&b.path));
+        graph
+            .edges
+            .sort_by(|a, b| (&a.from, a.line, &a.to_raw).cmp(&(&b.from, b.line, &b.to_raw)));
+        graph
+            .external
+            .sort_by(|a, b| (&a.from, a.line).cmp(&(&b.from, b.line)));
+
+        graph.stats.files_scanned = files.len();
+        graph.stats.edges = graph.edges.len();
+        graph.stats.edges_resolved = graph.edges.iter().filter(|e| e.resolved).count();
+        graph.stats.external = graph.external.len();
+        for edge in &graph.edges {
+            let kind = serde_json::to_value(edge.kind)
+                .ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_default();
+            *graph.stats.by_kind.entry(kind).or_default() += 1;
+        }
+        graph
+    }
+
+    fn resolve(&self, base: &Path, own_anchors: &[String], destination: &str) -> Target {
+        if destination.is_empty() || is_external(destination) {
+            return Target::Unchecked;
+        }
+        if self
+            .ignore
+            .as_ref()
+            .is_some_and(|set| set.is_match(destination))
+        {
+            return Target::Unchecked;
+        }
+        let (path_part, anchor) = split_destination(destination);
+        let path_part = percent_decode(path_part);
+        if path_part.is_empty() {
+            let broken_anchor = anchor.and_then(|anchor| self.missing_anchor(own_anchors, anchor));
+            return Target::Found {
+                path: None,
+                broken_anchor,
+            };
+        }
+        let joined = match path_part.strip_prefix('/') {
+            Some(rest) => self.root.join(rest),
+            None => base.join(&path_part),
+        };
+        let target = normalize(&joined);
+        // Links out of the repository (`../../issues` on a forge) cannot be
+        // resolved locally.
+        if base.starts_with(&self.root) && !target.starts_with(&self.root) {
+            return Target::Unchecked;
+        }
+        if !target.exists() {
+            return Target::Missing(target);
+        }
+        let broken_anchor = match anchor {
+            Some(anchor) if self.anchors && target.is_file() && is_markdown(&target) => {
+                let known = self.document_anchors(&target);
+                self.missing_anchor(&known, anchor)
+            }
+            _ => None,
+        };
+        Target::Found {
+            path: Some(target),
+            broken_anchor,
+        }
+    }
+
+    fn missing_anchor(&self, known: &[String], anchor: &str) -> Option<BrokenAnchor> {
+        let wanted = percent_decode(anchor).to_lowercase();
+        if !self.anchors || wanted.is_empty() || wanted == "top" || known.contains(&wanted) {
+            return None;
+        }
+        Some(BrokenAnchor {
+            nearest: nearest(&wanted, known.iter().map(String::as_str)).map(str::to_string),
+            anchor: anchor.to_string(),
+        })
+    }
+
+    fn document_anchors(&self, path: &Path) -> Vec<String> {
+        let mut documents = self
+            .documents
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        documents
+            .entry(path.to_path_buf())
+            .or_insert_with(|| match fs::read_to_string(path) {
+                Ok(text) => anchors(&text),
+                Err(_) => Vec::new(),
+            })
+            .clone()
+    }
+
+    fn broken_link(&self, text: &str, base: &Path, link: &RawLink, wanted: PathBuf) -> Diagnostic {
+        let (path_part, _) = split_destination(&link.destination);
+        let nearest = self.nearest_file(&wanted).map(|found| {
+            if path_part.starts_with('/') {
+                format!("/{}", self.display(&found))
+            } else {
+                relative_path(base, &found)
+            }
+        });
+        let what = if link.image { "Image" } else { "Link target" };
+        let suggestion = match &nearest {
+            Some(found) => format!("Did you mean `{found}`?"),
+            None => "Fix the path or remove the link.".to_string(),
+        };
+        let fix = nearest.filter(|_| link.verbatim).map(|found| {
+            let start = link.span.0;
+            Fix::replace(
+                (start, start + path_part.len()),
+                found,
+                Applicability::Unsafe,
+            )
+        });
+        link_diagnostic(
+            text,
+            link,
+            "links/broken-link",
+            format!("{what} `{}` does not exist.", percent_decode(path_part)),
+            suggestion,
+            fix,
+        )
+    }
+
+    fn broken_anchor(
+        &self,
+        text: &str,
+        link: &RawLink,
+        target: Option<&Path>,
+        broken: BrokenAnchor,
+    ) -> Diagnostic {
+        let message = match target {
+            Some(target) => format!(
+                "No heading in `{}` matches `#{}`.",
+                self.display(target),
+                broken.anchor
+            ),
+            None => format!("No heading matches `#{}`.", broken.anchor),
+        };
+        let suggestion = match &broken.nearest {
+            Some(anchor) => format!("Did you mean `#{anchor}`?"),
+            None => "Link to an existing heading or remove the anchor.".to_string(),
+        };
+        let fix = broken.nearest.filter(|_| link.verbatim).map(|anchor| {
+            let end = link.span.0 + link.destination.len();
+            Fix::replace(
+                (end - broken.anchor.len(), end),
+                anchor,
+                Applicability::Unsafe,
+            )
+        });
+        link_diagnostic(text, link, "links/broken-anchor", message, suggestion, fix)
+    }
+
+    /// The existing file whose name is closest to the missing one, preferring
+    /// the closest full path among equally close names.
+    fn nearest_file(&self, wanted: &Path) -> Option<PathBuf> {
+        let name = wanted.file_name()?.to_str()?.to_lowercase();
+        let wanted_path = self.display(wanted);
+        self.files()
+            .iter()
+            .filter_map(|file| {
+                let candidate = file.file_name()?.to_str()?.to_lowercase();
+                let distance = edit_distance(&name, &candidate);
+                (distance <= closeness(&name)).then(|| {
+                    let path_distance = edit_distance(&wanted_path, &self.display(file));
+                    (distance, path_distance, file)
+                })
+            })
+            .min()
+            .map(|(_, _, file)| file.clone())
+    }
+
+    /// Files under the root, skipping hidden directories and build output.
+    fn files(&self) -> &[PathBuf] {
+        self.files.get_or_init(|| {
+            WalkDir::new(&self.root)
+                .into_iter()
+                .filter_entry(|entry| {
+                    entry.depth() == 0 || {
+                        let name = entry.file_name().to_string_lossy();
+                        !(name.starts_with('.') || name == "target" || name == "node_modules")
+                    }
+                })
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+                .map(|entry| entry.into_path())
+                .collect()
+        })
+    }
+
+    /// Root-relative path with forward slashes, or the path itself when it
+    /// lies outside the root.
+    fn display(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+fn link_diagnostic(
+    text: &str,
+    link: &RawLink,
+    rule: &str,
+    message: String,
+    suggestion: String,
+    fix: Option<Fix>,
+) -> Diagnostic {
+    Diagnostic {
+        category: Category::Links,
+        rule: rule.into(),
+        severity: Severity::Warning,
+        message,
+        suggestion: Some(suggestion),
+        location: byte_to_location(text, link.span.0),
+        span: link.span,
+        snippet: link.destination.clone(),
+        fix,
+    }
+}
+
+/// Link and image destinations in document order, one per written
+/// destination: reference links that share a definition are listed once.
+fn extract_links(text: &str) -> Vec<RawLink> {
+    let mut parser = Parser::new_ext(text, parser_options()).into_offset_iter();
+    let events: Vec<_> = parser.by_ref().collect();
+    let definitions = parser.reference_definitions();
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    for (event, range) in events {
+        let (link_type, dest_url, id, image) = match event {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                id,
+                ..
+            }) => (link_type, dest_url, id, false),
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                id,
+                ..
+            }) => (link_type, dest_url, id, true),
+            _ => continue,
+        };
+        if matches!(link_type, LinkType::Email) {
+            continue;
+        }
+        let destination = dest_url.to_string();
+        let written = match link_type {
+            LinkType::Reference | LinkType::Collapsed | LinkType::Shortcut => definitions
+                .get(&id)
+                .and_then(|def| find_destination(text, def.span.clone(), &destination, false)),
+            _ => find_destination(text, range.clone(), &destination, true),
+        };
+        let (span, verbatim) = match written {
+            Some(start) => ((start, start + destination.len()), true),
+            None => ((range.start, range.end), false),
+        };
+        if seen.insert(span) {
+            links.push(RawLink {
+                destination,
+                span,
+                verbatim,
+                start: range.start,
+                image,
+            });
+        }
+    }
+    links
+}
+
+/// Offset of `destination` as written in `text[range]`. Inline links need
+/// it right after the `(` that opens the destination; the last such match
+/// belongs to the outer link when an image sits inside the link text.
+fn find_destination(
+    text: &str,
+    range: std::ops::Range<usize>,
+    destination: &str,
+    inline: bool,
+) -> Option<usize> {
+    if destination.is_empty() {
+        return None;
+    }
+    let source = text.get(range.clone())?;
+    let mut matches = source.match_indices(destination).map(|(at, _)| at);
+    let found = if inline {
+        matches
+            .filter(|&at| {
+                source[..at]
+                    .trim_end_matches(['<', ' ', '\t', '\n', '\r'])
+                    .ends_with('(')
+            })
+            .last()
+    } else {
+        matches.next()
+    };
+    found.map(|at| range.start + at)
+}
+
+/// GitHub anchors of the document's headings and its HTML `id`/`name`
+/// attributes, lowercased.
+pub fn anchors(text: &str) -> Vec<String> {
+    anchors_in(text, &MarkdownDoc::parse(text))
+}
+
+fn anchors_in(text: &str, doc: &MarkdownDoc) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut anchors = Vec::new();
+    for heading in &doc.headings {
+        let base = slugify(&heading.text);
+        let mut slug = base.clone();
+        while seen.contains_key(&slug) {
+            let count = seen.entry(base.clone()).or_default();
+            *count += 1;
+            slug = format!("{base}-{count}");
+        }
+        seen.insert(slug.clone(), 0);
+        anchors.push(slug);
+    }
+    anchors.extend(
+        HTML_ANCHOR_RE
+            .captures_iter(text)
+            .map(|cap| cap[1].to_lowercase()),
+    );
+    anchors
+}
+
+/// GitHub's anchor for a heading, without the suffix for repeats.
+pub fn slugify(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_external(destination: &str) -> bool {
+    destination.starts_with("//") || SCHEME_RE.is_match(destination)
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext.to_ascii_lowercase().as_str(), "md" | "markdown" | "mdx"))
+}
+
+/// The path part of a destination, without query, and its anchor.
+fn split_destination(destination: &str) -> (&str, Option<&str>) {
+    let (before, anchor) = match destination.split_once('#') {
+        Some((before, anchor)) => (before, Some(anchor)),
+        None => (destination, None),
+    };
+    (before.split('?').next().unwrap_or(before), anchor)
+}
+
+fn percent_decode(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit()
+        {
+            if let Ok(byte) = u8::from_str_radix(&raw[i + 1..i + 3], 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Edits allowed between a missing name and a suggestion.
+fn closeness(word: &str) -> usize {
+    (word.chars().count() / 4).clamp(1, 3)
+}
+
+/// The closest option within `closeness`, or one whose words include all
+/// the words of `wanted` or the other way round (a heading that gained or
+/// lost words).
+fn nearest<'a>(wanted: &str, options: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let wanted_words = anchor_words(wanted);
+    options
+        .filter_map(|option| {
+            let distance = edit_distance(wanted, option);
+            let option_words = anchor_words(option);
+            let related = !wanted_words.is_empty()
+                && !option_words.is_empty()
+                && (wanted_words.is_subset(&option_words) || option_words.is_subset(&wanted_words));
+            (distance <= closeness(wanted) || related).then_some((distance, option))
+        })
+        .min()
+        .map(|(_, option)| option)
+}
+
+/// Words of an anchor, short ones like `a` and `to` left out.
+fn anchor_words(anchor: &str) -> HashSet<&str> {
+    anchor.split('-').filter(|word| word.len() >= 3).collect()
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .unwrap_or_else(|_| normalize(&std::path::absolute(path).unwrap_or_else(|_| path.into())))
+}
+
+/// Directory that links in the document at `path` are relative to.
+fn base_dir(path: &Path) -> PathBuf {
+    let parent = path.parent().unwrap_or(Path::new(""));
+    let parent = if parent.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        parent
+    };
+    canonical(parent)
+}
+
+/// Resolve `.` and `..` without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+/// `to` relative to the directory `from`, with forward slashes.
+fn relative_path(from: &Path, to: &Path) -> String {
+    let from: Vec<_> = from.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let shared = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - shared];
+    parts.extend(
+        to[shared..]
+            .iter()
+            .map(|part| part.as_os_str().to_string_lossy().into_owned()),
+    );
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugs_follow_github_rules() {
+        let text = "# Getting Started!\n\n## API `v2` (beta)\n\n## FAQ\n\n## FAQ\n\n## faq-1\n\n<a name=\"Legacy\"></a>\n";
+        assert_eq!(
+            anchors(text),
+            [
+                "getting-started",
+                "api-v2-beta",
+                "faq",
+                "faq-1",
+                "faq-1-1",
+                "legacy"
+            ]
+        );
+        assert_eq!(
+            slugify("Émoji 🚀 and_more -- here"),
+            "émoji--and_more----here"
+        );
+    }
+
+    #[test]
+    fn test_destinations_are_found_where_written() {
+        let text = "See [![logo](img/a.png)](docs/a.md#intro \"docs/a.md\") and [ref][r], [ref again][r].\n\n[r]: <other%20file.md>\n";
+        let links = extract_links(text);
+        let found: Vec<(&str, &str, bool)> = links
+            .iter()
+            .map(|link| {
+                (
+                    link.destination.as_str(),
+                    &text[link.span.0..link.span.1],
+                    link.image,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("docs/a.md#intro", "docs/a.md#intro", false),
+                ("img/a.png", "img/a.png", true),
+                ("other%20file.md", "other%20file.md", false),
+            ]
+        );
+        assert_eq!(split_destination("a.md?x=1#top"), ("a.md", Some("top")));
+        assert_eq!(percent_decode("other%20file.md"), "other file.md");
+        assert!(is_external("https://example.com") && is_external("mailto:a@b.c"));
+        assert!(!is_external("docs/a.md"));
+    }
+
+    #[test]
+    fn test_relative_paths_and_nearest_names() {
+        assert_eq!(
+            relative_path(Path::new("/r/docs/api"), Path::new("/r/guide/setup.md")),
+            "../../guide/setup.md"
+        );
+        assert_eq!(
+            normalize(Path::new("/r/docs/../guide/./a.md")),
+            Path::new("/r/guide/a.md")
+        );
+        let options = ["installation", "usage", "configuring-profiles"];
+        assert_eq!(
+            nearest("instalation", options.into_iter()),
+            Some("installation")
+        );
+        assert_eq!(
+            nearest("profiles", options.into_iter()),
+            Some("configuring-profiles")
+        );
+        assert_eq!(
+            nearest("running-checks", ["running-the-checks"].into_iter()),
+            Some("running-the-checks")
+        );
+        assert_eq!(nearest("licence-terms", options.into_iter()), None);
+    }
+}
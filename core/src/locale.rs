@@ -0,0 +1,713 @@
+//! Built-in language packs.
+//!
+//! A document is analyzed in one language, picked in this order:
+//! 1. `lang:` (or `language:`) in its YAML frontmatter
+//! 2. The matched profile's `language`
+//! 3. The config's top-level `language` (default `en`)
+//!
+//! Each pack supplies the language-specific pieces of analysis:
+//! - Phrase lists: buzzwords, transitions, weasel words, puffery, marketing
+//! - Buzzword replacements offered as fixes
+//! - Passive-voice and specificity patterns
+//! - Sentence-boundary rules (abbreviations that do not end a sentence)
+//! - The stemmer used for inflection-aware matching
+//!
+//! English phrase lists come from the config (`buzzwords`, `transitions`, …),
+//! so the English pack leaves them empty. Other languages start from their
+//! pack's lists and take the config's edits to the defaults on top: entries
+//! a config adds (directly, with `add:`, or through `extends`) are matched
+//! too, and default entries it drops are dropped from the pack if listed
+//! there. `whitelist.allowed_phrases` applies in every language.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rust_stemmers::Algorithm;
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::phrases::PhraseMatcher;
+use crate::Config;
+
+/// Document language, written as a language tag in config and frontmatter.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub enum Language {
+    #[default]
+    En,
+    De,
+    Es,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::En, Language::De, Language::Es];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::De => "de",
+            Language::Es => "es",
+        }
+    }
+
+    /// Parse a BCP 47 style tag (`de`, `de-AT`, `es_MX`, `English`).
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "en" | "eng" | "english" => Some(Language::En),
+            "de" | "deu" | "ger" | "german" | "deutsch" => Some(Language::De),
+            "es" | "spa" | "spanish" | "español" | "espanol" => Some(Language::Es),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn stemmer(self) -> Algorithm {
+        match self {
+            Language::En => Algorithm::English,
+            Language::De => Algorithm::German,
+            Language::Es => Algorithm::Spanish,
+        }
+    }
+
+    pub fn pack(self) -> &'static LocalePack {
+        match self {
+            Language::En => &ENGLISH,
+            Language::De => &GERMAN,
+            Language::Es => &SPANISH,
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl TryFrom<String> for Language {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Language::from_tag(&value)
+            .ok_or_else(|| format!("unsupported language `{value}` (supported: en, de, es)"))
+    }
+}
+
+impl From<Language> for String {
+    fn from(value: Language) -> Self {
+        value.code().to_string()
+    }
+}
+
+impl JsonSchema for Language {
+    fn schema_name() -> String {
+        "Language".to_string()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> Schema {
+        let codes = Language::ALL.map(|language| language.code());
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            metadata: Some(Box::new(Metadata {
+                description: Some(format!(
+                    "Language tag, optionally with a region (`de-AT`): {}",
+                    codes.join(", ")
+                )),
+                examples: codes.iter().map(|code| (*code).into()).collect(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+static FRONTMATTER_LANG_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)^\s*(?:lang|language)\s*:\s*["']?([A-Za-z]+(?:[-_][A-Za-z0-9]+)*)"#)
+        .expect("valid frontmatter language regex")
+});
+
+/// Language declared in a YAML frontmatter block, if supported.
+pub fn frontmatter_language(frontmatter: &str) -> Option<Language> {
+    let caps = FRONTMATTER_LANG_RE.captures(frontmatter)?;
+    Language::from_tag(&caps[1])
+}
+
+/// Words that make up the specificity pattern, which suppresses buzzword and
+/// broad-term hints in sentences that carry concrete detail.
+struct SpecificityWords {
+    /// Figure/table reference nouns (`Figure 3`).
+    references: &'static str,
+    /// The word for a network port (`port 8080`), matched case-sensitively.
+    port: &'static str,
+    /// Units after a number (`200 ms`).
+    units: &'static str,
+    /// Connector after a percentage (`40% of`).
+    percent_of: &'static str,
+}
+
+/// Inflected forms phrase matching accepts for a listed word in `stem`
+/// mode: the word itself, or the word plus an ending, where the ending may
+/// replace one of `replaced` (`leverage` → `leveraging`) and, when
+/// `doubles_consonants` is set, follow a doubled final consonant (`ship` →
+/// `shipped`).
+struct InflectionRules {
+    endings: &'static [&'static str],
+    replaced: &'static [&'static str],
+    doubles_consonants: bool,
+}
+
+pub struct LocalePack {
+    pub language: Language,
+    pub buzzwords: &'static [&'static str],
+    pub transitions: &'static [&'static str],
+    pub weasel: &'static [&'static str],
+    pub puffery: &'static [&'static str],
+    pub marketing_cliches: &'static [&'static str],
+    /// Buzzword -> plain replacement, keyed by the lowercased listed form.
+    pub replacements: &'static [(&'static str, &'static str)],
+    /// Lowercased words that end in a period without ending the sentence,
+    /// written without their final period (`e.g`, `z`, `bzw`).
+    pub abbreviations: &'static [&'static str],
+    /// A period after a number is an ordinal (`am 3. Mai`), not a sentence end.
+    pub ordinal_dots: bool,
+    passive_voice: &'static str,
+    specificity: SpecificityWords,
+    inflections: InflectionRules,
+}
+
+impl LocalePack {
+    /// Whether `word` is `listed` or one of its inflected forms, both
+    /// folded.
+    pub(crate) fn is_inflection(&self, word: &str, listed: &str) -> bool {
+        let rules = &self.inflections;
+        let is_ending = |rest: &str| rules.endings.contains(&rest);
+        if word == listed || word.strip_prefix(listed).is_some_and(is_ending) {
+            return true;
+        }
+        let replaced = rules.replaced.iter().any(|tail| {
+            listed
+                .strip_suffix(tail)
+                .and_then(|head| word.strip_prefix(head))
+                .is_some_and(is_ending)
+        });
+        replaced
+            || (rules.doubles_consonants
+                && listed.chars().next_back().is_some_and(|last| {
+                    !"aeiouwxy".contains(last)
+                        && word
+                            .strip_prefix(listed)
+                            .and_then(|rest| rest.strip_prefix(last))
+                            .is_some_and(is_ending)
+                }))
+    }
+
+    fn passive_voice_regex(&self) -> Regex {
+        Regex::new(self.passive_voice).expect("valid passive voice regex")
+    }
+
+    fn specificity_regex(&self) -> Regex {
+        let words = &self.specificity;
+        Regex::new(&format!(
+            r"(?x)
+            # Semantic versions and simple versions
+            \bv\d+(?:\.\d+)*\b |                   # v1, v2, v1.0, v1.2.3
+            \b\d+\.\d+(?:\.\d+)+\b |               # 1.2.3 (at least 2 dots)
+
+            # Issue/ticket references
+            \#\d{{2,}}\b |                         # GitHub issues (#123)
+            \b[A-Z]{{2,}}-\d+\b |                  # Jira tickets (PROJ-123)
+
+            # Code identifiers (case-sensitive)
+            \b[a-z]{{2,}}[A-Z][A-Za-z0-9]{{2,}}\b |  # camelCase
+            \b[a-z]{{2,}}_[a-z0-9_]{{2,}}\b |        # snake_case
+            \b[A-Z]{{2}}[A-Z0-9_]{{2,}}\b |          # CONSTANTS (min 4 uppercase)
+
+            # File paths with slash
+            /[\w./-]+ |
+
+            # URLs
+            https?://\S+ |
+            www\.\S+ |
+
+            # Figure/Table/Listing references (case insensitive)
+            (?i)\b(?:{references})\s+\d+(?-i) |
+
+            # Technical references
+            (?i)\b(?:RFC|ISO|IEEE)\s*\d+(?-i) |
+            \b(?:{port})\s+\d{{2,5}}\b |
+            \$[A-Z][A-Z0-9_]+ |
+
+            # Function/method calls
+            \b[a-zA-Z_]\w*\(\) |
+            \b\w+::\w+ |
+
+            # Quantities with units (case insensitive for units)
+            \b\d+\s*(?i)(?:{units})(?-i)\b |
+            \b\d{{2,}}%\s+(?:{percent_of})\b
+            ",
+            references = words.references,
+            port = words.port,
+            units = words.units,
+            percent_of = words.percent_of,
+        ))
+        .expect("valid specificity regex")
+    }
+
+    fn replacement_map(&self) -> HashMap<&'static str, &'static str> {
+        self.replacements.iter().copied().collect()
+    }
+}
+
+/// A pack compiled against the config, built once per language.
+pub(crate) struct LocaleRuntime {
+    pub(crate) pack: &'static LocalePack,
+    pub(crate) puffery: Option<PhraseMatcher>,
+    pub(crate) buzzwords: Option<PhraseMatcher>,
+    pub(crate) weasel: Option<PhraseMatcher>,
+    pub(crate) transitions: Option<PhraseMatcher>,
+    pub(crate) marketing: Option<PhraseMatcher>,
+    pub(crate) passive_voice: Regex,
+    pub(crate) specificity: Regex,
+    replacements: HashMap<&'static str, &'static str>,
+}
+
+impl LocaleRuntime {
+    pub(crate) fn compile(language: Language, config: &Config) -> Self {
+        let pack = language.pack();
+        let matcher = |configured: &[String], default: &[String], builtin: &[&str]| {
+            let phrases = if language == Language::En {
+                configured.to_vec()
+            } else {
+                apply_edits(builtin, configured, default)
+            };
+            PhraseMatcher::new(&phrases, &config.matching, language)
+        };
+        let defaults = &*DEFAULT_CONFIG;
+        Self {
+            pack,
+            puffery: matcher(&config.puffery.ban, &defaults.puffery.ban, pack.puffery),
+            buzzwords: matcher(
+                &config.buzzwords.throttle,
+                &defaults.buzzwords.throttle,
+                pack.buzzwords,
+            ),
+            weasel: matcher(&config.weasel.ban, &defaults.weasel.ban, pack.weasel),
+            transitions: matcher(
+                &config.transitions.throttle,
+                &defaults.transitions.throttle,
+                pack.transitions,
+            ),
+            marketing: matcher(
+                &config.marketing_cliches.ban,
+                &defaults.marketing_cliches.ban,
+                pack.marketing_cliches,
+            ),
+            passive_voice: pack.passive_voice_regex(),
+            specificity: pack.specificity_regex(),
+            replacements: pack.replacement_map(),
+        }
+    }
+
+    /// Plain replacement for a listed buzzword.
+    pub(crate) fn replacement_for(&self, phrase: &str) -> Option<String> {
+        self.replacements
+            .get(phrase.to_lowercase().as_str())
+            .map(|s| s.to_string())
+    }
+
+    /// Whether the `.` ending `before` closes an abbreviation or an ordinal
+    /// rather than a sentence.
+    pub(crate) fn is_abbreviation_dot(&self, before: &str) -> bool {
+        let token = before
+            .rsplit(|c: char| c.is_whitespace() || matches!(c, '(' | '[' | '"' | '\''))
+            .next()
+            .unwrap_or_default();
+        if token.is_empty() {
+            return false;
+        }
+        if self.pack.ordinal_dots && token.len() <= 2 && token.bytes().all(|b| b.is_ascii_digit()) {
+            return true;
+        }
+        self.pack
+            .abbreviations
+            .contains(&token.to_lowercase().as_str())
+    }
+}
+
+static DEFAULT_CONFIG: Lazy<Config> = Lazy::new(Config::default);
+
+/// A pack's `builtin` list with the edits that took the config's list from
+/// `default` to `configured`. Entries compare case-insensitively.
+fn apply_edits(builtin: &[&str], configured: &[String], default: &[String]) -> Vec<String> {
+    let key = |phrase: &str| phrase.trim().to_lowercase();
+    let listed = |list: &[String], phrase: &str| list.iter().any(|entry| key(entry) == key(phrase));
+    let mut phrases: Vec<String> = builtin
+        .iter()
+        .filter(|phrase| listed(configured, phrase) || !listed(default, phrase))
+        .map(|phrase| phrase.to_string())
+        .collect();
+    for phrase in configured {
+        if !listed(default, phrase) && !listed(&phrases, phrase) {
+            phrases.push(phrase.clone());
+        }
+    }
+    phrases
+}
+
+static ENGLISH: LocalePack = LocalePack {
+    language: Language::En,
+    buzzwords: &[],
+    transitions: &[],
+    weasel: &[],
+    puffery: &[],
+    marketing_cliches: &[],
+    replacements: &[
+        ("delve into", "look at"),
+        ("navigate the landscape", "map the area"),
+        ("delve", "look at"),
+        ("deep dive", "look closely"),
+        ("underscores", "shows"),
+        ("showcasing", "showing"),
+        ("pivotal", "important"),
+        ("realm", "field"),
+        ("meticulous", "detailed"),
+        ("leverage", "use"),
+        ("utilise", "use"),
+        ("utilize", "use"),
+        ("facilitate", "help"),
+        ("optimise", "improve"),
+        ("optimize", "improve"),
+        ("embark", "start"),
+        ("embark on a journey", "start"),
+        ("underscore", "highlight"),
+        ("aims to explore", "studies"),
+        ("aligns", "fits"),
+        ("seamless", "smooth"),
+        ("seamlessly", "smoothly"),
+        ("robust", "solid"),
+        ("robustly", "solidly"),
+        ("innovative", "new"),
+        ("transformative", "changing"),
+        ("unprecedented", "new"),
+        ("plethora", "many"),
+        ("empower", "help"),
+    ],
+    abbreviations: &["e.g", "i.e", "vs", "cf", "mr", "mrs", "ms", "dr", "approx"],
+    ordinal_dots: false,
+    passive_voice: r"(?ix)
+        \b(?:is|are|was|were|be|been|being)\s+
+        (?:
+            \w+ed\b |           # Regular past participles (created, handled)
+            \w+en\b |           # Irregular (written, taken, given)
+            made\b | done\b | said\b | seen\b | known\b | shown\b |
+            built\b | sent\b | left\b | found\b | told\b | thought\b |
+            used\b | called\b | considered\b | designed\b | intended\b
+        )
+        ",
+    specificity: SpecificityWords {
+        references: "Figure|Fig|Table|Tbl|Listing|Example|Appendix",
+        port: "port",
+        units: "ms|sec|min|hours?|KB|MB|GB|TB|bytes?",
+        percent_of: "of",
+    },
+    inflections: InflectionRules {
+        endings: &[
+            "s", "es", "ed", "ing", "ly", "er", "est", "ies", "ied", "ier", "iest", "ily",
+        ],
+        replaced: &["e", "y"],
+        doubles_consonants: true,
+    },
+};
+
+static GERMAN: LocalePack = LocalePack {
+    language: Language::De,
+    buzzwords: &[
+        "nahtlos",
+        "robust",
+        "ganzheitlich",
+        "innovativ",
+        "Synergie",
+        "Mehrwert",
+        "zukunftsweisend",
+        "wegweisend",
+        "bahnbrechend",
+        "skalierbar",
+        "leistungsstark",
+        "maßgeschneidert",
+        "Paradigmenwechsel",
+        "Ökosystem",
+        "optimieren",
+        "revolutionär",
+        "unverzichtbar",
+        "tiefgreifend",
+        "facettenreich",
+        "vielschichtig",
+        "eintauchen",
+        "auf ein neues Level heben",
+        "spielt eine entscheidende Rolle",
+        "von entscheidender Bedeutung",
+        "in der heutigen schnelllebigen Welt",
+    ],
+    transitions: &[
+        "darüber hinaus",
+        "des Weiteren",
+        "außerdem",
+        "zudem",
+        "ferner",
+        "zusammenfassend",
+        "abschließend",
+        "letztendlich",
+        "im Wesentlichen",
+        "nichtsdestotrotz",
+        "in diesem Zusammenhang",
+        "es ist wichtig zu beachten",
+        "es sei darauf hingewiesen",
+    ],
+    weasel: &[
+        "man sagt",
+        "es heißt",
+        "Experten sagen",
+        "Studien zeigen",
+        "viele glauben",
+        "einige behaupten",
+        "es wird allgemein angenommen",
+        "Kritiker meinen",
+        "laut Experten",
+    ],
+    puffery: &[
+        "Weltklasse",
+        "erstklassig",
+        "branchenführend",
+        "marktführend",
+        "unvergleichlich",
+        "hochmodern",
+        "auf höchstem Niveau",
+        "preisgekrönt",
+    ],
+    marketing_cliches: &[
+        "jetzt zugreifen",
+        "nicht verpassen",
+        "nur für kurze Zeit",
+        "überzeugen Sie sich selbst",
+        "die perfekte Lösung",
+        "ohne Kompromisse",
+    ],
+    replacements: &[
+        ("nahtlos", "reibungslos"),
+        ("ganzheitlich", "umfassend"),
+        ("innovativ", "neu"),
+        ("optimieren", "verbessern"),
+        ("leistungsstark", "schnell"),
+        ("maßgeschneidert", "angepasst"),
+        ("zukunftsweisend", "neu"),
+        ("bahnbrechend", "neu"),
+        ("revolutionär", "neu"),
+        ("eintauchen", "untersuchen"),
+    ],
+    abbreviations: &[
+        "z", "b", "d", "h", "u", "a", "s", "bzw", "usw", "vgl", "ggf", "evtl", "ca", "nr", "dr",
+        "inkl", "exkl", "sog", "bspw", "etc", "z.b", "d.h", "u.a",
+    ],
+    ordinal_dots: true,
+    passive_voice: r"(?ix)
+        \b(?:wird|werden|wurde|wurden|worden|wirst|werdet)\b
+        [^.!?]{0,60}?
+        \b(?:
+            ge\w+(?:t|en) |                        # gemacht, geschrieben
+            (?:be|er|ver|zer|ent|emp|miss)\w+t     # bearbeitet, erstellt, verwendet
+        )\b
+        ",
+    specificity: SpecificityWords {
+        references: "Abbildung|Abb|Tabelle|Tab|Listing|Beispiel|Anhang",
+        port: "Port|port",
+        units: "ms|s|sek|sekunden?|min|minuten?|std|stunden?|KB|MB|GB|TB|bytes?",
+        percent_of: "der|aller|von",
+    },
+    inflections: InflectionRules {
+        endings: &[
+            "e", "em", "en", "er", "es", "n", "s", "st", "t", "et", "te", "ten", "ter", "tes",
+        ],
+        replaced: &["e", "en"],
+        doubles_consonants: false,
+    },
+};
+
+static SPANISH: LocalePack = LocalePack {
+    language: Language::Es,
+    buzzwords: &[
+        "sinergia",
+        "robusto",
+        "innovador",
+        "disruptivo",
+        "vanguardista",
+        "de vanguardia",
+        "holístico",
+        "escalable",
+        "potenciar",
+        "apalancar",
+        "optimizar",
+        "sin fisuras",
+        "ecosistema",
+        "paradigma",
+        "transformador",
+        "revolucionario",
+        "sumergirse",
+        "profundizar en",
+        "en el mundo actual",
+        "en la era digital",
+        "desempeña un papel crucial",
+        "llevar al siguiente nivel",
+    ],
+    transitions: &[
+        "además",
+        "asimismo",
+        "por otro lado",
+        "en conclusión",
+        "en resumen",
+        "cabe destacar",
+        "cabe mencionar",
+        "es importante destacar",
+        "es importante señalar",
+        "no obstante",
+        "por consiguiente",
+        "en definitiva",
+        "en este sentido",
+        "dicho esto",
+    ],
+    weasel: &[
+        "se dice que",
+        "muchos creen",
+        "algunos dicen",
+        "los expertos dicen",
+        "según los expertos",
+        "estudios demuestran",
+        "se cree que",
+        "es bien sabido",
+        "la mayoría de la gente",
+    ],
+    puffery: &[
+        "de clase mundial",
+        "líder del sector",
+        "líder en la industria",
+        "incomparable",
+        "inigualable",
+        "sin igual",
+        "de primer nivel",
+        "galardonado",
+    ],
+    marketing_cliches: &[
+        "no te lo pierdas",
+        "por tiempo limitado",
+        "compruébalo tú mismo",
+        "la solución perfecta",
+        "únete a miles de",
+    ],
+    replacements: &[
+        ("potenciar", "mejorar"),
+        ("apalancar", "usar"),
+        ("optimizar", "mejorar"),
+        ("robusto", "sólido"),
+        ("innovador", "nuevo"),
+        ("disruptivo", "nuevo"),
+        ("vanguardista", "moderno"),
+        ("de vanguardia", "moderno"),
+        ("holístico", "completo"),
+        ("profundizar en", "estudiar"),
+    ],
+    abbreviations: &[
+        "sr", "sra", "srta", "dr", "dra", "ud", "uds", "p", "ej", "pág", "núm", "aprox", "vs",
+        "etc", "p.ej",
+    ],
+    ordinal_dots: false,
+    passive_voice: r"(?ix)
+        \b(?:es|son|fue|fueron|era|eran|será|serán|sería|serían|sido|ser|siendo)\s+
+        (?:
+            \w+(?:ad|id)[oa]s?\b |       # creado, escritas, definidos
+            (?:hech|escrit|dich|vist|puest|abiert|rot|cubiert|resuelt|vuelt)[oa]s?\b
+        )
+        ",
+    specificity: SpecificityWords {
+        references: "Figura|Fig|Tabla|Listado|Ejemplo|Apéndice|Anexo",
+        port: "puerto",
+        units: "ms|s|seg|segundos?|min|minutos?|horas?|KB|MB|GB|TB|bytes?",
+        percent_of: "de|del",
+    },
+    inflections: InflectionRules {
+        endings: &[
+            "s", "es", "a", "as", "o", "os", "mente", "amos", "an", "ado", "ada", "ados", "adas",
+            "ando", "emos", "en", "ido", "ida", "idos", "idas", "iendo", "imos",
+        ],
+        replaced: &["a", "o", "e", "ar", "er", "ir"],
+        doubles_consonants: false,
+    },
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_tags_and_frontmatter() {
+        assert_eq!(Language::from_tag("de-AT"), Some(Language::De));
+        assert_eq!(Language::from_tag("es_MX"), Some(Language::Es));
+        assert_eq!(Language::from_tag("fr"), None);
+        assert_eq!(
+            frontmatter_language("---\ntitle: Hallo\nlang: de-DE\n---\n"),
+            Some(Language::De)
+        );
+        assert_eq!(
+            frontmatter_language("---\nlanguage: \"es\"\n---"),
+            Some(Language::Es)
+        );
+        assert_eq!(frontmatter_language("---\ntitle: x\n---"), None);
+        let parsed: Language = serde_yaml::from_str("de").unwrap();
+        assert_eq!(parsed, Language::De);
+        assert!(serde_yaml::from_str::<Language>("fr").is_err());
+    }
+
+    #[test]
+    fn test_pack_patterns_compile() {
+        for language in Language::ALL {
+            let pack = language.pack();
+            assert_eq!(pack.language, language);
+            pack.passive_voice_regex();
+            pack.specificity_regex();
+        }
+        assert!(GERMAN
+            .passive_voice_regex()
+            .is_match("Die Datei wird vom System erstellt."));
+        assert!(SPANISH
+            .passive_voice_regex()
+            .is_match("El informe fue escrito por el equipo."));
+        assert!(GERMAN.specificity_regex().is_match("Siehe Abbildung 3."));
+    }
+
+    #[test]
+    fn test_config_edits_apply_to_pack_lists() {
+        let default = vec!["leverage".to_string(), "nahtlos".to_string()];
+        let configured = vec!["leverage".to_string(), "Synergie".to_string()];
+        assert_eq!(
+            apply_edits(&["nahtlos", "innovativ"], &configured, &default),
+            vec!["innovativ", "Synergie"]
+        );
+        assert_eq!(
+            apply_edits(&["nahtlos", "synergie"], &configured, &default),
+            vec!["synergie"]
+        );
+        assert_eq!(
+            apply_edits(&["nahtlos"], &default, &default),
+            vec!["nahtlos"]
+        );
+    }
+}
@@ -0,0 +1,491 @@
+//! CommonMark/GFM document model shared by the prose detectors.
+//!
+//! Each document is parsed once with `pulldown-cmark`. The model records:
+//! - Prose blocks (paragraphs, headings, list items, table cells) with content spans
+//! - Headings with their level and plain text
+//! - Lists with their direct items and how each item opens
+//! - Regions that are not prose (code, HTML blocks, link destinations,
+//!   reference definitions, frontmatter)
+//!
+//! Detectors walk these typed nodes instead of guessing structure from
+//! leading `#`, `-` or ```` ``` ```` characters.
+
+use std::ops::Range;
+
+use pulldown_cmark::{Event, LinkType, Options, Parser, Tag, TagEnd};
+
+/// What kind of node a prose block came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockKind {
+    Paragraph,
+    Heading,
+    ListItem,
+    TableCell,
+}
+
+/// A run of inline prose. `span` covers the inline content only, so list
+/// markers, `#` prefixes, setext underlines and table pipes are excluded.
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub kind: BlockKind,
+    pub span: (usize, usize),
+    /// True when the block sits inside a block quote.
+    pub in_quote: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Heading {
+    pub level: usize,
+    /// Full source span, including markers and setext underlines.
+    pub span: (usize, usize),
+    /// Span of the inline content, if the heading has any.
+    pub content_span: Option<(usize, usize)>,
+    /// Plain text of the heading with inline markup removed.
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct List {
+    pub span: (usize, usize),
+    pub ordered: bool,
+    /// Nesting depth; top-level lists are 0.
+    pub depth: usize,
+    /// Direct items only. Nested lists appear separately in `MarkdownDoc::lists`.
+    pub items: Vec<ListItem>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListItem {
+    pub span: (usize, usize),
+    /// The first inline node of the item is strong emphasis (`**lead**`).
+    pub bold_lead: bool,
+    /// First visible character of the item text, ignoring task-list markers.
+    pub lead_char: Option<char>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownDoc {
+    /// Prose blocks in document order.
+    pub blocks: Vec<Block>,
+    pub headings: Vec<Heading>,
+    pub lists: Vec<List>,
+    /// Fenced and indented code blocks.
+    pub code_blocks: Vec<(usize, usize)>,
+    pub inline_code: Vec<(usize, usize)>,
+    pub html_blocks: Vec<(usize, usize)>,
+    /// Link and image destinations (`(url "title")`, `[label]`) and autolinks.
+    pub link_destinations: Vec<(usize, usize)>,
+    pub reference_definitions: Vec<(usize, usize)>,
+    pub frontmatter: Option<(usize, usize)>,
+}
+
+impl MarkdownDoc {
+    pub fn parse(text: &str) -> Self {
+        let parser = Parser::new_ext(text, parser_options()).into_offset_iter();
+
+        let mut builder = Builder::default();
+        builder.doc.reference_definitions = parser
+            .reference_definitions()
+            .iter()
+            .map(|(_, def)| (def.span.start, def.span.end))
+            .collect();
+        builder.doc.reference_definitions.sort_unstable();
+
+        for (event, range) in parser {
+            builder.event(text, event, range);
+        }
+        builder.finish()
+    }
+
+    /// Prose block containing `offset`, if any.
+    pub fn block_at(&self, offset: usize) -> Option<&Block> {
+        let idx = self.blocks.partition_point(|b| b.span.0 <= offset);
+        let block = self.blocks.get(idx.checked_sub(1)?)?;
+        (offset < block.span.1).then_some(block)
+    }
+
+    /// Kind of the prose block containing `offset`, if any.
+    pub fn block_kind_at(&self, offset: usize) -> Option<BlockKind> {
+        self.block_at(offset).map(|b| b.kind)
+    }
+}
+
+/// The Markdown extensions every parse of a document enables.
+pub(crate) fn parser_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+}
+
+#[derive(Debug)]
+enum Frame {
+    Prose {
+        kind: BlockKind,
+        heading: Option<usize>,
+        extent: Option<(usize, usize)>,
+    },
+    /// List item; `extent` collects inline content of tight items that is
+    /// not wrapped in a paragraph.
+    Item {
+        list: usize,
+        item: usize,
+        extent: Option<(usize, usize)>,
+        lead_seen: bool,
+    },
+    List(usize),
+    Quote,
+    Other,
+}
+
+#[derive(Debug)]
+struct OpenLink {
+    end: usize,
+    children_end: usize,
+}
+
+#[derive(Default)]
+struct Builder {
+    doc: MarkdownDoc,
+    stack: Vec<Frame>,
+    links: Vec<OpenLink>,
+}
+
+impl Builder {
+    fn event(&mut self, text: &str, event: Event<'_>, range: Range<usize>) {
+        match event {
+            Event::Start(tag) => self.start(tag, range),
+            Event::End(tag) => self.end(text, tag),
+            Event::Text(content) => {
+                let lead = content.trim_start().chars().next();
+                self.inline(&range, false, lead);
+                self.push_heading_text(&content);
+            }
+            Event::Code(content) => {
+                self.doc.inline_code.push((range.start, range.end));
+                let lead = text[range.clone()].chars().next();
+                self.inline(&range, false, lead);
+                self.push_heading_text(&content);
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                self.inline(&range, false, None);
+                self.push_heading_text(" ");
+            }
+            Event::InlineHtml(_)
+            | Event::FootnoteReference(_)
+            | Event::InlineMath(_)
+            | Event::DisplayMath(_) => {
+                let lead = text[range.clone()].chars().next();
+                self.inline(&range, false, lead);
+            }
+            Event::Rule => self.flush_item(),
+            Event::Html(_) | Event::TaskListMarker(_) => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>, range: Range<usize>) {
+        let span = (range.start, range.end);
+        match tag {
+            Tag::Paragraph => {
+                self.flush_item();
+                let kind = match self.stack.last() {
+                    Some(Frame::Item { .. }) => BlockKind::ListItem,
+                    _ => BlockKind::Paragraph,
+                };
+                self.stack.push(Frame::Prose {
+                    kind,
+                    heading: None,
+                    extent: None,
+                });
+            }
+            Tag::Heading { level, .. } => {
+                self.flush_item();
+                self.doc.headings.push(Heading {
+                    level: level as usize,
+                    span,
+                    content_span: None,
+                    text: String::new(),
+                });
+                self.stack.push(Frame::Prose {
+                    kind: BlockKind::Heading,
+                    heading: Some(self.doc.headings.len() - 1),
+                    extent: None,
+                });
+            }
+            Tag::TableCell => self.stack.push(Frame::Prose {
+                kind: BlockKind::TableCell,
+                heading: None,
+                extent: None,
+            }),
+            Tag::BlockQuote(_) => {
+                self.flush_item();
+                self.stack.push(Frame::Quote);
+            }
+            Tag::CodeBlock(_) => {
+                self.flush_item();
+                self.doc.code_blocks.push(span);
+                self.stack.push(Frame::Other);
+            }
+            Tag::HtmlBlock => {
+                self.flush_item();
+                self.doc.html_blocks.push(span);
+                self.stack.push(Frame::Other);
+            }
+            Tag::MetadataBlock(_) => {
+                self.doc.frontmatter = Some(span);
+                self.stack.push(Frame::Other);
+            }
+            Tag::List(first) => {
+                self.flush_item();
+                let depth = self
+                    .stack
+                    .iter()
+                    .filter(|f| matches!(f, Frame::List(_)))
+                    .count();
+                self.doc.lists.push(List {
+                    span,
+                    ordered: first.is_some(),
+                    depth,
+                    items: Vec::new(),
+                });
+                self.stack.push(Frame::List(self.doc.lists.len() - 1));
+            }
+            Tag::Item => {
+                let list = self
+                    .stack
+                    .iter()
+                    .rev()
+                    .find_map(|f| match f {
+                        Frame::List(idx) => Some(*idx),
+                        _ => None,
+                    })
+                    .unwrap_or(0);
+                let Some(entry) = self.doc.lists.get_mut(list) else {
+                    self.stack.push(Frame::Other);
+                    return;
+                };
+                entry.items.push(ListItem {
+                    span,
+                    bold_lead: false,
+                    lead_char: None,
+                });
+                self.stack.push(Frame::Item {
+                    list,
+                    item: entry.items.len() - 1,
+                    extent: None,
+                    lead_seen: false,
+                });
+            }
+            Tag::Table(_)
+            | Tag::TableHead
+            | Tag::TableRow
+            | Tag::FootnoteDefinition(_)
+            | Tag::DefinitionList
+            | Tag::DefinitionListTitle
+            | Tag::DefinitionListDefinition => {
+                self.flush_item();
+                self.stack.push(Frame::Other);
+            }
+            Tag::Link { link_type, .. } => self.open_link(link_type, &range, 1),
+            Tag::Image { link_type, .. } => self.open_link(link_type, &range, 2),
+            Tag::Strong => self.inline(&range, true, None),
+            Tag::Emphasis | Tag::Strikethrough | Tag::Superscript | Tag::Subscript => {
+                self.inline(&range, false, None)
+            }
+        }
+    }
+
+    fn end(&mut self, text: &str, tag: TagEnd) {
+        match tag {
+            TagEnd::Link | TagEnd::Image => {
+                if let Some(link) = self.links.pop() {
+                    // Skip the `]` that closes the link text.
+                    let start = match text.as_bytes().get(link.children_end) {
+                        Some(b']') => link.children_end + 1,
+                        _ => link.children_end,
+                    };
+                    if start < link.end {
+                        self.doc.link_destinations.push((start, link.end));
+                    }
+                }
+            }
+            TagEnd::Emphasis
+            | TagEnd::Strong
+            | TagEnd::Strikethrough
+            | TagEnd::Superscript
+            | TagEnd::Subscript => {}
+            TagEnd::Item => {
+                self.flush_item();
+                self.stack.pop();
+            }
+            _ => {
+                if let Some(Frame::Prose {
+                    kind,
+                    heading,
+                    extent,
+                }) = self.stack.pop()
+                {
+                    if let Some(entry) = heading.and_then(|idx| self.doc.headings.get_mut(idx)) {
+                        entry.content_span = extent;
+                        entry.text = entry.text.trim().to_string();
+                    }
+                    if let Some(span) = extent {
+                        let in_quote = self.in_quote();
+                        self.doc.blocks.push(Block {
+                            kind,
+                            span,
+                            in_quote,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Links and images are inline nodes whose destination follows the last
+    /// child; `opener` is the length of `[` or `![`.
+    fn open_link(&mut self, link_type: LinkType, range: &Range<usize>, opener: usize) {
+        self.inline(range, false, None);
+        if matches!(link_type, LinkType::Autolink | LinkType::Email) {
+            self.doc.link_destinations.push((range.start, range.end));
+        }
+        self.links.push(OpenLink {
+            end: range.end,
+            children_end: range.start + opener,
+        });
+    }
+
+    /// Record an inline node against the innermost prose container.
+    fn inline(&mut self, range: &Range<usize>, strong: bool, lead: Option<char>) {
+        for link in &mut self.links {
+            link.children_end = link.children_end.max(range.end);
+        }
+
+        let len = self.stack.len();
+        let item_frame = match self.stack.last() {
+            Some(Frame::Item { .. }) => Some(len - 1),
+            Some(Frame::Prose {
+                kind: BlockKind::ListItem,
+                ..
+            }) if len >= 2 => Some(len - 2),
+            _ => None,
+        };
+        if let Some(idx) = item_frame {
+            if let Frame::Item {
+                list,
+                item,
+                lead_seen,
+                ..
+            } = &mut self.stack[idx]
+            {
+                if !*lead_seen {
+                    *lead_seen = true;
+                    if let Some(entry) = self.doc.lists[*list].items.get_mut(*item) {
+                        entry.bold_lead = strong;
+                        entry.lead_char = lead;
+                    }
+                }
+            }
+        }
+
+        match self.stack.last_mut() {
+            Some(Frame::Prose { extent, .. }) | Some(Frame::Item { extent, .. }) => {
+                *extent = Some(match *extent {
+                    Some((start, end)) => (start.min(range.start), end.max(range.end)),
+                    None => (range.start, range.end),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn push_heading_text(&mut self, content: &str) {
+        if let Some(Frame::Prose {
+            heading: Some(idx), ..
+        }) = self.stack.last()
+        {
+            self.doc.headings[*idx].text.push_str(content);
+        }
+    }
+
+    /// Emit the loose inline content of a tight list item as its own block.
+    fn flush_item(&mut self) {
+        let in_quote = self.in_quote();
+        if let Some(Frame::Item { extent, .. }) = self.stack.last_mut() {
+            if let Some(span) = extent.take() {
+                self.doc.blocks.push(Block {
+                    kind: BlockKind::ListItem,
+                    span,
+                    in_quote,
+                });
+            }
+        }
+    }
+
+    fn in_quote(&self) -> bool {
+        self.stack.iter().any(|f| matches!(f, Frame::Quote))
+    }
+
+    fn finish(mut self) -> MarkdownDoc {
+        self.doc.blocks.sort_by_key(|b| b.span.0);
+        self.doc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setext_heading_and_table_cells() {
+        let text =
+            "Overview\n========\n\n| Name | Notes |\n| --- | --- |\n| cache | Fast path. |\n";
+        let doc = MarkdownDoc::parse(text);
+        assert_eq!(doc.headings.len(), 1);
+        assert_eq!(doc.headings[0].level, 1);
+        assert_eq!(doc.headings[0].text, "Overview");
+        let cells: Vec<&str> = doc
+            .blocks
+            .iter()
+            .filter(|b| b.kind == BlockKind::TableCell)
+            .map(|b| &text[b.span.0..b.span.1])
+            .collect();
+        assert_eq!(cells, vec!["Name", "Notes", "cache", "Fast path."]);
+    }
+
+    #[test]
+    fn test_nested_lists_and_bold_leads() {
+        let text = "- **One:** a\n  - [ ] nested task\n  - 🚀 launch\n- Two\n";
+        let doc = MarkdownDoc::parse(text);
+        assert_eq!(doc.lists.len(), 2);
+        assert_eq!(doc.lists[0].items.len(), 2);
+        assert_eq!(doc.lists[1].depth, 1);
+        assert!(doc.lists[0].items[0].bold_lead);
+        assert!(!doc.lists[0].items[1].bold_lead);
+        assert_eq!(doc.lists[1].items[0].lead_char, Some('n'));
+        assert_eq!(doc.lists[1].items[1].lead_char, Some('🚀'));
+        let item_text: Vec<&str> = doc
+            .blocks
+            .iter()
+            .filter(|b| b.kind == BlockKind::ListItem)
+            .map(|b| &text[b.span.0..b.span.1])
+            .collect();
+        assert_eq!(
+            item_text,
+            vec!["**One:** a", "nested task", "🚀 launch", "Two"]
+        );
+    }
+
+    #[test]
+    fn test_non_prose_regions() {
+        let text = "Intro with [a link](docs/deep-dive.md).\n\n    indented code\n\n<div>\nraw html\n</div>\n\n[ref]: https://example.com\n";
+        let doc = MarkdownDoc::parse(text);
+        assert_eq!(doc.code_blocks.len(), 1);
+        assert_eq!(doc.html_blocks.len(), 1);
+        assert_eq!(doc.reference_definitions.len(), 1);
+        let (start, end) = doc.link_destinations[0];
+        assert_eq!(&text[start..end], "(docs/deep-dive.md)");
+        assert_eq!(doc.blocks.len(), 1);
+    }
+}
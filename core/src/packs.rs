@@ -0,0 +1,382 @@
+//! Built-in rule packs and config layering.
+//!
+//! A config can pull in named packs with `extends: [toneguard:core, ...]`.
+//! The phrase lists that packs supply then start out empty, each pack is
+//! layered on in order, and the config file goes on top. Nested mappings
+//! merge key by key; anything else in a later layer replaces the earlier
+//! value.
+//!
+//! Any list may instead be written as `{add: [...], remove: [...]}` to edit
+//! the list it inherits: from earlier layers with `extends`, or from the
+//! built-in defaults without it. Removal compares strings case-insensitively
+//! and matches list entries with a `name` or `id` by that key. Added entries
+//! already in the list are skipped.
+//!
+//! `toneguard:core` is the default phrase lists from [`Config::default`];
+//! the other packs live in `core/packs/*.yml`.
+
+use anyhow::{anyhow, bail, Context};
+use serde_yaml::{Mapping, Value};
+
+use crate::Config;
+
+/// Prefix naming a built-in pack in `extends`.
+pub const PACK_PREFIX: &str = "toneguard:";
+
+/// Lists that rule packs supply, as key paths into the config.
+const PACK_LISTS: &[&[&str]] = &[
+    &["buzzwords", "throttle"],
+    &["transitions", "throttle"],
+    &["puffery", "ban"],
+    &["templates", "ban"],
+    &["weasel", "ban"],
+    &["marketing_cliches", "ban"],
+    &["profile_defaults", "call_to_action_phrases"],
+    &["profile_defaults", "cadence_starts"],
+    &["profile_defaults", "broad_terms"],
+    &["profile_defaults", "confidence_phrases"],
+];
+
+pub struct RulePack {
+    pub name: &'static str,
+    pub summary: &'static str,
+    /// Pack YAML; `None` for `core`, which is built from the defaults.
+    source: Option<&'static str>,
+}
+
+pub const PACKS: &[RulePack] = &[
+    RulePack {
+        name: "core",
+        summary:
+            "Default buzzwords, transitions, puffery, templates, weasel words and marketing cliches",
+        source: None,
+    },
+    RulePack {
+        name: "marketing",
+        summary: "Product pages, launch posts and release announcements",
+        source: Some(include_str!("../packs/marketing.yml")),
+    },
+    RulePack {
+        name: "academic",
+        summary: "Papers, reports and literature reviews",
+        source: Some(include_str!("../packs/academic.yml")),
+    },
+    RulePack {
+        name: "api-reference",
+        summary: "API and CLI reference pages",
+        source: Some(include_str!("../packs/api-reference.yml")),
+    },
+    RulePack {
+        name: "changelog",
+        summary: "Changelogs and release notes",
+        source: Some(include_str!("../packs/changelog.yml")),
+    },
+];
+
+impl RulePack {
+    fn value(&self, defaults: &Value) -> anyhow::Result<Value> {
+        match self.source {
+            Some(source) => serde_yaml::from_str(source)
+                .with_context(|| format!("built-in pack `{PACK_PREFIX}{}`", self.name)),
+            None => {
+                let mut pack = Value::Mapping(Mapping::new());
+                for path in PACK_LISTS {
+                    let list = lookup(defaults, path).cloned().unwrap_or(Value::Null);
+                    let mut ops = Mapping::new();
+                    ops.insert("add".into(), list);
+                    *slot(&mut pack, path) = Value::Mapping(ops);
+                }
+                Ok(pack)
+            }
+        }
+    }
+}
+
+fn find_pack(entry: &str) -> anyhow::Result<&'static RulePack> {
+    let available = || {
+        PACKS
+            .iter()
+            .map(|pack| format!("{PACK_PREFIX}{}", pack.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let name = entry.strip_prefix(PACK_PREFIX).ok_or_else(|| {
+        anyhow!(
+            "`extends` entry `{entry}` is not a built-in pack (available: {})",
+            available()
+        )
+    })?;
+    PACKS
+        .iter()
+        .find(|pack| pack.name == name)
+        .ok_or_else(|| anyhow!("unknown rule pack `{entry}` (available: {})", available()))
+}
+
+/// Apply `extends` and list operators in a parsed config file, giving YAML
+/// that deserializes into [`Config`].
+pub fn resolve(value: Value) -> anyhow::Result<Value> {
+    let Value::Mapping(mut root) = value else {
+        return Ok(value);
+    };
+    let extends = take_extends(&mut root)?;
+    let defaults = serde_yaml::to_value(Config::default())?;
+
+    if extends.is_empty() {
+        let mut root = Value::Mapping(root);
+        resolve_operators(&mut root, Some(&defaults), "")?;
+        return Ok(root);
+    }
+
+    let mut merged = defaults.clone();
+    for path in PACK_LISTS {
+        *slot(&mut merged, path) = Value::Sequence(Vec::new());
+    }
+    for entry in &extends {
+        let pack = find_pack(entry)?.value(&defaults)?;
+        merge(&mut merged, pack, "").with_context(|| format!("in pack `{entry}`"))?;
+    }
+    merge(&mut merged, Value::Mapping(root), "")?;
+    Ok(merged)
+}
+
+/// Layer a config file over an already resolved config, as for a
+/// `.toneguard.yml` below the root. Packs in its `extends` go on first, then
+/// the file itself; lists keep what they inherit from `base` unless replaced.
+pub fn layer(base: &mut Value, overlay: Value) -> anyhow::Result<()> {
+    let mut overlay = match overlay {
+        Value::Mapping(overlay) => overlay,
+        Value::Null => return Ok(()),
+        _ => bail!("config must be a mapping"),
+    };
+    let extends = take_extends(&mut overlay)?;
+    if !extends.is_empty() {
+        let defaults = serde_yaml::to_value(Config::default())?;
+        for entry in &extends {
+            let pack = find_pack(entry)?.value(&defaults)?;
+            merge(base, pack, "").with_context(|| format!("in pack `{entry}`"))?;
+        }
+    }
+    merge(base, Value::Mapping(overlay), "")
+}
+
+fn take_extends(root: &mut Mapping) -> anyhow::Result<Vec<String>> {
+    match root.remove("extends") {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::String(entry)) => Ok(vec![entry]),
+        Some(Value::Sequence(entries)) => entries
+            .into_iter()
+            .map(|entry| match entry {
+                Value::String(entry) => Ok(entry),
+                other => Err(anyhow!("`extends` entries must be strings, got {other:?}")),
+            })
+            .collect(),
+        Some(other) => bail!("`extends` must be a pack name or a list, got {other:?}"),
+    }
+}
+
+/// Layer `overlay` onto `base`.
+fn merge(base: &mut Value, overlay: Value, path: &str) -> anyhow::Result<()> {
+    match overlay {
+        Value::Mapping(ops) if is_operators(&ops) => {
+            let inherited = match std::mem::take(base) {
+                Value::Sequence(items) => items,
+                Value::Null => Vec::new(),
+                _ => bail!("`{path}` is not a list, so it cannot take `add`/`remove`"),
+            };
+            *base = Value::Sequence(apply_operators(inherited, ops, path)?);
+        }
+        Value::Mapping(overlay) => {
+            if !base.is_mapping() {
+                *base = Value::Mapping(Mapping::new());
+            }
+            let Value::Mapping(base) = base else {
+                unreachable!("base was just made a mapping");
+            };
+            for (key, value) in overlay {
+                let child = child_path(path, &key);
+                let slot = base.entry(key).or_insert(Value::Null);
+                merge(slot, value, &child)?;
+            }
+        }
+        mut other => {
+            resolve_operators(&mut other, None, path)?;
+            *base = other;
+        }
+    }
+    Ok(())
+}
+
+/// Replace operator mappings in `node` with the lists they produce, starting
+/// from the matching list in `inherited`.
+fn resolve_operators(
+    node: &mut Value,
+    inherited: Option<&Value>,
+    path: &str,
+) -> anyhow::Result<()> {
+    match node {
+        Value::Mapping(map) if is_operators(map) => {
+            let base = match inherited {
+                Some(Value::Sequence(items)) => items.clone(),
+                _ => Vec::new(),
+            };
+            let ops = std::mem::take(map);
+            *node = Value::Sequence(apply_operators(base, ops, path)?);
+        }
+        Value::Mapping(map) => {
+            for (key, value) in map.iter_mut() {
+                let child = child_path(path, key);
+                resolve_operators(value, inherited.and_then(|base| base.get(key)), &child)?;
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                resolve_operators(item, None, path)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn is_operators(map: &Mapping) -> bool {
+    !map.is_empty()
+        && map
+            .keys()
+            .all(|key| matches!(key.as_str(), Some("add" | "remove")))
+}
+
+fn apply_operators(mut items: Vec<Value>, ops: Mapping, path: &str) -> anyhow::Result<Vec<Value>> {
+    let entries = |op: &str| -> anyhow::Result<Vec<Value>> {
+        match ops.get(op) {
+            None | Some(Value::Null) => Ok(Vec::new()),
+            Some(Value::Sequence(entries)) => Ok(entries.clone()),
+            Some(Value::Mapping(_)) => bail!("`{path}.{op}` must be a list"),
+            Some(single) => Ok(vec![single.clone()]),
+        }
+    };
+    let remove = entries("remove")?;
+    items.retain(|item| !remove.iter().any(|entry| same_entry(item, entry)));
+    for entry in entries("add")? {
+        if !items.iter().any(|item| same_entry(item, &entry)) {
+            items.push(entry);
+        }
+    }
+    Ok(items)
+}
+
+fn same_entry(item: &Value, entry: &Value) -> bool {
+    match (item, entry) {
+        (Value::String(item), Value::String(entry)) => item.to_lowercase() == entry.to_lowercase(),
+        (Value::Mapping(item), Value::String(entry)) => ["name", "id"]
+            .iter()
+            .any(|key| item.get(*key).and_then(Value::as_str) == Some(entry.as_str())),
+        _ => item == entry,
+    }
+}
+
+fn lookup<'a>(value: &'a Value, path: &[&str]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |node, key| node.get(*key))
+}
+
+/// The value at `path`, creating mappings along the way.
+fn slot<'a>(value: &'a mut Value, path: &[&str]) -> &'a mut Value {
+    let mut node = value;
+    for key in path {
+        if !node.is_mapping() {
+            *node = Value::Mapping(Mapping::new());
+        }
+        let Value::Mapping(map) = node else {
+            unreachable!("node was just made a mapping");
+        };
+        node = map.entry((*key).into()).or_insert(Value::Null);
+    }
+    node
+}
+
+fn child_path(path: &str, key: &Value) -> String {
+    let key = key.as_str().unwrap_or("?");
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(yaml: &str) -> anyhow::Result<Config> {
+        let value = resolve(serde_yaml::from_str(yaml)?)?;
+        Ok(serde_yaml::from_value(value)?)
+    }
+
+    #[test]
+    fn test_core_pack_matches_defaults() {
+        let defaults = Config::default();
+        let cfg = resolved("extends: toneguard:core\n").unwrap();
+        assert_eq!(cfg.buzzwords.throttle, defaults.buzzwords.throttle);
+        assert_eq!(cfg.templates.ban, defaults.templates.ban);
+        assert_eq!(
+            cfg.profile_defaults.broad_terms,
+            defaults.profile_defaults.broad_terms
+        );
+    }
+
+    #[test]
+    fn test_packs_layer_in_order_with_operators() {
+        let cfg = resolved(
+            "extends: [toneguard:api-reference, toneguard:changelog]\n\
+             buzzwords:\n  throttle:\n    remove: [Simply]\n    add: [bespoke, improved]\n\
+             profile_defaults:\n  max_headings: 4\n",
+        )
+        .unwrap();
+        let throttle = &cfg.buzzwords.throttle;
+        assert!(throttle.contains(&"easily".to_string()));
+        assert!(!throttle.contains(&"simply".to_string()));
+        assert!(!throttle.contains(&"leverage".to_string()));
+        assert_eq!(throttle.last().map(String::as_str), Some("bespoke"));
+        assert_eq!(throttle.iter().filter(|w| *w == "improved").count(), 1);
+        assert!(cfg.puffery.ban.is_empty());
+        assert_eq!(cfg.profile_defaults.max_headings, Some(4));
+        assert_eq!(cfg.profile_defaults.max_heading_depth, Some(3));
+    }
+
+    #[test]
+    fn test_every_pack_compiles() {
+        for pack in PACKS {
+            let cfg = resolved(&format!("extends: {PACK_PREFIX}{}\n", pack.name)).unwrap();
+            crate::Analyzer::new(cfg).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_operators_without_extends_edit_defaults() {
+        let cfg = resolved("buzzwords:\n  throttle:\n    remove: [leverage]\n").unwrap();
+        let defaults = Config::default();
+        assert_eq!(
+            cfg.buzzwords.throttle.len() + 1,
+            defaults.buzzwords.throttle.len()
+        );
+        assert!(resolved("extends: [toneguard:nope]\n").is_err());
+        assert!(resolved("extends: [./other.yml]\n").is_err());
+        assert!(resolved("extends: [toneguard:core]\nlimits:\n  add: [x]\n").is_err());
+    }
+
+    #[test]
+    fn test_layer_edits_inherited_lists() {
+        let mut base = serde_yaml::to_value(Config::default()).unwrap();
+        let overlay = serde_yaml::from_str(
+            "extends: toneguard:changelog\n\
+             buzzwords:\n  throttle:\n    add: [synergy]\n    remove: [leverage]\n",
+        )
+        .unwrap();
+        layer(&mut base, overlay).unwrap();
+        let cfg: Config = serde_yaml::from_value(base).unwrap();
+        let throttle = &cfg.buzzwords.throttle;
+        assert!(throttle.contains(&"synergy".to_string()));
+        assert!(throttle.contains(&"robust".to_string()));
+        assert!(!throttle.contains(&"leverage".to_string()));
+        assert_eq!(cfg.puffery.ban, Config::default().puffery.ban);
+    }
+}
@@ -0,0 +1,475 @@
+//! Phrase matching for the configured word lists.
+//!
+//! Text is folded before matching: NFKC, Unicode lowercasing (plus the
+//! `ß`/`ς` folds lowercasing misses), hyphen and quote variants mapped to
+//! ASCII, and invisible joiners such as soft hyphens dropped. Folding keeps a
+//! map from every folded byte back to the grapheme it came from, so matches
+//! report byte spans in the original text.
+//!
+//! The folded text is split into words, and phrases match word by word with
+//! the separators between them compared after collapsing whitespace. How a
+//! word in the text may differ from the configured word is set by
+//! [`Inflections`]: `stem` compares Snowball stems so one `throttle` entry
+//! covers "leverage", "leveraged" and "leveraging"; `suffixes` keeps the
+//! older rule of a fixed suffix list on the last word; `none` needs the exact
+//! word. A shared stem alone is not enough: the text word must also be the
+//! listed word plus an inflectional ending of the language, so `learnings`
+//! does not match "learns", `pivotal` does not match "pivoting" and `robust`
+//! does not match "robustness". Stems come from the Snowball stemmer for the
+//! document's [`Language`].
+
+use std::collections::HashMap;
+
+use rust_stemmers::Stemmer;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::locale::Language;
+
+/// Suffixes accepted on the last word in `suffixes` mode.
+const ALLOWED_SUFFIXES: [&str; 6] = ["s", "es", "ed", "ing", "ly", "d"];
+
+/// How word forms in the text may differ from a configured phrase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Inflections {
+    /// Words must match exactly (after folding).
+    None,
+    /// The last word may carry one of `s`, `es`, `ed`, `ing`, `ly`, `d`.
+    Suffixes,
+    /// Words match when their Snowball stems match and the text word is the
+    /// listed one plus an inflectional ending (`s`, `ed`, `ing`, … in English).
+    #[default]
+    Stem,
+}
+
+/// Normalisation and inflection settings for phrase lists.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct MatchingConfig {
+    /// Fold case, compatibility forms, dashes and quotes before matching.
+    /// When off, only ASCII case is ignored.
+    pub normalize: bool,
+    pub inflections: Inflections,
+}
+
+impl Default for MatchingConfig {
+    fn default() -> Self {
+        Self {
+            normalize: true,
+            inflections: Inflections::Stem,
+        }
+    }
+}
+
+/// `text` folded the way phrase matching sees it.
+pub fn fold(text: &str, options: &MatchingConfig) -> String {
+    let mut out = String::with_capacity(text.len());
+    for grapheme in text.graphemes(true) {
+        fold_grapheme(grapheme, options, &mut out);
+    }
+    out
+}
+
+fn fold_grapheme(grapheme: &str, options: &MatchingConfig, out: &mut String) {
+    if !options.normalize {
+        out.extend(grapheme.chars().map(|ch| ch.to_ascii_lowercase()));
+        return;
+    }
+    for ch in grapheme.nfkc() {
+        match ch {
+            '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2043}' | '\u{2212}' => out.push('-'),
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' | '\u{02BC}' => {
+                out.push('\'')
+            }
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => out.push('"'),
+            '\u{00AD}' | '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' => {}
+            'ß' | 'ẞ' => out.push_str("ss"),
+            'ς' => out.push('σ'),
+            _ => out.extend(ch.to_lowercase()),
+        }
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || is_combining_mark(ch)
+}
+
+/// Separator characters that glue two words into one term (`cutting-edge`,
+/// `leverage's`); a phrase never starts or ends against one.
+fn is_joiner(ch: char) -> bool {
+    matches!(ch, '-' | '\'')
+}
+
+#[derive(Debug, Clone)]
+struct Word {
+    /// Byte span in the original text.
+    start: usize,
+    end: usize,
+    /// Folded word.
+    text: String,
+    /// Comparison key: the stem in `stem` mode, the folded word otherwise.
+    key: String,
+    /// Folded separator before the word, whitespace runs collapsed.
+    sep: String,
+}
+
+/// Words in `folded`, plus the separator text after the last one.
+fn split_words(
+    folded: &str,
+    origin: &[(usize, usize)],
+    options: &MatchingConfig,
+    language: Language,
+) -> (Vec<Word>, String) {
+    let stemmer = Stemmer::create(language.stemmer());
+    let mut words = Vec::new();
+    let mut sep = String::new();
+    let mut word_start: Option<usize> = None;
+    let finish = |start: usize, end: usize, sep: &mut String, words: &mut Vec<Word>| {
+        let text = folded[start..end].to_string();
+        let key = match options.inflections {
+            Inflections::Stem => stemmer.stem(&text).into_owned(),
+            Inflections::None | Inflections::Suffixes => text.clone(),
+        };
+        words.push(Word {
+            start: origin[start].0,
+            end: origin[end - 1].1,
+            text,
+            key,
+            sep: std::mem::take(sep),
+        });
+    };
+    for (idx, ch) in folded.char_indices() {
+        if is_word_char(ch) {
+            word_start.get_or_insert(idx);
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            finish(start, idx, &mut sep, &mut words);
+        }
+        if ch.is_whitespace() {
+            if !sep.ends_with(' ') {
+                sep.push(' ');
+            }
+        } else {
+            sep.push(ch);
+        }
+    }
+    if let Some(start) = word_start {
+        finish(start, folded.len(), &mut sep, &mut words);
+    }
+    (words, sep)
+}
+
+/// A document split into folded words, built once and shared by every
+/// matcher. Matchers must use the same [`MatchingConfig`].
+pub struct Words<'a> {
+    source: &'a str,
+    language: Language,
+    words: Vec<Word>,
+    /// Folded text after the last word.
+    tail: String,
+}
+
+impl<'a> Words<'a> {
+    pub fn new(source: &'a str, options: &MatchingConfig, language: Language) -> Self {
+        let mut folded = String::with_capacity(source.len());
+        let mut origin = Vec::with_capacity(source.len());
+        for (start, grapheme) in source.grapheme_indices(true) {
+            let before = folded.len();
+            fold_grapheme(grapheme, options, &mut folded);
+            let span = (start, start + grapheme.len());
+            origin.resize(origin.len() + folded.len() - before, span);
+        }
+        let (words, tail) = split_words(&folded, &origin, options, language);
+        Self {
+            source,
+            language,
+            words,
+            tail,
+        }
+    }
+
+    /// The original text.
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Language the words were stemmed for.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Separator following word `idx`.
+    fn sep_after(&self, idx: usize) -> &str {
+        match self.words.get(idx + 1) {
+            Some(next) => &next.sep,
+            None => &self.tail,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Pattern {
+    phrase: usize,
+    words: Vec<String>,
+    keys: Vec<String>,
+    seps: Vec<String>,
+}
+
+/// A phrase found in the text, with its byte span in the original and the
+/// index of the configured phrase it matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhraseMatch {
+    pub start: usize,
+    pub end: usize,
+    pub phrase: usize,
+}
+
+/// Word-level matcher for a phrase list.
+#[derive(Debug, Clone)]
+pub struct PhraseMatcher {
+    phrases: Vec<String>,
+    patterns: Vec<Pattern>,
+    by_first_key: HashMap<String, Vec<usize>>,
+    inflections: Inflections,
+    language: Language,
+}
+
+impl PhraseMatcher {
+    /// Compile `phrases` for text in `language`; `None` when no entry
+    /// contains a word.
+    pub fn new(phrases: &[String], options: &MatchingConfig, language: Language) -> Option<Self> {
+        let mut patterns = Vec::new();
+        let mut by_first_key: HashMap<String, Vec<usize>> = HashMap::new();
+        for (idx, phrase) in phrases.iter().enumerate() {
+            let parsed = Words::new(phrase, options, language);
+            let Some(first) = parsed.words.first() else {
+                continue;
+            };
+            by_first_key
+                .entry(first.key.clone())
+                .or_default()
+                .push(patterns.len());
+            patterns.push(Pattern {
+                phrase: idx,
+                words: parsed.words.iter().map(|w| w.text.clone()).collect(),
+                keys: parsed.words.iter().map(|w| w.key.clone()).collect(),
+                seps: parsed.words.iter().skip(1).map(|w| w.sep.clone()).collect(),
+            });
+        }
+        if patterns.is_empty() {
+            return None;
+        }
+        Some(Self {
+            phrases: phrases.to_vec(),
+            patterns,
+            by_first_key,
+            inflections: options.inflections,
+            language,
+        })
+    }
+
+    /// The configured phrase at `idx`.
+    pub fn phrase(&self, idx: usize) -> &str {
+        &self.phrases[idx]
+    }
+
+    /// Non-overlapping matches, left to right. At each position the longest
+    /// phrase wins, then the one listed first.
+    pub fn find_iter(&self, words: &Words) -> Vec<PhraseMatch> {
+        // Words stemmed for another language are re-stemmed with ours.
+        let restemmed: Option<Vec<String>> =
+            (self.inflections == Inflections::Stem && words.language != self.language).then(|| {
+                let stemmer = Stemmer::create(self.language.stemmer());
+                words
+                    .words
+                    .iter()
+                    .map(|word| stemmer.stem(&word.text).into_owned())
+                    .collect()
+            });
+        let keys: Vec<&str> = match &restemmed {
+            Some(keys) => keys.iter().map(String::as_str).collect(),
+            None => words.words.iter().map(|word| word.key.as_str()).collect(),
+        };
+        let mut matches = Vec::new();
+        let mut idx = 0;
+        while idx < words.words.len() {
+            let mut best: Option<&Pattern> = None;
+            for key in self.lookup_keys(&words.words[idx], keys[idx]) {
+                for &pattern_idx in self.by_first_key.get(key).into_iter().flatten() {
+                    let pattern = &self.patterns[pattern_idx];
+                    if !self.matches_at(pattern, words, &keys, idx) {
+                        continue;
+                    }
+                    let better = best.is_none_or(|current| {
+                        (pattern.words.len(), std::cmp::Reverse(pattern.phrase))
+                            > (current.words.len(), std::cmp::Reverse(current.phrase))
+                    });
+                    if better {
+                        best = Some(pattern);
+                    }
+                }
+            }
+            match best {
+                Some(pattern) => {
+                    let last = idx + pattern.words.len() - 1;
+                    matches.push(PhraseMatch {
+                        start: words.words[idx].start,
+                        end: words.words[last].end,
+                        phrase: pattern.phrase,
+                    });
+                    idx = last + 1;
+                }
+                None => idx += 1,
+            }
+        }
+        matches
+    }
+
+    /// Keys under which a pattern starting at `word` may be indexed.
+    fn lookup_keys<'w>(&self, word: &'w Word, key: &'w str) -> Vec<&'w str> {
+        let mut keys = vec![key];
+        if self.inflections == Inflections::Suffixes {
+            for suffix in ALLOWED_SUFFIXES {
+                if let Some(stripped) = word.text.strip_suffix(suffix) {
+                    if !stripped.is_empty() {
+                        keys.push(stripped);
+                    }
+                }
+            }
+        }
+        keys
+    }
+
+    fn matches_at(&self, pattern: &Pattern, words: &Words, keys: &[&str], idx: usize) -> bool {
+        let count = pattern.words.len();
+        let Some(candidate) = words.words.get(idx..idx + count) else {
+            return false;
+        };
+        if candidate[0].sep.ends_with(is_joiner)
+            || words.sep_after(idx + count - 1).starts_with(is_joiner)
+        {
+            return false;
+        }
+        candidate.iter().enumerate().all(|(pos, word)| {
+            if pos > 0 && word.sep != pattern.seps[pos - 1] {
+                return false;
+            }
+            match self.inflections {
+                Inflections::None => word.text == pattern.words[pos],
+                Inflections::Stem => {
+                    keys[idx + pos] == pattern.keys[pos]
+                        && self
+                            .language
+                            .pack()
+                            .is_inflection(&word.text, &pattern.words[pos])
+                }
+                Inflections::Suffixes => {
+                    word.text == pattern.words[pos]
+                        || (pos + 1 == count
+                            && word
+                                .text
+                                .strip_prefix(pattern.words[pos].as_str())
+                                .is_some_and(|rest| ALLOWED_SUFFIXES.contains(&rest)))
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(phrases: &[&str], text: &str, options: &MatchingConfig) -> Vec<String> {
+        let phrases: Vec<String> = phrases.iter().map(|p| p.to_string()).collect();
+        let matcher = PhraseMatcher::new(&phrases, options, Language::En).unwrap();
+        let words = Words::new(text, options, Language::En);
+        matcher
+            .find_iter(&words)
+            .iter()
+            .map(|m| text[m.start..m.end].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_stems_match_all_word_forms() {
+        let options = MatchingConfig::default();
+        assert_eq!(
+            spans(
+                &["leverage"],
+                "Leveraged data, leveraging tools, leverages.",
+                &options
+            ),
+            vec!["Leveraged", "leveraging", "leverages"]
+        );
+    }
+
+    #[test]
+    fn test_unicode_case_dashes_and_quotes_fold() {
+        let options = MatchingConfig::default();
+        assert_eq!(
+            spans(&["résumé"], "Send your RÉSUMÉ today.", &options),
+            vec!["RÉSUMÉ"]
+        );
+        assert_eq!(
+            spans(&["cutting-edge"], "A cutting\u{2011}edge tool.", &options),
+            vec!["cutting\u{2011}edge"]
+        );
+        assert_eq!(
+            spans(&["don't miss out"], "Don\u{2019}t  miss out!", &options),
+            vec!["Don\u{2019}t  miss out"]
+        );
+    }
+
+    #[test]
+    fn test_joined_words_are_not_split() {
+        let options = MatchingConfig::default();
+        assert!(spans(&["edge"], "a cutting-edge tool", &options).is_empty());
+        assert!(spans(&["robust"], "the robust's case", &options).is_empty());
+        assert!(spans(&["learnings"], "The team learns fast.", &options).is_empty());
+    }
+
+    #[test]
+    fn test_shared_stems_need_an_inflectional_ending() {
+        let options = MatchingConfig::default();
+        assert!(spans(&["pivotal"], "The team is pivoting the table.", &options).is_empty());
+        assert!(spans(&["robust"], "We measured the robustness.", &options).is_empty());
+        assert_eq!(
+            spans(&["robust"], "Robustly built, more robust.", &options),
+            vec!["Robustly", "robust"]
+        );
+        assert_eq!(
+            spans(
+                &["ship", "identify"],
+                "We shipped it and identified two.",
+                &options
+            ),
+            vec!["shipped", "identified"]
+        );
+    }
+
+    #[test]
+    fn test_suffix_and_exact_modes() {
+        let suffixes = MatchingConfig {
+            normalize: true,
+            inflections: Inflections::Suffixes,
+        };
+        assert_eq!(
+            spans(&["leverage"], "leveraged, leveraging", &suffixes),
+            vec!["leveraged"]
+        );
+        let exact = MatchingConfig {
+            normalize: false,
+            inflections: Inflections::None,
+        };
+        assert_eq!(
+            spans(&["leverage"], "Leverage, leveraged, LEVERAGE", &exact),
+            vec!["Leverage", "LEVERAGE"]
+        );
+        assert!(spans(&["résumé"], "RÉSUMÉ", &exact).is_empty());
+    }
+}
@@ -0,0 +1,411 @@
+//! Stable rule identifiers.
+//!
+//! Every diagnostic carries a rule ID of the form `<category>/<name>`, e.g.
+//! `structure/max-bullet-items`. IDs do not change between releases, so they
+//! can be used anywhere a category name is accepted:
+//! - CLI filters (`--only`, `--enable`, `--disable`)
+//! - Inline ignores (`<!-- dwg:ignore tone/passive-density -->`)
+//! - `severity_overrides` in the config
+//! - The LSP category settings
+//!
+//! `Category` stays as the grouping used for counts and density scores.
+//! Rules declared under `custom_rules:` in the config report as
+//! `custom/<id>` and are not listed in [`RULES`].
+
+use crate::{parse_category, Category};
+
+/// Catalog entry describing one check.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleInfo {
+    pub id: &'static str,
+    pub category: Category,
+    pub summary: &'static str,
+}
+
+impl RuleInfo {
+    const fn new(id: &'static str, category: Category, summary: &'static str) -> Self {
+        Self {
+            id,
+            category,
+            summary,
+        }
+    }
+}
+
+/// Every rule the analyzer can emit, grouped by category.
+pub const RULES: &[RuleInfo] = &[
+    RuleInfo::new(
+        "puffery/phrase",
+        Category::Puffery,
+        "Puffery phrase from the ban list",
+    ),
+    RuleInfo::new("buzzword/phrase", Category::Buzzword, "Throttled buzzword"),
+    RuleInfo::new(
+        "negative-parallelism/phrase",
+        Category::NegativeParallel,
+        "\"Not X, but Y\" template phrasing",
+    ),
+    RuleInfo::new(
+        "rule-of-three/triplet",
+        Category::RuleOfThree,
+        "More rule-of-three lists per paragraph than allowed",
+    ),
+    RuleInfo::new(
+        "connector-glut/per-sentence",
+        Category::ConnectorGlut,
+        "Too many connectors in one sentence",
+    ),
+    RuleInfo::new(
+        "template/phrase",
+        Category::Template,
+        "Boilerplate template phrasing",
+    ),
+    RuleInfo::new(
+        "weasel/vague-attribution",
+        Category::Weasel,
+        "Vague attribution without a source",
+    ),
+    RuleInfo::new(
+        "weasel/exaggerated-range",
+        Category::Weasel,
+        "Exaggerated \"from X to Y\" range",
+    ),
+    RuleInfo::new(
+        "transition/filler",
+        Category::Transition,
+        "Throttled transitional filler",
+    ),
+    RuleInfo::new("marketing/cliche", Category::Marketing, "Marketing cliché"),
+    RuleInfo::new(
+        "structure/required-pattern",
+        Category::Structure,
+        "Required pattern missing from the document",
+    ),
+    RuleInfo::new(
+        "structure/forbidden-pattern",
+        Category::Structure,
+        "Forbidden pattern present in the document",
+    ),
+    RuleInfo::new(
+        "structure/max-bullet-items",
+        Category::Structure,
+        "List has more items than allowed",
+    ),
+    RuleInfo::new(
+        "structure/subject-length",
+        Category::Structure,
+        "Commit subject line longer than allowed",
+    ),
+    RuleInfo::new(
+        "structure/max-heading-depth",
+        Category::Structure,
+        "Heading nested deeper than allowed",
+    ),
+    RuleInfo::new(
+        "structure/rhetorical-heading",
+        Category::Structure,
+        "Heading phrased as a question",
+    ),
+    RuleInfo::new(
+        "structure/max-headings",
+        Category::Structure,
+        "Too many headings",
+    ),
+    RuleInfo::new(
+        "structure/required-heading",
+        Category::Structure,
+        "Required heading missing",
+    ),
+    RuleInfo::new(
+        "structure/banned-heading",
+        Category::Structure,
+        "Heading matches a banned pattern",
+    ),
+    RuleInfo::new(
+        "structure/min-code-blocks",
+        Category::Structure,
+        "Fewer code blocks than required",
+    ),
+    RuleInfo::new(
+        "structure/template-triad",
+        Category::Structure,
+        "Summary/conclusion/future development heading triad",
+    ),
+    RuleInfo::new(
+        "structure/thin-section",
+        Category::Structure,
+        "Section has fewer sentences than required",
+    ),
+    RuleInfo::new(
+        "call-to-action/phrase",
+        Category::CallToAction,
+        "Marketing call-to-action phrasing",
+    ),
+    RuleInfo::new(
+        "sentence-length/max-words",
+        Category::SentenceLength,
+        "Sentence longer than allowed",
+    ),
+    RuleInfo::new(
+        "repetition/duplicate-paragraph",
+        Category::Repetition,
+        "Paragraph repeated across linted files",
+    ),
+    RuleInfo::new(
+        "repetition/duplicate-sentence",
+        Category::Repetition,
+        "Sentence repeats earlier phrasing",
+    ),
+    RuleInfo::new(
+        "cadence/opening-streak",
+        Category::Cadence,
+        "Consecutive sentences open with the same word",
+    ),
+    RuleInfo::new(
+        "cadence/repetitive-openings",
+        Category::Cadence,
+        "One opening dominates the document",
+    ),
+    RuleInfo::new(
+        "confidence/unsupported-claim",
+        Category::Confidence,
+        "Confidence phrase without evidence",
+    ),
+    RuleInfo::new(
+        "confidence/numeric-claim",
+        Category::Confidence,
+        "Percentage claim without supporting context",
+    ),
+    RuleInfo::new(
+        "broad-term/unspecific",
+        Category::BroadTerm,
+        "Broad term in a sentence without specifics",
+    ),
+    RuleInfo::new(
+        "tone/question-lead",
+        Category::Tone,
+        "Document opens with a run of questions",
+    ),
+    RuleInfo::new(
+        "tone/mid-sentence-question",
+        Category::Tone,
+        "Question mark in the middle of a sentence",
+    ),
+    RuleInfo::new(
+        "tone/exclamation-density",
+        Category::Tone,
+        "Too many exclamation marks in a paragraph",
+    ),
+    RuleInfo::new(
+        "tone/uniform-sentence-length",
+        Category::Tone,
+        "Sentence lengths are suspiciously uniform",
+    ),
+    RuleInfo::new(
+        "tone/passive-density",
+        Category::Tone,
+        "Most sentences use passive voice",
+    ),
+    RuleInfo::new(
+        "em-dash/density",
+        Category::EmDash,
+        "Too many em dashes in a paragraph",
+    ),
+    RuleInfo::new(
+        "formatting/emoji-bullet",
+        Category::Formatting,
+        "List item opens with an emoji",
+    ),
+    RuleInfo::new(
+        "formatting/bold-lead-bullets",
+        Category::Formatting,
+        "List leans on bold lead-ins",
+    ),
+    RuleInfo::new(
+        "formatting/bold-spans",
+        Category::Formatting,
+        "Too many bold spans in a paragraph",
+    ),
+    RuleInfo::new(
+        "formatting/heading-emoji",
+        Category::Formatting,
+        "Emoji inside a heading",
+    ),
+    RuleInfo::new(
+        "formatting/heading-case",
+        Category::Formatting,
+        "Heading is not sentence case",
+    ),
+    RuleInfo::new(
+        "quote-style/curly-quotes",
+        Category::QuoteStyle,
+        "Curly quotation mark where straight quotes are required",
+    ),
+    RuleInfo::new(
+        "readability/grade-level",
+        Category::Readability,
+        "Document or section reads above the profile's grade level",
+    ),
+    RuleInfo::new(
+        "readability/reading-ease",
+        Category::Readability,
+        "Document or section reads below the profile's reading ease",
+    ),
+    RuleInfo::new(
+        "terminology/discouraged-term",
+        Category::Terminology,
+        "Glossary variant where the preferred term should be used",
+    ),
+    RuleInfo::new(
+        "terminology/inconsistent-term",
+        Category::Terminology,
+        "Document mixes interchangeable variants of one glossary term",
+    ),
+    RuleInfo::new(
+        "spelling/unknown-word",
+        Category::Spelling,
+        "Word missing from the dictionary and the configured word lists",
+    ),
+    RuleInfo::new(
+        "links/broken-link",
+        Category::Links,
+        "Relative link or image whose file does not exist",
+    ),
+    RuleInfo::new(
+        "links/broken-anchor",
+        Category::Links,
+        "Anchor that matches no heading of the linked document",
+    ),
+    RuleInfo::new(
+        "directive/invalid-frontmatter",
+        Category::Directive,
+        "`toneguard:` frontmatter key that does not check out against the config",
+    ),
+    RuleInfo::new(
+        "directive/unknown-name",
+        Category::Directive,
+        "`dwg:ignore` directive naming no category or rule",
+    ),
+    RuleInfo::new(
+        "directive/unterminated-ignore",
+        Category::Directive,
+        "`dwg:ignore` without a matching `dwg:end-ignore`",
+    ),
+    RuleInfo::new(
+        "directive/unused",
+        Category::Directive,
+        "Inline directive that suppressed nothing",
+    ),
+];
+
+/// Look up a rule by its ID (case-insensitive).
+pub fn rule_info(id: &str) -> Option<&'static RuleInfo> {
+    let id = id.trim();
+    RULES.iter().find(|rule| rule.id.eq_ignore_ascii_case(id))
+}
+
+/// A category or a single rule, as named in filters, ignores and overrides.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RuleSelector {
+    Category(Category),
+    Rule(String),
+}
+
+impl RuleSelector {
+    /// Parse a category name (`structure`) or rule ID (`structure/max-headings`).
+    ///
+    /// Any well-formed `custom/<id>` is accepted; whether such a rule is
+    /// declared is only known once the config has been loaded.
+    pub fn parse(name: &str) -> Option<Self> {
+        if let Some(id) = crate::custom::normalize_id(name) {
+            Some(RuleSelector::Rule(id))
+        } else if name.contains('/') {
+            rule_info(name).map(|rule| RuleSelector::Rule(rule.id.to_string()))
+        } else {
+            parse_category(name).map(RuleSelector::Category)
+        }
+    }
+
+    pub fn matches(&self, category: Category, rule: &str) -> bool {
+        match self {
+            RuleSelector::Category(cat) => *cat == category,
+            RuleSelector::Rule(id) => id == rule,
+        }
+    }
+
+    /// Rule selectors are more specific than category selectors.
+    pub fn is_rule(&self) -> bool {
+        matches!(self, RuleSelector::Rule(_))
+    }
+}
+
+impl std::fmt::Display for RuleSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleSelector::Category(cat) => write!(f, "{cat}"),
+            RuleSelector::Rule(id) => f.write_str(id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_ids_are_prefixed_by_category() {
+        let mut seen = std::collections::HashSet::new();
+        for rule in RULES {
+            let (prefix, name) = rule.id.split_once('/').expect("rule id has a slash");
+            assert_eq!(prefix, rule.category.to_string(), "{}", rule.id);
+            assert!(!name.is_empty());
+            assert!(seen.insert(rule.id), "duplicate rule id {}", rule.id);
+        }
+    }
+
+    /// Every `"<category>/<name>"` literal outside test modules, in the core,
+    /// the CLI and the LSP, names a catalog rule.
+    #[test]
+    fn test_emitted_rules_are_in_the_catalog() {
+        let literal = regex::Regex::new(r#""([a-z-]+)/([a-z0-9-]+)""#).unwrap();
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for dir in ["core/src", "cli/src", "lsp/src"] {
+            for entry in std::fs::read_dir(root.join(dir)).unwrap() {
+                let path = entry.unwrap().path();
+                if path.extension().is_none_or(|ext| ext != "rs") {
+                    continue;
+                }
+                let source = std::fs::read_to_string(&path).unwrap();
+                let code = source.split("#[cfg(test)]").next().unwrap_or_default();
+                for caps in literal.captures_iter(code) {
+                    if &caps[1] == "custom" || parse_category(&caps[1]).is_none() {
+                        continue;
+                    }
+                    let id = &caps[0][1..caps[0].len() - 1];
+                    assert!(
+                        rule_info(id).is_some(),
+                        "{} emits `{id}`, which is not in RULES",
+                        path.display()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_selector_parsing() {
+        assert_eq!(
+            RuleSelector::parse("Structure"),
+            Some(RuleSelector::Category(Category::Structure))
+        );
+        assert_eq!(
+            RuleSelector::parse("tone/passive-density"),
+            Some(RuleSelector::Rule("tone/passive-density".into()))
+        );
+        assert_eq!(RuleSelector::parse("tone/unknown"), None);
+        assert_eq!(
+            RuleSelector::parse("custom/No-Simply"),
+            Some(RuleSelector::Rule("custom/no-simply".into()))
+        );
+    }
+}
@@ -0,0 +1,614 @@
+//! SARIF 2.1.0 output for prose diagnostics, flow audit findings and
+//! organizer findings.
+//!
+//! Only the subset of the SARIF object model ToneGuard fills in is modelled.
+//! Each invocation produces one run; [`SarifRun`] collects rule metadata and
+//! results, then [`SarifRun::into_log`] wraps it in a log.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::arch::{FindingCategory, FindingSeverity, FixInstructions, FlowFinding};
+use crate::baseline::BaselineEntry;
+use crate::flow::{FlowSpecIssue, IssueSeverity};
+use crate::organize::{IssueKind, OrganizationFinding};
+use crate::rules::{rule_info, RULES};
+use crate::{Diagnostic, Severity};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/editnori/toneguard";
+/// Key under `partialFingerprints`; bump the suffix if the recipe changes.
+const FINGERPRINT_KEY: &str = "toneguard/v1";
+const SRCROOT: &str = "SRCROOT";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Run {
+    pub tool: Tool,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub original_uri_base_ids: BTreeMap<String, ArtifactLocation>,
+    pub column_kind: String,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Tool {
+    pub driver: ToolComponent,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolComponent {
+    pub name: String,
+    pub version: String,
+    pub information_uri: String,
+    pub rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingDescriptor {
+    pub id: String,
+    pub short_description: Message,
+    pub default_configuration: ReportingConfiguration,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportingConfiguration {
+    pub level: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Message {
+    pub text: String,
+}
+
+impl Message {
+    fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: String,
+    pub message: Message,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<Location>,
+    pub partial_fingerprints: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<Fix>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<Region>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactLocation {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri_base_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byte_offset: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byte_length: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fix {
+    pub description: Message,
+    pub artifact_changes: Vec<ArtifactChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactChange {
+    pub artifact_location: ArtifactLocation,
+    pub replacements: Vec<Replacement>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Replacement {
+    pub deleted_region: Region,
+    pub inserted_content: Message,
+}
+
+/// Builder for a single SARIF run.
+#[derive(Debug, Clone)]
+pub struct SarifRun {
+    root: Option<PathBuf>,
+    rules: Vec<ReportingDescriptor>,
+    rule_index: HashMap<String, usize>,
+    results: Vec<SarifResult>,
+}
+
+impl SarifRun {
+    /// Result paths are made relative to `root`, which becomes the
+    /// `SRCROOT` base URI.
+    pub fn new(root: Option<&Path>) -> Self {
+        Self {
+            root: root.map(Path::to_path_buf),
+            rules: Vec::new(),
+            rule_index: HashMap::new(),
+            results: Vec::new(),
+        }
+    }
+
+    /// Register every built-in prose rule so the log documents the full rule
+    /// set, not only the rules that fired.
+    pub fn with_prose_rules(mut self) -> Self {
+        for rule in RULES {
+            self.add_rule(
+                rule.id,
+                rule.summary,
+                "warning",
+                Some(&rule.category.to_string()),
+            );
+        }
+        self
+    }
+
+    /// Register a rule (once) and return its index.
+    pub fn add_rule(
+        &mut self,
+        id: &str,
+        description: &str,
+        level: &str,
+        category: Option<&str>,
+    ) -> usize {
+        if let Some(index) = self.rule_index.get(id) {
+            return *index;
+        }
+        let mut properties = BTreeMap::new();
+        if let Some(category) = category {
+            properties.insert("category".to_string(), Value::from(category));
+        }
+        self.rules.push(ReportingDescriptor {
+            id: id.to_string(),
+            short_description: Message::new(description),
+            default_configuration: ReportingConfiguration {
+                level: level.to_string(),
+            },
+            properties,
+        });
+        let index = self.rules.len() - 1;
+        self.rule_index.insert(id.to_string(), index);
+        index
+    }
+
+    /// Add a prose diagnostic for `path`, whose contents are `text`.
+    pub fn push_diagnostic(&mut self, path: &str, text: &str, diag: &Diagnostic) {
+        let description = rule_info(&diag.rule).map_or("Custom rule", |rule| rule.summary);
+        let category = diag.category.to_string();
+        let rule_index = self.add_rule(&diag.rule, description, "warning", Some(&category));
+        let uri = self.artifact(Path::new(path));
+        let region = Region {
+            start_line: Some(diag.location.line),
+            start_column: Some(diag.location.column),
+            byte_offset: Some(diag.span.0),
+            byte_length: Some(diag.span.1.saturating_sub(diag.span.0)),
+        };
+        let fixes = diag
+            .fix
+            .iter()
+            .map(|fix| Fix {
+                description: Message::new(format!("{} fix", fix.applicability)),
+                artifact_changes: vec![ArtifactChange {
+                    artifact_location: uri.clone(),
+                    replacements: fix
+                        .edits
+                        .iter()
+                        .map(|edit| Replacement {
+                            deleted_region: Region {
+                                byte_offset: Some(edit.span.0),
+                                byte_length: Some(edit.span.1.saturating_sub(edit.span.0)),
+                                ..Region::default()
+                            },
+                            inserted_content: Message::new(edit.replacement.clone()),
+                        })
+                        .collect(),
+                }],
+            })
+            .collect();
+        let mut properties = BTreeMap::new();
+        if let Some(suggestion) = &diag.suggestion {
+            properties.insert("suggestion".to_string(), Value::from(suggestion.clone()));
+        }
+        let entry = BaselineEntry::new(&uri.uri, text, diag);
+        self.results.push(SarifResult {
+            rule_id: diag.rule.clone(),
+            rule_index,
+            level: level_for(diag.severity).to_string(),
+            message: Message::new(diag.message.clone()),
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: uri,
+                    region: Some(region),
+                },
+            }],
+            partial_fingerprints: fingerprint(&[
+                &entry.path,
+                &entry.rule,
+                &entry.snippet,
+                &entry.context,
+            ]),
+            fixes,
+            properties,
+        });
+    }
+
+    /// Add a result without a dedicated conversion, e.g. a repo hygiene issue.
+    pub fn push_result(
+        &mut self,
+        rule_id: &str,
+        description: &str,
+        level: &str,
+        message: &str,
+        path: Option<&Path>,
+    ) {
+        let rule_index = self.add_rule(rule_id, description, level, None);
+        let locations = path
+            .map(|path| {
+                vec![Location {
+                    physical_location: PhysicalLocation {
+                        artifact_location: self.artifact(path),
+                        region: None,
+                    },
+                }]
+            })
+            .unwrap_or_default();
+        let path_text = path.map(|p| p.to_string_lossy().to_string());
+        self.results.push(SarifResult {
+            rule_id: rule_id.to_string(),
+            rule_index,
+            level: level.to_string(),
+            message: Message::new(message),
+            locations,
+            partial_fingerprints: fingerprint(&[
+                rule_id,
+                path_text.as_deref().unwrap_or(""),
+                message,
+            ]),
+            fixes: Vec::new(),
+            properties: BTreeMap::new(),
+        });
+    }
+
+    /// Add a flow spec validation issue for the spec at `path`.
+    pub fn push_flow_spec_issue(&mut self, path: &Path, issue: &FlowSpecIssue) {
+        let level = match issue.severity {
+            IssueSeverity::Error => "error",
+            IssueSeverity::Warning => "warning",
+        };
+        let message = match &issue.field {
+            Some(field) => format!("{field}: {}", issue.message),
+            None => issue.message.clone(),
+        };
+        self.push_result(
+            "flow-spec/invalid",
+            "Flow spec fails validation",
+            level,
+            &message,
+            Some(path),
+        );
+    }
+
+    /// Add a flow audit finding. Fix instructions describe an edit rather
+    /// than spell it out, so they are carried in the result properties.
+    pub fn push_flow_finding(&mut self, finding: &FlowFinding) {
+        let (id, description) = flow_rule(&finding.category);
+        let level = match finding.severity {
+            FindingSeverity::Error => "error",
+            FindingSeverity::Warning => "warning",
+            FindingSeverity::Info => "note",
+        };
+        let rule_index = self.add_rule(id, description, level, Some("flow"));
+        let region = finding.line.map(|line| Region {
+            start_line: Some(line as usize),
+            ..Region::default()
+        });
+        let mut properties = BTreeMap::new();
+        if let Some(symbol) = &finding.symbol {
+            properties.insert("symbol".to_string(), Value::from(symbol.clone()));
+        }
+        if !finding.evidence.is_empty() {
+            properties.insert(
+                "evidence".to_string(),
+                Value::from(finding.evidence.clone()),
+            );
+        }
+        if let Some(fix) = &finding.fix_instructions {
+            properties.insert("fixInstructions".to_string(), fix_instructions(fix));
+        }
+        let line = finding.line.map(|l| l.to_string()).unwrap_or_default();
+        self.results.push(SarifResult {
+            rule_id: id.to_string(),
+            rule_index,
+            level: level.to_string(),
+            message: Message::new(finding.message.clone()),
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: self.artifact(Path::new(&finding.path)),
+                    region,
+                },
+            }],
+            partial_fingerprints: fingerprint(&[
+                id,
+                &finding.path,
+                finding.symbol.as_deref().unwrap_or(&line),
+                &finding.message,
+            ]),
+            fixes: Vec::new(),
+            properties,
+        });
+    }
+
+    /// Add an organizer finding; the suggested action goes in the properties.
+    pub fn push_organization_finding(&mut self, finding: &OrganizationFinding) {
+        let (id, description) = organize_rule(&finding.issue);
+        let rule_index = self.add_rule(id, description, "note", Some("organize"));
+        let mut properties = BTreeMap::new();
+        if let Ok(action) = serde_json::to_value(&finding.suggested_action) {
+            properties.insert("suggestedAction".to_string(), action);
+        }
+        if let Some(target) = &finding.target_path {
+            properties.insert(
+                "targetPath".to_string(),
+                Value::from(target.to_string_lossy().to_string()),
+            );
+        }
+        let artifact = self.artifact(&finding.path);
+        self.results.push(SarifResult {
+            rule_id: id.to_string(),
+            rule_index,
+            level: "note".to_string(),
+            message: Message::new(finding.reason.clone()),
+            partial_fingerprints: fingerprint(&[id, &artifact.uri]),
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: artifact,
+                    region: None,
+                },
+            }],
+            fixes: Vec::new(),
+            properties,
+        });
+    }
+
+    pub fn into_log(self) -> SarifLog {
+        let mut original_uri_base_ids = BTreeMap::new();
+        if let Some(root) = &self.root {
+            let mut uri = format!("file://{}", encode_uri(&to_slash(root)));
+            if !uri.ends_with('/') {
+                uri.push('/');
+            }
+            if !uri.starts_with("file:///") {
+                uri = uri.replacen("file://", "file:///", 1);
+            }
+            original_uri_base_ids.insert(
+                SRCROOT.to_string(),
+                ArtifactLocation {
+                    uri,
+                    uri_base_id: None,
+                },
+            );
+        }
+        SarifLog {
+            schema: SCHEMA.to_string(),
+            version: "2.1.0".to_string(),
+            runs: vec![Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: "toneguard".to_string(),
+                        version: env!("CARGO_PKG_VERSION").to_string(),
+                        information_uri: INFORMATION_URI.to_string(),
+                        rules: self.rules,
+                    },
+                },
+                original_uri_base_ids,
+                column_kind: "unicodeCodePoints".to_string(),
+                results: self.results,
+            }],
+        }
+    }
+
+    /// Location of `path` relative to the run root when possible.
+    fn artifact(&self, path: &Path) -> ArtifactLocation {
+        let relative = match &self.root {
+            Some(root) if path.is_absolute() => path.strip_prefix(root).ok(),
+            Some(_) => Some(path),
+            None => None,
+        };
+        match relative {
+            Some(rel) => {
+                let rel = to_slash(rel);
+                ArtifactLocation {
+                    uri: encode_uri(rel.trim_start_matches("./")),
+                    uri_base_id: Some(SRCROOT.to_string()),
+                }
+            }
+            None => ArtifactLocation {
+                uri: encode_uri(&to_slash(path)),
+                uri_base_id: None,
+            },
+        }
+    }
+}
+
+/// SARIF level for a diagnostic severity.
+pub fn level_for(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Hint | Severity::Information => "note",
+    }
+}
+
+fn flow_rule(category: &FindingCategory) -> (&'static str, &'static str) {
+    match category {
+        FindingCategory::Placeholder => ("flow/placeholder", "Placeholder or stub code"),
+        FindingCategory::LonelyAbstraction => (
+            "flow/lonely-abstraction",
+            "Abstraction with a single implementation",
+        ),
+        FindingCategory::PassThrough => ("flow/pass-through", "Wrapper that only forwards"),
+        FindingCategory::Duplication => ("flow/duplication", "Duplicated logic"),
+        FindingCategory::ExitPath => ("flow/exit-path", "Exit path reached unexpectedly"),
+        FindingCategory::ErrorEscalation => {
+            ("flow/error-escalation", "Warning escalated to an error")
+        }
+        FindingCategory::DeadBranch => ("flow/dead-branch", "Unreachable code"),
+        FindingCategory::ValidationGap => ("flow/validation-gap", "Unchecked input"),
+    }
+}
+
+fn organize_rule(issue: &IssueKind) -> (&'static str, &'static str) {
+    match issue {
+        IssueKind::Misplaced => ("organize/misplaced", "File in the wrong directory"),
+        IssueKind::Legacy => ("organize/legacy", "Legacy or backup file"),
+        IssueKind::DataInSource => ("organize/data-in-source", "Large data file in source"),
+        IssueKind::UntrackedExperiment => (
+            "organize/untracked-experiment",
+            "Untracked experiment output",
+        ),
+        IssueKind::Duplicate => ("organize/duplicate", "Near-duplicate file"),
+    }
+}
+
+fn fix_instructions(fix: &FixInstructions) -> Value {
+    serde_json::to_value(fix).unwrap_or(Value::Null)
+}
+
+fn fingerprint(parts: &[&str]) -> BTreeMap<String, String> {
+    let digest = Sha256::digest(parts.join("\0").as_bytes());
+    let hex: String = digest
+        .iter()
+        .take(16)
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    BTreeMap::from([(FINGERPRINT_KEY.to_string(), hex)])
+}
+
+fn to_slash(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Percent-encode the characters that are not allowed in a URI path.
+fn encode_uri(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~:".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Analyzer, Config};
+
+    #[test]
+    fn test_diagnostics_become_results_with_fixes() {
+        let text = "We will leverage the system.";
+        let report = Analyzer::new(Config::default()).unwrap().analyze(text);
+        let mut run = SarifRun::new(Some(Path::new("/repo"))).with_prose_rules();
+        for diag in &report.diagnostics {
+            run.push_diagnostic("docs/my notes.md", text, diag);
+        }
+        let log = serde_json::to_value(run.into_log()).unwrap();
+        let run = &log["runs"][0];
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["originalUriBaseIds"]["SRCROOT"]["uri"], "file:///repo/");
+        let result = run["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| r["ruleId"] == "buzzword/phrase")
+            .unwrap();
+        let index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(
+            run["tool"]["driver"]["rules"][index]["id"],
+            "buzzword/phrase"
+        );
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "docs/my%20notes.md");
+        assert_eq!(location["region"]["startColumn"], 9);
+        let replacement = &result["fixes"][0]["artifactChanges"][0]["replacements"][0];
+        assert_eq!(replacement["insertedContent"]["text"], "use");
+        assert!(result["partialFingerprints"][FINGERPRINT_KEY].is_string());
+    }
+
+    #[test]
+    fn test_flow_findings_keep_fix_instructions() {
+        let finding = FlowFinding {
+            category: FindingCategory::PassThrough,
+            severity: FindingSeverity::Warning,
+            message: "Wrapper only forwards".into(),
+            path: "src/lib.rs".into(),
+            line: Some(12),
+            fix_instructions: Some(FixInstructions {
+                action: "inline".into(),
+                description: "Inline the wrapper".into(),
+                ..FixInstructions::default()
+            }),
+            ..FlowFinding::default()
+        };
+        let mut run = SarifRun::new(None);
+        run.push_flow_finding(&finding);
+        let log = serde_json::to_value(run.into_log()).unwrap();
+        let result = &log["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "flow/pass-through");
+        assert_eq!(result["level"], "warning");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startLine"],
+            12
+        );
+        assert_eq!(result["properties"]["fixInstructions"]["action"], "inline");
+    }
+}
@@ -0,0 +1,131 @@
+//! Heading-delimited sections.
+//!
+//! A document splits at every heading: a section runs from its heading to
+//! the next heading of any level, and text before the first heading forms an
+//! untitled leading section. Sections do not nest; `heading_path` records
+//! the headings that enclose each one instead. A document without headings
+//! is a single untitled section.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::markdown::MarkdownDoc;
+use crate::metrics::Metrics;
+use crate::{Category, Location};
+
+/// Per-section summary in a [`DocumentReport`](crate::DocumentReport).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Section {
+    /// Heading texts from the outermost enclosing heading down to this
+    /// section's own; empty for the leading section.
+    pub heading_path: Vec<String>,
+    /// Heading level, or 0 for the leading section.
+    pub level: usize,
+    /// Byte span from the heading to the start of the next section.
+    pub span: (usize, usize),
+    /// Position of the heading (or of the section start).
+    pub location: Location,
+    pub word_count: usize,
+    /// Diagnostics starting in the section per 100 of its words.
+    pub density_per_100_words: f32,
+    pub category_counts: BTreeMap<Category, usize>,
+    pub metrics: Metrics,
+}
+
+/// Where a section sits in the source.
+#[derive(Debug, Clone)]
+pub(crate) struct Bounds {
+    pub(crate) heading_path: Vec<String>,
+    pub(crate) level: usize,
+    pub(crate) span: (usize, usize),
+    /// First line of the heading; diagnostics about the section attach here.
+    pub(crate) heading: Option<(usize, usize)>,
+}
+
+/// Split `text` into sections at its headings.
+pub(crate) fn segment(text: &str, doc: &MarkdownDoc) -> Vec<Bounds> {
+    let headings: Vec<_> = doc
+        .headings
+        .iter()
+        .filter(|heading| !heading.text.is_empty())
+        .collect();
+    let mut sections = Vec::new();
+    let first = headings
+        .first()
+        .map_or(text.len(), |heading| heading.span.0);
+    let body_start = doc.frontmatter.map_or(0, |(_, end)| end.min(first));
+    let has_lead = text[body_start..first]
+        .split_whitespace()
+        .any(|word| word.chars().any(char::is_alphabetic));
+    if has_lead || headings.is_empty() {
+        sections.push(Bounds {
+            heading_path: Vec::new(),
+            level: 0,
+            span: (0, first),
+            heading: None,
+        });
+    }
+    let mut path: Vec<(usize, &str)> = Vec::new();
+    for (i, heading) in headings.iter().enumerate() {
+        while path
+            .last()
+            .is_some_and(|(level, _)| *level >= heading.level)
+        {
+            path.pop();
+        }
+        path.push((heading.level, &heading.text));
+        let (start, end) = heading.span;
+        let line_end = text[start..end]
+            .find('\n')
+            .map_or(end, |newline| start + newline);
+        sections.push(Bounds {
+            heading_path: path.iter().map(|(_, text)| text.to_string()).collect(),
+            level: heading.level,
+            span: (
+                start,
+                headings.get(i + 1).map_or(text.len(), |next| next.span.0),
+            ),
+            heading: Some((start, line_end)),
+        });
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_split_at_every_heading() {
+        let text = "---\ntitle: x\n---\n# Guide\n\nIntro.\n\n## Install\n\nRun it.\n\n### Linux\n\nApt.\n\n## Use\nGo.\n";
+        let doc = MarkdownDoc::parse(text);
+        let sections = segment(text, &doc);
+        let paths: Vec<Vec<String>> = sections.iter().map(|s| s.heading_path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                vec!["Guide"],
+                vec!["Guide", "Install"],
+                vec!["Guide", "Install", "Linux"],
+                vec!["Guide", "Use"],
+            ]
+        );
+        assert_eq!(sections[0].span.0, text.find("# Guide").unwrap());
+        assert_eq!(sections[1].span.1, text.find("### Linux").unwrap());
+        assert_eq!(sections[3].span.1, text.len());
+        let (start, end) = sections[1].heading.unwrap();
+        assert_eq!(&text[start..end], "## Install");
+
+        let lead = segment(
+            "Intro.\n\n# Title\n",
+            &MarkdownDoc::parse("Intro.\n\n# Title\n"),
+        );
+        assert_eq!(lead.len(), 2);
+        assert!(lead[0].heading.is_none());
+        assert_eq!(
+            segment("No headings.", &MarkdownDoc::parse("No headings.")).len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Offline spell checking for English prose.
+//!
+//! Words are looked up in a bundled list (the en_US Hunspell word list built
+//! from SCOWL, with inflected forms spelled out, plus technical terms common
+//! in software documentation), then in the config's own words:
+//! `spelling.words`, the files listed in `spelling.dictionaries` (one word per
+//! line, `#` comments) and `whitelist.allowed_typos`. Inflections of the
+//! config's words (`toneguard` → "toneguards") are accepted too, so they need
+//! no entries of their own.
+//!
+//! Only plain words are checked. Code, URLs and other regions that
+//! [`DisabledRanges`] masks are skipped, as are tokens that look like
+//! identifiers or paths (`CamelCase`, `snake_case`, digits, dots, slashes),
+//! acronyms, and capitalised words missing from every list, which are
+//! assumed to be names. Unknown words get up to `suggestions` corrections
+//! within two edits.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+use rust_stemmers::{Algorithm, Stemmer};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::locale::Language;
+use crate::markdown::BlockKind;
+use crate::{
+    byte_to_location, edit_distance, Applicability, Category, Diagnostic, DisabledRanges, Fix,
+    Severity,
+};
+
+/// Largest edit distance for a suggestion.
+const MAX_DISTANCE: usize = 2;
+
+/// The `spelling:` section.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SpellingConfig {
+    pub enabled: bool,
+    /// Word lists relative to the config root. Missing files are skipped.
+    pub dictionaries: Vec<String>,
+    /// Extra accepted words. The words from `dictionaries` are added here
+    /// when the config is loaded.
+    pub words: Vec<String>,
+    /// Shorter words are not checked.
+    pub min_length: usize,
+    /// Most corrections offered per word.
+    pub suggestions: usize,
+}
+
+impl Default for SpellingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dictionaries: vec![".toneguard/words.txt".into()],
+            words: Vec::new(),
+            min_length: 3,
+            suggestions: 3,
+        }
+    }
+}
+
+impl SpellingConfig {
+    /// Read `dictionaries` under `root` into `words`. Returns the contents
+    /// of the files read, so callers can fingerprint them.
+    pub fn load_dictionaries(&mut self, root: &Path) -> anyhow::Result<Vec<String>> {
+        let mut contents = Vec::new();
+        for name in &self.dictionaries {
+            let path = root.join(name);
+            if !path.is_file() {
+                continue;
+            }
+            let text = fs::read_to_string(&path)
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", path.display()))?;
+            self.words
+                .extend(parse_word_list(&text).map(str::to_string));
+            contents.push(text);
+        }
+        Ok(contents)
+    }
+}
+
+/// Entries of a word list: one per line, `#` starts a comment.
+fn parse_word_list(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|word| !word.is_empty())
+}
+
+/// The bundled English list, with words grouped by first letter and length.
+struct Dictionary {
+    words: HashSet<&'static str>,
+    by_initial: HashMap<char, BTreeMap<usize, Vec<&'static str>>>,
+}
+
+static ENGLISH: Lazy<Dictionary> = Lazy::new(|| {
+    let mut dictionary = Dictionary {
+        words: HashSet::new(),
+        by_initial: HashMap::new(),
+    };
+    for word in parse_word_list(include_str!("../dictionaries/en.txt")) {
+        if dictionary.words.insert(word) {
+            let Some(initial) = word.chars().next() else {
+                continue;
+            };
+            dictionary
+                .by_initial
+                .entry(initial)
+                .or_default()
+                .entry(word.chars().count())
+                .or_default()
+                .push(word);
+        }
+    }
+    dictionary
+});
+
+/// The bundled list plus the config's accepted words.
+pub(crate) struct SpellChecker {
+    stemmer: Stemmer,
+    extra: HashSet<String>,
+    /// The config's words by stem, for accepting their inflections.
+    extra_stems: HashMap<String, Vec<String>>,
+    min_length: usize,
+    suggestions: usize,
+}
+
+impl SpellChecker {
+    pub(crate) fn new(config: &SpellingConfig, allowed_typos: &[String]) -> Self {
+        let stemmer = Stemmer::create(Algorithm::English);
+        let extra: HashSet<String> = config
+            .words
+            .iter()
+            .chain(allowed_typos)
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let mut extra_stems: HashMap<String, Vec<String>> = HashMap::new();
+        for word in &extra {
+            extra_stems
+                .entry(stemmer.stem(word).into_owned())
+                .or_default()
+                .push(word.clone());
+        }
+        Self {
+            stemmer,
+            extra,
+            extra_stems,
+            min_length: config.min_length,
+            suggestions: config.suggestions,
+        }
+    }
+
+    fn is_known(&self, word: &str) -> bool {
+        self.is_listed(word) || american(word).is_some_and(|word| self.is_listed(&word))
+    }
+
+    fn is_listed(&self, word: &str) -> bool {
+        if ENGLISH.words.contains(word) || self.extra.contains(word) {
+            return true;
+        }
+        let pack = Language::En.pack();
+        self.extra_stems
+            .get(self.stemmer.stem(word).as_ref())
+            .is_some_and(|listed| listed.iter().any(|listed| pack.is_inflection(word, listed)))
+    }
+
+    /// Closest known words with their ranks, best first. Words at the same
+    /// edit distance rank by how many letters they add or drop, so a swap
+    /// ("recieve") beats a substitution; remaining ties are alphabetical.
+    ///
+    /// Only words starting with the word's first or second letter are
+    /// compared, so a swap of the first two letters ("teh") is still found
+    /// but a typo in the first letter is not.
+    fn suggest(&self, word: &str) -> Vec<(Rank, String)> {
+        let length = word.chars().count();
+        let mut initials: Vec<char> = word.chars().take(2).collect();
+        initials.dedup();
+        let lengths = length.saturating_sub(MAX_DISTANCE)..=length + MAX_DISTANCE;
+        let mut found: Vec<(Rank, &str)> = initials
+            .iter()
+            .filter_map(|initial| ENGLISH.by_initial.get(initial))
+            .flat_map(|by_length| by_length.range(lengths.clone()))
+            .flat_map(|(_, words)| words.iter().copied())
+            .chain(self.extra.iter().map(String::as_str).filter(|candidate| {
+                candidate
+                    .chars()
+                    .next()
+                    .is_some_and(|initial| initials.contains(&initial))
+            }))
+            .filter_map(|candidate| {
+                let distance = edit_distance(word, candidate);
+                (distance <= MAX_DISTANCE)
+                    .then(|| ((distance, letter_difference(word, candidate)), candidate))
+            })
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+            .into_iter()
+            .take(self.suggestions)
+            .map(|(rank, candidate)| (rank, candidate.to_string()))
+            .collect()
+    }
+}
+
+/// The American spelling of a British `-ise`, `-isation` or `-our` word;
+/// the bundled list mostly uses American spellings.
+fn american(word: &str) -> Option<String> {
+    const ENDINGS: &[(&str, &str)] = &[
+        ("isation", "ization"),
+        ("isations", "izations"),
+        ("ise", "ize"),
+        ("ised", "ized"),
+        ("ises", "izes"),
+        ("ising", "izing"),
+        ("iser", "izer"),
+        ("isers", "izers"),
+        ("our", "or"),
+        ("ours", "ors"),
+    ];
+    ENDINGS.iter().find_map(|(british, american)| {
+        word.strip_suffix(british)
+            .map(|stem| format!("{stem}{american}"))
+    })
+}
+
+/// Edit distance, then [`letter_difference`].
+type Rank = (usize, usize);
+
+/// Letters one word has more of than the other, counted both ways.
+fn letter_difference(a: &str, b: &str) -> usize {
+    let mut counts: HashMap<char, isize> = HashMap::new();
+    for ch in a.chars() {
+        *counts.entry(ch).or_default() += 1;
+    }
+    for ch in b.chars() {
+        *counts.entry(ch).or_default() -= 1;
+    }
+    counts.values().map(|count| count.unsigned_abs()).sum()
+}
+
+/// The best suggestion, unless another one ranks the same: the fix would
+/// then be a coin toss.
+fn best_unique(suggestions: &[(Rank, String)]) -> Option<&str> {
+    match suggestions {
+        [(best, word), rest @ ..] if rest.first().is_none_or(|(next, _)| next > best) => Some(word),
+        _ => None,
+    }
+}
+
+/// The word to look up for a whitespace-separated token, with its offset
+/// in the token, or `None` when the token is not a plain word.
+fn checkable(token: &str) -> Option<(usize, &str)> {
+    let word = token.trim_matches(|c: char| !c.is_alphanumeric());
+    if word.is_empty() {
+        return None;
+    }
+    let offset = token.find(word).unwrap_or_default();
+    // Possessives and contractions: check the part before the apostrophe.
+    let word = word.split(['\'', '’']).next().unwrap_or(word);
+    let identifier = word.chars().any(|c| !(c.is_alphabetic() || c == '-'))
+        || word.chars().skip(1).any(char::is_uppercase);
+    (!identifier).then_some((offset, word))
+}
+
+/// Check the prose sentences. Words in headings are checked too.
+pub(crate) fn detect(
+    checker: &SpellChecker,
+    text: &str,
+    sentences: &[(String, usize, BlockKind)],
+    filtered: &DisabledRanges,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for (sentence, base, _) in sentences {
+        let mut cursor = 0;
+        for token in sentence.split_whitespace() {
+            let token_start = cursor + sentence[cursor..].find(token).unwrap_or_default();
+            cursor = token_start + token.len();
+            let Some((offset, word)) = checkable(token) else {
+                continue;
+            };
+            let mut part_start = base + token_start + offset;
+            for part in word.split('-') {
+                let start = part_start;
+                part_start += part.len() + 1;
+                if part.chars().count() < checker.min_length {
+                    continue;
+                }
+                let lower = part.to_lowercase();
+                let capitalised = part.chars().next().is_some_and(char::is_uppercase);
+                if checker.is_known(&lower) || capitalised {
+                    continue;
+                }
+                if filtered.is_category_disabled(start, Category::Spelling) {
+                    continue;
+                }
+                let location = byte_to_location(text, start);
+                if filtered.is_line_ignored(location.line) {
+                    continue;
+                }
+                let suggestions = checker.suggest(&lower);
+                let suggestion = match suggestions.as_slice() {
+                    [] => "Fix the spelling or add the word to `.toneguard/words.txt`.".to_string(),
+                    words => format!(
+                        "Did you mean {}? Otherwise add the word to `.toneguard/words.txt`.",
+                        words
+                            .iter()
+                            .map(|(_, word)| format!("`{word}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                };
+                let end = start + part.len();
+                diagnostics.push(Diagnostic {
+                    category: Category::Spelling,
+                    rule: "spelling/unknown-word".into(),
+                    severity: Severity::Warning,
+                    message: format!("Unknown word `{part}`."),
+                    suggestion: Some(suggestion),
+                    location,
+                    span: (start, end),
+                    snippet: part.to_string(),
+                    fix: best_unique(&suggestions)
+                        .map(|word| Fix::replace((start, end), word, Applicability::Unsafe)),
+                });
+            }
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tied_suggestions_get_no_fix() {
+        let checker = SpellChecker::new(&SpellingConfig::default(), &[]);
+        let best = |word: &str| best_unique(&checker.suggest(word)).map(str::to_string);
+        assert_eq!(best("recieve").as_deref(), Some("receive"));
+        assert_eq!(best("teh").as_deref(), Some("the"));
+        assert_eq!(best("sistem"), None);
+        assert_eq!(best("documentaton").as_deref(), Some("documentation"));
+    }
+
+    #[test]
+    fn test_identifiers_are_not_words() {
+        assert_eq!(checkable("(hello),"), Some((1, "hello")));
+        assert_eq!(checkable("don't"), Some((0, "don")));
+        assert_eq!(checkable("snake_case"), None);
+        assert_eq!(checkable("CamelCase"), None);
+        assert_eq!(checkable("config.yml"), None);
+        assert_eq!(checkable("v2"), None);
+    }
+
+    #[test]
+    fn test_known_words_and_inflections() {
+        let checker = SpellChecker::new(
+            &SpellingConfig {
+                words: vec!["toneguard".into()],
+                ..SpellingConfig::default()
+            },
+            &["detmerinsitc".into()],
+        );
+        for word in [
+            "documentation",
+            "installing",
+            "toneguard",
+            "toneguards",
+            "detmerinsitc",
+            "colour",
+        ] {
+            assert!(checker.is_known(word), "{word}");
+        }
+        for word in [
+            "documentaton",
+            "thier",
+            "posession",
+            "seperate",
+            "toneguardism",
+        ] {
+            assert!(!checker.is_known(word), "{word}");
+        }
+        assert_eq!(checker.suggest("documentaton")[0].1, "documentation");
+    }
+}
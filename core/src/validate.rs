@@ -0,0 +1,1106 @@
+//! Strict config validation and the config JSON Schema.
+//!
+//! Config structs take `#[serde(default)]`, so loading a config drops a
+//! misspelt or misplaced key without a word. [`check_config`] reads a config
+//! file strictly instead: unknown keys, wrong types, regexes and globs that do
+//! not compile, and profile or rule names that point nowhere, each located at
+//! the line and column of the offending key. [`check_document_overrides`]
+//! does the same for the `toneguard:` block of a document's frontmatter.
+//! [`config_schema`] describes the config as a JSON Schema for editors.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+use globset::Glob;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use serde_yaml::Value;
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::Marker;
+
+use crate::frontmatter::{DocumentOverrides, FRONTMATTER_KEY};
+use crate::rules::RuleSelector;
+use crate::{commits, docstrings, edit_distance, packs, Analyzer, Config, ProfileRules, Severity};
+
+/// Top-level keys read by the editor extension rather than the analyzer.
+pub const EXTENSION_KEYS: &[&str] = &["version", "file_types"];
+
+/// Give up on a file after this many type errors.
+const MAX_TYPE_ERRORS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfigIssueKind {
+    Syntax,
+    UnknownKey,
+    InvalidType,
+    InvalidRegex,
+    InvalidGlob,
+    UnknownProfile,
+    UnknownRule,
+    Invalid,
+}
+
+impl fmt::Display for ConfigIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ConfigIssueKind::Syntax => "syntax",
+            ConfigIssueKind::UnknownKey => "unknown-key",
+            ConfigIssueKind::InvalidType => "invalid-type",
+            ConfigIssueKind::InvalidRegex => "invalid-regex",
+            ConfigIssueKind::InvalidGlob => "invalid-glob",
+            ConfigIssueKind::UnknownProfile => "unknown-profile",
+            ConfigIssueKind::UnknownRule => "unknown-rule",
+            ConfigIssueKind::Invalid => "invalid",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One problem in a config file.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigIssue {
+    pub kind: ConfigIssueKind,
+    /// Key path such as `profiles[2].rules.max_bullet_items`; empty for the
+    /// file as a whole.
+    pub path: String,
+    /// 1-based line and column of the key, or 0 when unknown.
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Field(String),
+    Index(usize),
+}
+
+fn format_path(path: &[Key]) -> String {
+    let mut out = String::new();
+    for key in path {
+        match key {
+            Key::Field(name) if out.is_empty() => out.push_str(name),
+            Key::Field(name) => {
+                out.push('.');
+                out.push_str(name);
+            }
+            Key::Index(index) => out.push_str(&format!("[{index}]")),
+        }
+    }
+    out
+}
+
+fn field(name: &str) -> Key {
+    Key::Field(name.to_string())
+}
+
+fn syntax_issue(err: &serde_yaml::Error) -> ConfigIssue {
+    let (line, column) = err
+        .location()
+        .map(|location| (location.line(), location.column()))
+        .unwrap_or_default();
+    ConfigIssue {
+        kind: ConfigIssueKind::Syntax,
+        path: String::new(),
+        line,
+        column,
+        message: err.to_string(),
+    }
+}
+
+/// Check a config file's text. Issues come back in file order; an empty
+/// list means the file loads exactly as written.
+pub fn check_config(text: &str) -> Vec<ConfigIssue> {
+    let raw: Value = match serde_yaml::from_str(text) {
+        Ok(raw) => raw,
+        Err(err) => return vec![syntax_issue(&err)],
+    };
+    let mut checker = Checker {
+        positions: Positions::parse(text),
+        schema: config_schema(),
+        issues: Vec::new(),
+    };
+    if let Some(config) = checker.deserialize(raw) {
+        checker.check_semantics(&config);
+        if checker.issues.is_empty() {
+            if let Err(err) = Analyzer::new(config) {
+                checker.push(ConfigIssueKind::Invalid, &[], format!("{err:#}"));
+            }
+        }
+    }
+    let mut issues = checker.issues;
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+/// Frontmatter as far as the checker is concerned: only the `toneguard:`
+/// key, so a document's other metadata is never reported.
+#[derive(Deserialize, schemars::JsonSchema)]
+struct Frontmatter {
+    #[serde(default)]
+    toneguard: DocumentOverrides,
+}
+
+/// Check the `toneguard:` block of a document's frontmatter against
+/// `config`. `frontmatter` is the YAML between the fences; lines count from
+/// its start. Issues come back in order, none when there is no block.
+pub fn check_document_overrides(frontmatter: &str, config: &Config) -> Vec<ConfigIssue> {
+    document_overrides(frontmatter, config).1
+}
+
+/// The `toneguard:` block read as strictly as [`check_document_overrides`]
+/// checks it: unknown keys and values of the wrong type are left out.
+pub(crate) fn document_overrides(
+    frontmatter: &str,
+    config: &Config,
+) -> (Option<DocumentOverrides>, Vec<ConfigIssue>) {
+    let raw: Value = match serde_yaml::from_str(frontmatter) {
+        Ok(raw) => raw,
+        Err(err) => return (None, vec![syntax_issue(&err)]),
+    };
+    let Some(block) = raw.get(FRONTMATTER_KEY) else {
+        return (None, Vec::new());
+    };
+    let mut value = serde_yaml::Mapping::new();
+    value.insert(FRONTMATTER_KEY.into(), block.clone());
+    let mut checker = Checker {
+        positions: Positions::parse(frontmatter),
+        schema: serde_json::to_value(schemars::schema_for!(Frontmatter))
+            .expect("frontmatter schema serializes"),
+        issues: Vec::new(),
+    };
+    let overrides = checker
+        .strict::<Frontmatter>(Value::Mapping(value))
+        .map(|frontmatter| frontmatter.toneguard);
+    if let Some(overrides) = &overrides {
+        let path = vec![field(FRONTMATTER_KEY)];
+        if let Some(name) = &overrides.document_type {
+            if !config.document_types.contains_key(name) {
+                let mut item = path.clone();
+                item.push(field("type"));
+                checker.push(
+                    ConfigIssueKind::Invalid,
+                    &item,
+                    format!("unknown document type `{name}`"),
+                );
+            }
+        }
+        checker.check_overrides(
+            path,
+            config,
+            overrides.profile.as_deref(),
+            &overrides.disable,
+            overrides.rules.as_ref(),
+        );
+    }
+    let mut issues = checker.issues;
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    (overrides, issues)
+}
+
+struct Checker {
+    positions: Positions,
+    schema: JsonValue,
+    issues: Vec<ConfigIssue>,
+}
+
+impl Checker {
+    fn push(&mut self, kind: ConfigIssueKind, path: &[Key], message: String) {
+        let (line, column) = self.positions.locate(path);
+        self.issues.push(ConfigIssue {
+            kind,
+            path: format_path(path),
+            line,
+            column,
+            message,
+        });
+    }
+
+    /// Resolve packs and deserialize, recording unknown keys and type errors.
+    fn deserialize(&mut self, raw: Value) -> Option<Config> {
+        let mut value = match packs::resolve(raw) {
+            Ok(value) => value,
+            Err(err) => {
+                let message = format!("{err:#}");
+                let path = backticked_path(&message)
+                    .filter(|path| self.positions.contains(path))
+                    .unwrap_or_else(|| vec![field("extends")]);
+                self.push(ConfigIssueKind::Invalid, &path, message);
+                return None;
+            }
+        };
+        if let Value::Mapping(map) = &mut value {
+            for key in EXTENSION_KEYS {
+                map.remove(*key);
+            }
+        }
+        self.strict(value)
+    }
+
+    /// Deserialize `value`, recording unknown keys and type errors.
+    fn strict<T: DeserializeOwned>(&mut self, mut value: Value) -> Option<T> {
+        for _ in 0..MAX_TYPE_ERRORS {
+            let mut unknown = Vec::new();
+            let mut on_ignored = |path: serde_ignored::Path| unknown.push(ignored_path(&path));
+            let deserializer = serde_ignored::Deserializer::new(value.clone(), &mut on_ignored);
+            match serde_path_to_error::deserialize::<_, T>(deserializer) {
+                Ok(parsed) => {
+                    for path in unknown {
+                        let message = self.unknown_key_message(&path);
+                        self.push(ConfigIssueKind::UnknownKey, &path, message);
+                    }
+                    return Some(parsed);
+                }
+                Err(err) => {
+                    let path = error_path(err.path());
+                    self.push(ConfigIssueKind::InvalidType, &path, err.inner().to_string());
+                    // Drop the offending key so the next pass can look further.
+                    if !remove_field(&mut value, &path) {
+                        return None;
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn unknown_key_message(&self, path: &[Key]) -> String {
+        let Some((Key::Field(name), parent)) = path.split_last() else {
+            return format!("unknown key `{}`", format_path(path));
+        };
+        let mut message = if parent.is_empty() {
+            format!("unknown key `{name}`")
+        } else {
+            format!("unknown key `{name}` in `{}`", format_path(parent))
+        };
+        let known = schema_keys(&self.schema, parent);
+        if let Some(suggestion) = closest(name, &known) {
+            message.push_str(&format!(" (did you mean `{suggestion}`?)"));
+        }
+        message
+    }
+
+    fn check_semantics(&mut self, config: &Config) {
+        for (i, pattern) in config.templates.ban.iter().enumerate() {
+            self.check_regex(&[field("templates"), field("ban"), Key::Index(i)], pattern);
+        }
+        self.check_profile_rules(vec![field("profile_defaults")], &config.profile_defaults);
+        for (i, rule) in config.custom_rules.iter().enumerate() {
+            if let Some(pattern) = &rule.pattern {
+                self.check_regex(
+                    &[field("custom_rules"), Key::Index(i), field("pattern")],
+                    pattern,
+                );
+            }
+        }
+        if let Some(pattern) = &config.comment_policy.ticket_reference_regex {
+            self.check_regex(
+                &[field("comment_policy"), field("ticket_reference_regex")],
+                pattern,
+            );
+        }
+        for (i, pattern) in config.repo_rules.suspicious_filenames.iter().enumerate() {
+            self.check_regex(
+                &[
+                    field("repo_rules"),
+                    field("suspicious_filenames"),
+                    Key::Index(i),
+                ],
+                pattern,
+            );
+        }
+
+        let globs: [(&str, &str, &[String]); 9] = [
+            (
+                "repo_rules",
+                "ignore_globs",
+                &config.repo_rules.ignore_globs,
+            ),
+            ("repo_rules", "slop_globs", &config.repo_rules.slop_globs),
+            (
+                "repo_rules",
+                "large_json_globs",
+                &config.repo_rules.large_json_globs,
+            ),
+            (
+                "repo_rules",
+                "allow_large_json_globs",
+                &config.repo_rules.allow_large_json_globs,
+            ),
+            (
+                "comment_policy",
+                "ignore_globs",
+                &config.comment_policy.ignore_globs,
+            ),
+            (
+                "comment_policy",
+                "allow_globs",
+                &config.comment_policy.allow_globs,
+            ),
+            (
+                "flow_rules",
+                "ignore_globs",
+                &config.flow_rules.ignore_globs,
+            ),
+            (
+                "organize_rules",
+                "ignore_globs",
+                &config.organize_rules.ignore_globs,
+            ),
+            ("links", "ignore", &config.links.ignore),
+        ];
+        for (section, key, patterns) in globs {
+            for (i, pattern) in patterns.iter().enumerate() {
+                self.check_glob(&[field(section), field(key), Key::Index(i)], pattern);
+            }
+        }
+
+        let names: BTreeSet<&str> = config
+            .profiles
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect();
+        for (i, profile) in config.profiles.iter().enumerate() {
+            let path = vec![field("profiles"), Key::Index(i)];
+            for (j, pattern) in profile.globs.iter().enumerate() {
+                let mut glob_path = path.clone();
+                glob_path.extend([field("globs"), Key::Index(j)]);
+                self.check_glob(&glob_path, pattern);
+            }
+            if let Some(parent) = &profile.extends {
+                let mut extends_path = path.clone();
+                extends_path.push(field("extends"));
+                if parent != "default" && !names.contains(parent.as_str()) {
+                    self.push(
+                        ConfigIssueKind::UnknownProfile,
+                        &extends_path,
+                        format!(
+                            "profile `{}` extends unknown profile `{parent}`",
+                            profile.name
+                        ),
+                    );
+                } else if extends_cycle(config, &profile.name) {
+                    self.push(
+                        ConfigIssueKind::UnknownProfile,
+                        &extends_path,
+                        format!("profile `{}` is part of an `extends` cycle", profile.name),
+                    );
+                }
+            }
+            let mut rules_path = path;
+            rules_path.push(field("rules"));
+            self.check_profile_rules(rules_path, &profile.rules);
+        }
+
+        let known = |name: &str| profile_exists(config, name);
+        for (i, rule) in config.custom_rules.iter().enumerate() {
+            for (j, name) in rule.profiles.iter().enumerate() {
+                if !known(name) {
+                    self.push(
+                        ConfigIssueKind::UnknownProfile,
+                        &[
+                            field("custom_rules"),
+                            Key::Index(i),
+                            field("profiles"),
+                            Key::Index(j),
+                        ],
+                        format!("custom rule `{}` names unknown profile `{name}`", rule.id),
+                    );
+                }
+            }
+        }
+        for (i, term) in config.glossary.terms.iter().enumerate() {
+            for (j, name) in term.profiles.iter().enumerate() {
+                if !known(name) {
+                    let term_name = term
+                        .preferred
+                        .as_ref()
+                        .or(term.variants.first())
+                        .map_or("", String::as_str);
+                    self.push(
+                        ConfigIssueKind::UnknownProfile,
+                        &[
+                            field("glossary"),
+                            field("terms"),
+                            Key::Index(i),
+                            field("profiles"),
+                            Key::Index(j),
+                        ],
+                        format!("glossary term `{term_name}` names unknown profile `{name}`"),
+                    );
+                }
+            }
+        }
+
+        for (name, document_type) in &config.document_types {
+            self.check_overrides(
+                vec![field("document_types"), field(name)],
+                config,
+                document_type.profile.as_deref(),
+                &document_type.disable,
+                document_type.rules.as_ref(),
+            );
+        }
+
+        self.check_severity_overrides(vec![], &config.severity_overrides);
+
+        let threshold = config.duplicates.threshold;
+        if !(0.0..=1.0).contains(&threshold) {
+            self.push(
+                ConfigIssueKind::Invalid,
+                &[field("duplicates"), field("threshold")],
+                format!("`threshold` must be between 0 and 1, got {threshold}"),
+            );
+        }
+    }
+
+    /// The keys shared by `document_types` entries and frontmatter blocks.
+    fn check_overrides(
+        &mut self,
+        path: Vec<Key>,
+        config: &Config,
+        profile: Option<&str>,
+        disable: &[String],
+        rules: Option<&ProfileRules>,
+    ) {
+        if let Some(name) = profile.filter(|name| !profile_exists(config, name)) {
+            let mut item = path.clone();
+            item.push(field("profile"));
+            self.push(
+                ConfigIssueKind::UnknownProfile,
+                &item,
+                format!("unknown profile `{name}`"),
+            );
+        }
+        for (i, name) in disable.iter().enumerate() {
+            if RuleSelector::parse(name).is_none() {
+                let mut item = path.clone();
+                item.extend([field("disable"), Key::Index(i)]);
+                self.push(
+                    ConfigIssueKind::UnknownRule,
+                    &item,
+                    format!("unknown category or rule `{name}`"),
+                );
+            }
+        }
+        if let Some(rules) = rules {
+            let mut item = path;
+            item.push(field("rules"));
+            self.check_profile_rules(item, rules);
+        }
+    }
+
+    fn check_severity_overrides(&mut self, path: Vec<Key>, overrides: &BTreeMap<String, Severity>) {
+        for name in overrides.keys() {
+            if RuleSelector::parse(name).is_none() {
+                let mut item = path.clone();
+                item.extend([field("severity_overrides"), field(name)]);
+                self.push(
+                    ConfigIssueKind::UnknownRule,
+                    &item,
+                    format!("unknown category or rule `{name}`"),
+                );
+            }
+        }
+    }
+
+    fn check_profile_rules(&mut self, path: Vec<Key>, rules: &ProfileRules) {
+        let lists: [(&str, &[String]); 4] = [
+            ("banned_headings", &rules.banned_headings),
+            ("template_phrases", &rules.template_phrases),
+            ("required_patterns", &rules.required_patterns),
+            ("forbidden_patterns", &rules.forbidden_patterns),
+        ];
+        for (key, patterns) in lists {
+            for (i, pattern) in patterns.iter().enumerate() {
+                let mut item = path.clone();
+                item.extend([field(key), Key::Index(i)]);
+                self.check_regex(&item, pattern);
+            }
+        }
+        self.check_severity_overrides(path.clone(), &rules.severity_overrides);
+        let ratios = [
+            ("uniform_max_cv", rules.statistics.uniform_max_cv),
+            ("passive_max_ratio", rules.statistics.passive_max_ratio),
+            ("opening_max_ratio", rules.statistics.opening_max_ratio),
+        ];
+        for (key, value) in ratios {
+            if let Some(value) = value.filter(|value| !(0.0..=1.0).contains(value)) {
+                let mut item = path.clone();
+                item.extend([field("statistics"), field(key)]);
+                self.push(
+                    ConfigIssueKind::Invalid,
+                    &item,
+                    format!("`{key}` must be between 0 and 1, got {value}"),
+                );
+            }
+        }
+    }
+
+    fn check_regex(&mut self, path: &[Key], pattern: &str) {
+        if let Err(err) = Regex::new(pattern) {
+            self.push(
+                ConfigIssueKind::InvalidRegex,
+                path,
+                format!("invalid regex `{pattern}`: {err}"),
+            );
+        }
+    }
+
+    fn check_glob(&mut self, path: &[Key], pattern: &str) {
+        if let Err(err) = Glob::new(pattern) {
+            self.push(
+                ConfigIssueKind::InvalidGlob,
+                path,
+                format!("invalid glob `{pattern}`: {}", err.kind()),
+            );
+        }
+    }
+}
+
+/// Whether `name` is a built-in profile or one declared in `config`.
+fn profile_exists(config: &Config, name: &str) -> bool {
+    name == "default"
+        || name == docstrings::CODE_DOCS_PROFILE
+        || name == commits::COMMIT_MESSAGE_PROFILE
+        || config.profiles.iter().any(|profile| profile.name == name)
+}
+
+/// Whether following `extends` from `name` comes back to it.
+fn extends_cycle(config: &Config, name: &str) -> bool {
+    let parents: HashMap<&str, &str> = config
+        .profiles
+        .iter()
+        .filter_map(|profile| Some((profile.name.as_str(), profile.extends.as_deref()?)))
+        .collect();
+    let mut current = name;
+    for _ in 0..=parents.len() {
+        match parents.get(current) {
+            Some(parent) if *parent == name => return true,
+            Some(parent) => current = parent,
+            None => return false,
+        }
+    }
+    false
+}
+
+fn ignored_path(path: &serde_ignored::Path) -> Vec<Key> {
+    use serde_ignored::Path;
+    match path {
+        Path::Root => Vec::new(),
+        Path::Seq { parent, index } => {
+            let mut keys = ignored_path(parent);
+            keys.push(Key::Index(*index));
+            keys
+        }
+        Path::Map { parent, key } => {
+            let mut keys = ignored_path(parent);
+            keys.push(Key::Field(key.clone()));
+            keys
+        }
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => ignored_path(parent),
+    }
+}
+
+fn error_path(path: &serde_path_to_error::Path) -> Vec<Key> {
+    use serde_path_to_error::Segment;
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(Key::Index(*index)),
+            Segment::Map { key } => Some(Key::Field(key.clone())),
+            Segment::Enum { .. } | Segment::Unknown => None,
+        })
+        .collect()
+}
+
+/// Remove the deepest mapping key on `path` from `value`.
+fn remove_field(value: &mut Value, path: &[Key]) -> bool {
+    let Some(last) = path.iter().rposition(|key| matches!(key, Key::Field(_))) else {
+        return false;
+    };
+    let mut node = value;
+    for key in &path[..last] {
+        let next = match key {
+            Key::Field(name) => node.get_mut(name.as_str()),
+            Key::Index(index) => node.get_mut(*index),
+        };
+        match next {
+            Some(next) => node = next,
+            None => return false,
+        }
+    }
+    let (Key::Field(name), Value::Mapping(map)) = (&path[last], node) else {
+        return false;
+    };
+    map.remove(name.as_str()).is_some()
+}
+
+/// The first backticked dotted path in an error message, e.g. from
+/// "`limits` is not a list".
+fn backticked_path(message: &str) -> Option<Vec<Key>> {
+    let start = message.find('`')? + 1;
+    let end = start + message[start..].find('`')?;
+    Some(message[start..end].split('.').map(field).collect())
+}
+
+/// Keys the schema allows in the mapping at `path`.
+fn schema_keys(schema: &JsonValue, path: &[Key]) -> Vec<String> {
+    let mut node = schema;
+    for key in path {
+        node = resolve_schema(schema, node, Some(key));
+        let next = match key {
+            Key::Field(name) => node.get("properties").and_then(|props| props.get(name)),
+            Key::Index(_) => node.get("items"),
+        };
+        let Some(next) = next else {
+            return Vec::new();
+        };
+        node = next;
+    }
+    let node = resolve_schema(schema, node, None);
+    node.get("properties")
+        .and_then(JsonValue::as_object)
+        .map(|props| props.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Follow `$ref`s and `allOf` wrappers, and pick the `anyOf` branch that
+/// can take `next`.
+fn resolve_schema<'a>(
+    root: &'a JsonValue,
+    node: &'a JsonValue,
+    next: Option<&Key>,
+) -> &'a JsonValue {
+    let mut node = node;
+    for _ in 0..16 {
+        if let Some(name) = node
+            .get("$ref")
+            .and_then(JsonValue::as_str)
+            .and_then(|reference| reference.strip_prefix("#/definitions/"))
+        {
+            match root.get("definitions").and_then(|defs| defs.get(name)) {
+                Some(target) => node = target,
+                None => break,
+            }
+        } else if let Some(first) = node
+            .get("allOf")
+            .and_then(JsonValue::as_array)
+            .and_then(|parts| parts.first())
+        {
+            node = first;
+        } else if let Some(options) = node.get("anyOf").and_then(JsonValue::as_array) {
+            let wanted = match next {
+                Some(Key::Index(_)) => "items",
+                _ => "properties",
+            };
+            match options
+                .iter()
+                .find(|option| option.get(wanted).is_some() || option.get("$ref").is_some())
+            {
+                Some(option) => node = option,
+                None => break,
+            }
+        } else {
+            break;
+        }
+    }
+    node
+}
+
+/// The known key closest to a misspelt one, if any is close enough.
+fn closest<'a>(name: &str, known: &'a [String]) -> Option<&'a str> {
+    known
+        .iter()
+        .map(|key| (edit_distance(name, key), key))
+        .filter(|(distance, key)| *distance <= (key.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, key)| key.as_str())
+}
+
+/// Line and column of every key and list item in a YAML document.
+struct Positions {
+    map: HashMap<Vec<Key>, (usize, usize)>,
+}
+
+impl Positions {
+    fn parse(text: &str) -> Self {
+        let mut recorder = PositionRecorder::default();
+        // Syntax errors were already reported by serde_yaml.
+        let _ = Parser::new_from_str(text).load(&mut recorder, false);
+        Self { map: recorder.map }
+    }
+
+    fn contains(&self, path: &[Key]) -> bool {
+        self.map.contains_key(path)
+    }
+
+    /// Position of `path`, or of its nearest ancestor written in the file.
+    fn locate(&self, path: &[Key]) -> (usize, usize) {
+        (0..=path.len())
+            .rev()
+            .find_map(|len| self.map.get(&path[..len]).copied())
+            .unwrap_or_default()
+    }
+}
+
+enum Frame {
+    /// Mapping with the key whose value comes next, if it has been read.
+    Map(Option<Key>),
+    /// Sequence with the index of the next item.
+    Seq(usize),
+}
+
+#[derive(Default)]
+struct PositionRecorder {
+    stack: Vec<Frame>,
+    map: HashMap<Vec<Key>, (usize, usize)>,
+}
+
+impl PositionRecorder {
+    fn record(&mut self, mark: Marker) {
+        let path: Vec<Key> = self
+            .stack
+            .iter()
+            .filter_map(|frame| match frame {
+                Frame::Map(key) => key.clone(),
+                Frame::Seq(index) => Some(Key::Index(*index)),
+            })
+            .collect();
+        // Marker columns count from zero.
+        self.map
+            .entry(path)
+            .or_insert((mark.line(), mark.col() + 1));
+    }
+
+    /// A value finished: the parent moves to its next key or item.
+    fn advance(&mut self) {
+        match self.stack.last_mut() {
+            Some(Frame::Map(key)) => *key = None,
+            Some(Frame::Seq(index)) => *index += 1,
+            None => {}
+        }
+    }
+}
+
+impl MarkedEventReceiver for PositionRecorder {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::Scalar(value, ..) => {
+                if let Some(Frame::Map(key @ None)) = self.stack.last_mut() {
+                    *key = Some(Key::Field(value));
+                    self.record(mark);
+                } else {
+                    self.record(mark);
+                    self.advance();
+                }
+            }
+            Event::Alias(_) => {
+                self.record(mark);
+                self.advance();
+            }
+            Event::MappingStart(..) | Event::SequenceStart(..) => {
+                if let Some(Frame::Map(key @ None)) = self.stack.last_mut() {
+                    // Complex mapping key; positions below it are not tracked.
+                    *key = Some(Key::Field(String::new()));
+                }
+                self.record(mark);
+                self.stack
+                    .push(if matches!(event, Event::MappingStart(..)) {
+                        Frame::Map(None)
+                    } else {
+                        Frame::Seq(0)
+                    });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.stack.pop();
+                self.advance();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// JSON Schema for config files, including `extends`, list operators and
+/// the keys the editor extension reads. Unknown keys are rejected.
+pub fn config_schema() -> JsonValue {
+    let mut schema =
+        serde_json::to_value(schemars::schema_for!(Config)).expect("config schema serializes");
+    allow_operators(&mut schema);
+    if let Some(definitions) = schema
+        .get_mut("definitions")
+        .and_then(JsonValue::as_object_mut)
+    {
+        for definition in definitions.values_mut() {
+            allow_operators(definition);
+        }
+    }
+    schema["title"] = json!("ToneGuard config");
+    let properties = &mut schema["properties"];
+    properties["extends"] = json!({
+        "description": format!(
+            "Built-in rule packs to start from, in order: {}",
+            packs::PACKS
+                .iter()
+                .map(|pack| format!("{}{}", packs::PACK_PREFIX, pack.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        "anyOf": [
+            {"type": "string"},
+            {"type": "array", "items": {"type": "string"}}
+        ]
+    });
+    properties["version"] = json!({
+        "description": "Config version recorded by the editor extension",
+        "type": ["string", "number"]
+    });
+    properties["file_types"] = json!({
+        "description": "File types the editor extension lints",
+        "type": "array",
+        "items": {"type": "string"}
+    });
+    deny_unknown(&mut schema);
+    if let Some(definitions) = schema
+        .get_mut("definitions")
+        .and_then(JsonValue::as_object_mut)
+    {
+        for definition in definitions.values_mut() {
+            deny_unknown(definition);
+        }
+    }
+    schema
+}
+
+/// Let every list property also take `{add: [...], remove: [...]}`.
+fn allow_operators(node: &mut JsonValue) {
+    let Some(properties) = node
+        .get_mut("properties")
+        .and_then(JsonValue::as_object_mut)
+    else {
+        return;
+    };
+    for property in properties.values_mut() {
+        if property.get("type") != Some(&json!("array")) {
+            allow_operators(property);
+            continue;
+        }
+        let mut list = property.clone();
+        let Some(list_object) = list.as_object_mut() else {
+            continue;
+        };
+        let description = list_object.remove("description");
+        let default = list_object.remove("default");
+        let mut wrapped = json!({
+            "anyOf": [
+                list.clone(),
+                {
+                    "type": "object",
+                    "properties": {"add": list.clone(), "remove": list},
+                    "additionalProperties": false
+                }
+            ]
+        });
+        if let Some(description) = description {
+            wrapped["description"] = description;
+        }
+        if let Some(default) = default {
+            wrapped["default"] = default;
+        }
+        *property = wrapped;
+    }
+}
+
+fn deny_unknown(node: &mut JsonValue) {
+    let Some(object) = node.as_object_mut() else {
+        return;
+    };
+    if object.contains_key("properties") && !object.contains_key("additionalProperties") {
+        object.insert("additionalProperties".to_string(), json!(false));
+    }
+    if let Some(properties) = object
+        .get_mut("properties")
+        .and_then(JsonValue::as_object_mut)
+    {
+        for property in properties.values_mut() {
+            deny_unknown(property);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(issues: &[ConfigIssue]) -> Vec<(ConfigIssueKind, &str, usize, usize)> {
+        issues
+            .iter()
+            .map(|issue| (issue.kind, issue.path.as_str(), issue.line, issue.column))
+            .collect()
+    }
+
+    #[test]
+    fn test_unknown_keys_and_types_are_located() {
+        let text = "\
+version: 1
+limits:
+  em_dashes_per_paragraph: two
+profiles:
+  - name: readme
+    globs: [\"README.md\"]
+    rules:
+      max_bulet_items: 5
+      max_headings: 3
+";
+        let issues = check_config(text);
+        assert_eq!(
+            kinds(&issues),
+            [
+                (
+                    ConfigIssueKind::InvalidType,
+                    "limits.em_dashes_per_paragraph",
+                    3,
+                    3
+                ),
+                (
+                    ConfigIssueKind::UnknownKey,
+                    "profiles[0].rules.max_bulet_items",
+                    8,
+                    7
+                ),
+            ]
+        );
+        assert!(issues[1]
+            .message
+            .contains("did you mean `max_bullet_items`"));
+    }
+
+    #[test]
+    fn test_regexes_globs_and_references_are_checked() {
+        let text = "\
+templates:
+  ban: ['(unclosed']
+profiles:
+  - name: docs
+    globs: ['docs/[']
+    extends: guide
+    rules:
+      severity_overrides:
+        structure/nope: hint
+      statistics:
+        passive_max_ratio: 1.5
+severity_overrides:
+  tone/nope: error
+custom_rules:
+  - id: x
+    phrases: [y]
+    profiles: [docs, blog]
+duplicates:
+  threshold: 80
+";
+        let found: Vec<_> = check_config(text)
+            .into_iter()
+            .map(|issue| (issue.kind, issue.path, issue.line))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (ConfigIssueKind::InvalidRegex, "templates.ban[0]".into(), 2),
+                (
+                    ConfigIssueKind::InvalidGlob,
+                    "profiles[0].globs[0]".into(),
+                    5
+                ),
+                (
+                    ConfigIssueKind::UnknownProfile,
+                    "profiles[0].extends".into(),
+                    6
+                ),
+                (
+                    ConfigIssueKind::UnknownRule,
+                    "profiles[0].rules.severity_overrides.structure/nope".into(),
+                    9
+                ),
+                (
+                    ConfigIssueKind::Invalid,
+                    "profiles[0].rules.statistics.passive_max_ratio".into(),
+                    11
+                ),
+                (
+                    ConfigIssueKind::UnknownRule,
+                    "severity_overrides.tone/nope".into(),
+                    13
+                ),
+                (
+                    ConfigIssueKind::UnknownProfile,
+                    "custom_rules[0].profiles[1]".into(),
+                    17
+                ),
+                (ConfigIssueKind::Invalid, "duplicates.threshold".into(), 19),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repo_config_is_clean() {
+        let text = include_str!("../../layth-style.yml");
+        assert!(check_config(text).is_empty(), "{:?}", check_config(text));
+        let issues = check_config("limits: [\n");
+        assert_eq!(issues[0].kind, ConfigIssueKind::Syntax);
+    }
+
+    #[test]
+    fn test_schema_allows_operators_and_denies_unknown_keys() {
+        let schema = config_schema();
+        assert_eq!(schema["additionalProperties"], json!(false));
+        assert!(schema["properties"]["extends"].is_object());
+        let throttle = &schema["definitions"]["BuzzwordConfig"]["properties"]["throttle"];
+        assert_eq!(throttle["anyOf"][1]["properties"]["add"]["type"], "array");
+        assert!(
+            schema_keys(&schema, &[field("profiles"), Key::Index(0), field("rules")])
+                .contains(&"max_bullet_items".to_string())
+        );
+    }
+
+    #[test]
+    fn test_document_types_and_frontmatter_are_checked() {
+        let text = "\
+document_types:
+  generated:
+    profile: blog
+    disable: [tone, nope]
+";
+        let issues = check_config(text);
+        assert_eq!(
+            kinds(&issues),
+            [
+                (
+                    ConfigIssueKind::UnknownProfile,
+                    "document_types.generated.profile",
+                    3,
+                    5
+                ),
+                (
+                    ConfigIssueKind::UnknownRule,
+                    "document_types.generated.disable[1]",
+                    4,
+                    21
+                ),
+            ]
+        );
+
+        let config: Config = serde_yaml::from_str(text).unwrap();
+        let frontmatter = "title: x\ntoneguard:\n  typ: generated\n";
+        let issues = check_document_overrides(frontmatter, &config);
+        assert_eq!(
+            kinds(&issues),
+            [(ConfigIssueKind::UnknownKey, "toneguard.typ", 3, 3)]
+        );
+        assert!(issues[0].message.contains("did you mean `type`"));
+        assert!(check_document_overrides("title: x\n", &config).is_empty());
+    }
+}
@@ -25,6 +25,32 @@ fn assert_not(report: &DocumentReport, category: Category) {
     );
 }
 
+#[test]
+fn reports_are_identical_across_runs_and_thread_counts() {
+    use rayon::prelude::*;
+
+    let analyzer = Analyzer::new(Config::default()).unwrap();
+    let docs = [
+        include_str!("../../README.md"),
+        "We leverage robust synergy to seamlessly empower teams. Moreover, we utilize cutting-edge tools. Furthermore, the holistic paradigm delivers. Additionally, we streamline everything.",
+    ];
+    let run = |threads: usize| -> Vec<String> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            docs.par_iter()
+                .map(|text| serde_json::to_string(&analyzer.analyze(text)).unwrap())
+                .collect()
+        })
+    };
+    let first = run(1);
+    for threads in [1, 1, 4, 4, 8] {
+        assert_eq!(run(threads), first, "output changed with {threads} threads");
+    }
+}
+
 #[test]
 fn ignores_yaml_frontmatter() {
     let text = r#"---
@@ -86,6 +112,80 @@ fn flags_buzzword_cluster_even_with_specifics() {
     assert_has(&report, Category::Buzzword);
 }
 
+#[test]
+fn matches_inflected_and_unicode_variants_of_listed_phrases() {
+    let text = "We were leveraging a Cutting\u{2011}Edge stack.";
+    let report = analyze(text);
+    let snippets: Vec<&str> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.category == Category::Buzzword)
+        .map(|d| &text[d.span.0..d.span.1])
+        .collect();
+    assert_eq!(snippets, vec!["leveraging", "Cutting\u{2011}Edge"]);
+    assert!(report.diagnostics.iter().all(|d| d.fix.is_none()));
+}
+
+#[test]
+fn frontmatter_language_selects_the_locale_pack() {
+    let text = "---\nlang: de-DE\n---\n\nUnsere nahtlose Lösung ist innovativ. Darüber hinaus sagen Experten sagen viel. Wir leverage nichts.";
+    let report = analyze(text);
+    let snippets: Vec<&str> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.category == Category::Buzzword)
+        .map(|d| &text[d.span.0..d.span.1])
+        .collect();
+    assert_eq!(snippets, vec!["nahtlose", "innovativ"]);
+    assert_has(&report, Category::Transition);
+    assert_has(&report, Category::Weasel);
+}
+
+#[test]
+fn configured_phrases_extend_the_locale_pack() {
+    let text =
+        "---\nlang: de\n---\n\nUnsere nahtlose Lösung schafft Synergien. Wir leverage nichts.";
+    let yaml =
+        serde_yaml::from_str("buzzwords:\n  throttle:\n    add: [Synergie]\n").expect("valid yaml");
+    let resolved = dwg_core::packs::resolve(yaml).expect("resolved config");
+    let cfg: Config = serde_yaml::from_value(resolved).expect("valid config");
+    let report = analyze_with(cfg, text);
+    let snippets: Vec<&str> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.category == Category::Buzzword)
+        .map(|d| &text[d.span.0..d.span.1])
+        .collect();
+    assert_eq!(snippets, vec!["nahtlose", "Synergien"]);
+}
+
+#[test]
+fn language_setting_drives_passive_voice_and_profiles() {
+    let passive =
+        "Die Datei wird erstellt. Der Bericht wurde geschrieben. Die Tabelle wird aktualisiert. \
+        Das Ergebnis wurde geprüft. Der Fehler wurde behoben. Die Seite wird geladen. Wir testen.";
+    let cfg = config_from_yaml("language: de\n");
+    let report = analyze_with(cfg, passive);
+    assert!(report
+        .diagnostics
+        .iter()
+        .any(|d| d.rule == "tone/passive-density"));
+    assert!(analyze(passive)
+        .diagnostics
+        .iter()
+        .all(|d| d.rule != "tone/passive-density"));
+
+    let cfg = config_from_yaml(
+        "profiles:\n  - name: es\n    globs: ['es/**']\n    rules:\n      language: es\n",
+    );
+    let analyzer = Analyzer::new(cfg).unwrap();
+    let report = analyzer
+        .analyze_profile_name("Queremos potenciar la sinergia del equipo.", "es")
+        .unwrap();
+    assert_has(&report, Category::Buzzword);
+    assert!(serde_yaml::from_str::<Config>("language: fr\n").is_err());
+}
+
 #[test]
 fn suppresses_single_transition_when_sentence_has_specifics() {
     let report = analyze("Additionally, we shipped API v2.");
@@ -247,3 +347,819 @@ Additionally, we will leverage the system."#;
     assert_eq!(buzzwords, 1, "expected only 1 buzzword diagnostic");
     assert_eq!(transitions, 1, "expected only 1 transition diagnostic");
 }
+
+#[test]
+fn indented_code_blocks_are_not_prose() {
+    let report = analyze("Intro sentence.\n\n    As an AI language model, I cannot help.\n");
+    assert_not(&report, Category::Template);
+}
+
+#[test]
+fn html_blocks_are_not_prose() {
+    let report = analyze("<div>\nWe will leverage the system.\n</div>\n\nWe ship tomorrow.");
+    assert_not(&report, Category::Buzzword);
+}
+
+#[test]
+fn relative_link_destinations_are_ignored() {
+    let report = analyze("Read the [notes](docs/deep-dive.md) before you start.");
+    assert_not(&report, Category::Buzzword);
+}
+
+#[test]
+fn setext_headings_are_checked() {
+    let mut cfg = Config::default();
+    cfg.profile_defaults.forbid_rhetorical_headings = true;
+    let report = analyze_with(
+        cfg,
+        "Why does this matter?\n---------------------\n\nIt ships.",
+    );
+    assert_has(&report, Category::Structure);
+}
+
+#[test]
+fn nested_lists_are_counted_per_level() {
+    let mut cfg = Config::default();
+    cfg.profile_defaults.max_bullet_items = Some(3);
+    let text = "- one\n  - a\n  - b\n- two\n  - c\n- three\n";
+    let report = analyze_with(cfg, text);
+    assert_not(&report, Category::Structure);
+}
+
+#[test]
+fn table_rows_do_not_merge_into_one_sentence() {
+    let mut cfg = Config::default();
+    cfg.profile_defaults.max_sentence_length = Some(8);
+    let text = "| Name | Notes |\n| --- | --- |\n| cache | Keeps hot entries in memory for reads. |\n| queue | Buffers writes until the flush interval. |\n";
+    let report = analyze_with(cfg, text);
+    assert_not(&report, Category::SentenceLength);
+}
+
+#[test]
+fn diagnostics_carry_rule_ids() {
+    let report = analyze("We will leverage the system.");
+    assert!(report
+        .diagnostics
+        .iter()
+        .any(|d| d.category == Category::Buzzword && d.rule == "buzzword/phrase"));
+}
+
+#[test]
+fn rule_ignore_suppresses_only_that_rule() {
+    let mut cfg = Config::default();
+    cfg.profile_defaults.forbid_rhetorical_headings = true;
+    cfg.profile_defaults.max_heading_depth = Some(1);
+    let text = "<!-- dwg:ignore structure/rhetorical-heading -->\n## Why does this matter?\n<!-- dwg:end-ignore -->\n\nIt ships.";
+    let report = analyze_with(cfg, text);
+    let rules: Vec<&str> = report.diagnostics.iter().map(|d| d.rule.as_str()).collect();
+    assert!(rules.contains(&"structure/max-heading-depth"), "{rules:?}");
+    assert!(
+        !rules.contains(&"structure/rhetorical-heading"),
+        "{rules:?}"
+    );
+}
+
+#[test]
+fn severity_overrides_prefer_rule_over_category() {
+    let mut cfg = Config::default();
+    cfg.severity_overrides
+        .insert("buzzword".into(), dwg_core::Severity::Hint);
+    cfg.severity_overrides
+        .insert("buzzword/phrase".into(), dwg_core::Severity::Error);
+    let report = analyze_with(cfg, "We will leverage the system.");
+    let buzzword = report
+        .diagnostics
+        .iter()
+        .find(|d| d.category == Category::Buzzword)
+        .expect("buzzword diagnostic");
+    assert_eq!(buzzword.severity, dwg_core::Severity::Error);
+}
+
+#[test]
+fn unknown_severity_override_is_rejected() {
+    let mut cfg = Config::default();
+    cfg.severity_overrides
+        .insert("tone/not-a-rule".into(), dwg_core::Severity::Hint);
+    assert!(Analyzer::new(cfg).is_err());
+}
+
+#[test]
+fn profile_severity_overrides_apply_after_global_ones() {
+    let cfg = config_from_yaml(
+        r#"
+severity_overrides:
+  buzzword/phrase: error
+profiles:
+  - name: notes
+    rules:
+      severity_overrides:
+        buzzword: information
+  - name: draft
+    extends: notes
+"#,
+    );
+    let analyzer = Analyzer::new(cfg).unwrap();
+    let severity = |profile: &str| {
+        analyzer
+            .analyze_profile_name("We will leverage the system.", profile)
+            .unwrap()
+            .diagnostics
+            .into_iter()
+            .find(|d| d.category == Category::Buzzword)
+            .expect("buzzword diagnostic")
+            .severity
+    };
+    assert_eq!(severity("default"), dwg_core::Severity::Error);
+    assert_eq!(severity("notes"), dwg_core::Severity::Information);
+    assert_eq!(severity("draft"), dwg_core::Severity::Information);
+
+    let bad = config_from_yaml(
+        r#"
+profiles:
+  - name: notes
+    rules:
+      severity_overrides:
+        custom/missing: hint
+"#,
+    );
+    assert!(Analyzer::new(bad).is_err());
+}
+
+#[test]
+fn weighted_density_discounts_soft_severities() {
+    let mut cfg = Config::default();
+    cfg.severity_overrides
+        .insert("buzzword".into(), dwg_core::Severity::Hint);
+    let report = analyze_with(cfg, "We will leverage the system.");
+    let weights = dwg_core::SeverityWeights::default();
+    let hints = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.severity == dwg_core::Severity::Hint)
+        .count();
+    assert!(hints > 0);
+    assert_eq!(
+        report.weighted_count(&weights),
+        report.diagnostics.len() as f32 - hints as f32 * 0.5
+    );
+    assert!(report.weighted_density_per_100_words(&weights) < report.density_per_100_words());
+
+    let flat = dwg_core::SeverityWeights {
+        hint: 1.0,
+        information: 1.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        report.weighted_density_per_100_words(&flat),
+        report.density_per_100_words()
+    );
+}
+
+#[test]
+fn readability_targets_flag_dense_sections() {
+    let cfg = config_from_yaml(
+        r#"
+profiles:
+  - name: guide
+    rules:
+      max_grade_level: 8
+"#,
+    );
+    let analyzer = Analyzer::new(cfg).unwrap();
+    let text = "\
+# Getting started
+
+You run the tool. It reads the files. It prints what it finds. You fix the text. Then you run it again.
+
+# Internals
+
+Configuration resolution necessitates hierarchical amalgamation of organizational preferences. Deterministic evaluation guarantees reproducible diagnostic classification. Comprehensive documentation accompanies sophisticated architectural abstractions. Sophisticated tokenization facilitates multilingual morphological normalization. Parallelization considerations influence implementation decisions considerably.
+";
+    let report = analyzer.analyze_profile_name(text, "guide").unwrap();
+    assert_eq!(report.metrics.sentences, 10);
+    assert!(report.metrics.flesch_kincaid_grade > 8.0);
+    let flagged: Vec<(usize, &str)> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.category == Category::Readability)
+        .map(|d| (d.location.line, d.message.as_str()))
+        .collect();
+    assert_eq!(flagged.len(), 2, "{flagged:?}");
+    assert!(flagged[0].1.starts_with("Document reads at grade level"));
+    assert_eq!(flagged[1].0, 5);
+    assert!(flagged[1].1.starts_with("Section `Internals`"));
+
+    let default = analyzer.analyze(text);
+    assert!(default
+        .diagnostics
+        .iter()
+        .all(|d| d.category != Category::Readability));
+}
+
+#[test]
+fn statistical_signals_attach_to_the_section_heading() {
+    let text = "\
+# Overview
+
+Short intro here. It varies a lot in length, as real prose tends to do when someone writes it.
+
+## Process
+
+The team reviews each change. The tool checks every file. The build runs all tests. The script packs the output. The server stores each report. The client shows the result. The user reads the summary. The bot posts a comment.
+";
+    let report = analyze(text);
+    let uniform: Vec<_> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "tone/uniform-sentence-length")
+        .collect();
+    assert_eq!(uniform.len(), 1, "{:#?}", report.diagnostics);
+    assert_eq!(uniform[0].location.line, 5);
+    assert_eq!(uniform[0].snippet, "## Process");
+    assert_eq!(&text[uniform[0].span.0..uniform[0].span.1], "## Process");
+
+    let paths: Vec<Vec<String>> = report
+        .sections
+        .iter()
+        .map(|s| s.heading_path.clone())
+        .collect();
+    assert_eq!(paths, [vec!["Overview"], vec!["Overview", "Process"]]);
+    let process = &report.sections[1];
+    assert_eq!(process.level, 2);
+    assert_eq!(process.location.line, 5);
+    assert_eq!(process.metrics.sentences, 8);
+    assert_eq!(process.category_counts.get(&Category::Tone), Some(&1));
+    assert!(process.density_per_100_words > 0.0);
+    assert_eq!(
+        report.sections.iter().map(|s| s.word_count).sum::<usize>(),
+        report.word_count
+    );
+}
+
+#[test]
+fn document_level_signals_attach_to_the_title() {
+    let uniform = |report: &DocumentReport| {
+        report
+            .diagnostics
+            .iter()
+            .find(|d| d.rule == "tone/uniform-sentence-length")
+            .cloned()
+            .unwrap_or_else(|| panic!("{:#?}", report.diagnostics))
+    };
+    let text = "\
+# Release notes
+
+## Build
+
+The team reviews each change. The tool checks every file. The build runs all tests. The script packs the output.
+
+## Deploy
+
+The server stores each report. The client shows the result. The user reads the summary. The bot posts a comment.
+";
+    let diag = uniform(&analyze(text));
+    assert_eq!(diag.location.line, 1);
+    assert_eq!(diag.snippet, "# Release notes");
+    assert_eq!(&text[diag.span.0..diag.span.1], "# Release notes");
+
+    let text = "The team reviews each change. The tool checks every file. The build runs all tests. The script packs the output.\nThe server stores each report. The client shows the result. The user reads the summary. The bot posts a comment.\n";
+    let diag = uniform(&analyze(text));
+    assert_eq!(diag.location.line, 1);
+    assert_eq!(diag.snippet, text.lines().next().unwrap());
+}
+
+#[test]
+fn suggested_statistics_start_from_the_resolved_thresholds() {
+    let text = "\
+The team reviews each change. The tool checks every file. The build runs all tests. The script packs the output. The server stores each report. The client shows the result. The user reads the summary. The bot posts a comment.
+";
+    let analyzer = Analyzer::new(Config::default()).unwrap();
+    let samples = analyzer.statistics_samples(text);
+    let suggested = analyzer.suggest_statistics(&samples);
+    // The check flags a CV strictly below the limit.
+    assert!(suggested.uniform_max_cv.unwrap() <= samples[0].sentence_length_cv);
+    assert!(suggested.uniform_max_cv.unwrap() < 0.2);
+    assert_eq!(suggested.uniform_min_sentences, Some(8));
+    assert_eq!(suggested.triad_min_headings, Some(2));
+
+    let analyzer = Analyzer::new(config_from_yaml(
+        r#"
+profile_defaults:
+  statistics:
+    uniform_min_sentences: 12
+    uniform_max_cv: 0.15
+    opening_min_count: 3
+"#,
+    ))
+    .unwrap();
+    let suggested = analyzer.suggest_statistics(&analyzer.statistics_samples(text));
+    assert_eq!(suggested.uniform_max_cv, Some(0.15));
+    assert_eq!(suggested.uniform_min_sentences, Some(12));
+    assert_eq!(suggested.opening_min_count, Some(3));
+}
+
+#[test]
+fn profile_statistics_thresholds_are_inherited() {
+    let text = "\
+The team reviews each change. The tool checks every file. The build runs all tests. The script packs the output. The server stores each report. The client shows the result. The user reads the summary. The bot posts a comment.
+";
+    let cfg = config_from_yaml(
+        r#"
+profiles:
+  - name: reference
+    rules:
+      statistics:
+        uniform_max_cv: 0.0
+  - name: api
+    extends: reference
+  - name: strict
+    extends: reference
+    rules:
+      statistics:
+        uniform_max_cv: 0.5
+        uniform_min_sentences: 20
+"#,
+    );
+    let analyzer = Analyzer::new(cfg).unwrap();
+    let uniform = |profile: &str| {
+        analyzer
+            .analyze_profile_name(text, profile)
+            .unwrap()
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == "tone/uniform-sentence-length")
+    };
+    assert!(uniform("default"));
+    assert!(!uniform("reference"));
+    assert!(!uniform("api"));
+    assert!(!uniform("strict"));
+
+    let samples = analyzer.statistics_samples(text);
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].uniform_sentences, 8);
+    assert!(samples[0].sentence_length_cv < 0.2);
+    assert_eq!(samples[0].top_opening.as_deref(), Some("the bot"));
+    assert_eq!(samples[0].top_opening_count, 1);
+    assert_eq!(samples[0].opening_sentences, 8);
+}
+
+#[test]
+fn glossary_prefers_terms_and_keeps_variants_consistent() {
+    let cfg = config_from_yaml(
+        r#"
+profiles:
+  - name: docs
+glossary:
+  terms:
+    - preferred: sign in
+      variants: [login, log in]
+      part_of_speech: verb
+    - preferred: ToneGuard
+      case: exact
+    - preferred: workspace
+      variants: [project]
+      profiles: [docs]
+    - variants: [email, e-mail]
+"#,
+    );
+    let analyzer = Analyzer::new(cfg).unwrap();
+    let text = "Login to your project. Open the login page. Install toneguard and read `toneguard --help`. Send an email, then an email, then an e-mail.\n";
+    let terms = |profile: &str| -> Vec<(String, String)> {
+        analyzer
+            .analyze_profile_name(text, profile)
+            .unwrap()
+            .diagnostics
+            .into_iter()
+            .filter(|d| d.category == Category::Terminology)
+            .map(|d| (d.rule, d.snippet))
+            .collect()
+    };
+    let discouraged = |snippet: &str| {
+        (
+            "terminology/discouraged-term".to_string(),
+            snippet.to_string(),
+        )
+    };
+    assert_eq!(
+        terms("default"),
+        [
+            discouraged("Login"),
+            discouraged("toneguard"),
+            ("terminology/inconsistent-term".into(), "e-mail".into()),
+        ]
+    );
+    assert!(terms("docs").contains(&discouraged("project")));
+
+    let report = analyzer.analyze_profile_name(text, "docs").unwrap();
+    let fixed = dwg_core::apply_fixes(text, &report.diagnostics, false).text;
+    assert!(fixed.starts_with("Login to your workspace. Open the login page. Install ToneGuard"));
+    let fixed = dwg_core::apply_fixes(text, &report.diagnostics, true).text;
+    assert!(fixed.starts_with("Sign in to your workspace."));
+    assert!(fixed.ends_with("then an email.\n"));
+
+    let report = analyzer.analyze("Send an e-mail or an email.\n");
+    let tie = report
+        .diagnostics
+        .iter()
+        .find(|d| d.rule == "terminology/inconsistent-term")
+        .unwrap();
+    assert_eq!(tie.snippet, "email");
+    assert!(tie
+        .message
+        .ends_with("so this follows the first one used, `e-mail`."));
+}
+
+#[test]
+fn spelling_flags_unknown_words_outside_code() {
+    let cfg = config_from_yaml(
+        r#"
+whitelist:
+  allowed_typos: [teh]
+spelling:
+  words: [frobnicate]
+"#,
+    );
+    let analyzer = Analyzer::new(cfg).unwrap();
+    let text = "# Setup\n\nWe recieve the colour config and frobnicate it with teh `recieve_all` helper, see https://example.com/recieve or my_var and MyType. Kubernetes runs it.\n\n```\nrecieve\n```\n";
+    let report = analyzer.analyze(text);
+    let spelling: Vec<_> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.category == Category::Spelling)
+        .collect();
+    assert_eq!(spelling.len(), 1, "{spelling:#?}");
+    assert_eq!(spelling[0].rule, "spelling/unknown-word");
+    assert_eq!(spelling[0].snippet, "recieve");
+    assert_eq!(&text[spelling[0].span.0..spelling[0].span.1], "recieve");
+    assert!(spelling[0]
+        .suggestion
+        .as_deref()
+        .is_some_and(|s| s.contains("`receive`")));
+    let fixed = dwg_core::apply_fixes(text, &report.diagnostics, true).text;
+    assert!(fixed.contains("We receive the colour"));
+
+    let german = analyzer.analyze("---\nlang: de\n---\nWir prüfen die Rechtschreibung.\n");
+    assert!(german
+        .diagnostics
+        .iter()
+        .all(|d| d.category != Category::Spelling));
+}
+
+#[test]
+fn links_report_missing_files_and_anchors_with_nearest_fixes() {
+    use dwg_core::links::{LinkChecker, LinkConfig, LinkKind};
+    use std::fs;
+
+    let root = std::env::temp_dir().join(format!("toneguard-links-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("docs/guide")).unwrap();
+    fs::write(
+        root.join("docs/guide/usage.md"),
+        "# Usage\n\n## Running the checks\n\n## Configuration\n",
+    )
+    .unwrap();
+    fs::write(root.join("docs/guide/installation.md"), "# Installation\n").unwrap();
+    let index = root.join("docs/index.md");
+    let text = "# Home\n\nRead [setup](guide/instalation.md), [usage](guide/usage.md#configuraton), [here](#home) and [there](#nowhere).\n\nSee [the site](https://example.com), [issues](../../issues) and [generated](api/index.md).\n\n<!-- dwg:ignore links -->\n[old](guide/old.md)\n";
+    fs::write(&index, text).unwrap();
+
+    let config = LinkConfig {
+        ignore: vec!["api/**".into()],
+        ..LinkConfig::default()
+    };
+    let checker = LinkChecker::new(&root, &config).unwrap();
+    let analyzer = Analyzer::new(Config::default()).unwrap();
+    let mut report = analyzer.analyze(text);
+    analyzer.check_links(&checker, &index, text, &mut report);
+    let links: Vec<(&str, &str)> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.category == Category::Links)
+        .map(|d| (d.rule.as_str(), d.snippet.as_str()))
+        .collect();
+    assert_eq!(
+        links,
+        [
+            ("links/broken-link", "guide/instalation.md"),
+            ("links/broken-anchor", "guide/usage.md#configuraton"),
+            ("links/broken-anchor", "#nowhere"),
+        ]
+    );
+    assert_eq!(report.category_counts[&Category::Links], 3);
+    assert!(report
+        .diagnostics
+        .iter()
+        .all(|d| d.rule != "directive/unused"));
+
+    let fixed = dwg_core::apply_fixes(text, &report.diagnostics, true).text;
+    assert!(fixed.contains("[setup](guide/installation.md)"));
+    assert!(fixed.contains("[usage](guide/usage.md#configuration)"));
+    assert!(fixed.contains("[there](#nowhere)"));
+
+    let graph = checker.graph(&[index.clone(), root.join("docs/guide/usage.md")]);
+    assert_eq!(graph.nodes.len(), 2);
+    assert_eq!(graph.external.len(), 1);
+    let edges: Vec<(Option<&str>, LinkKind, bool)> = graph
+        .edges
+        .iter()
+        .map(|edge| (edge.to.as_deref(), edge.kind, edge.resolved))
+        .collect();
+    assert_eq!(
+        edges,
+        [
+            (None, LinkKind::Doc, false),
+            (Some("docs/guide/usage.md"), LinkKind::Doc, false),
+            (None, LinkKind::Doc, false),
+        ]
+    );
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn frontmatter_overrides_profile_rules_and_disables() {
+    let cfg = config_from_yaml(
+        r#"
+profiles:
+  - name: changelog
+    rules:
+      max_sentence_length: 6
+document_types:
+  generated:
+    disable: [buzzword]
+"#,
+    );
+    let analyzer = Analyzer::new(cfg).unwrap();
+    let body = "We will leverage the system to ship the release on time today.";
+
+    let plain = analyzer.analyze(body);
+    assert_has(&plain, Category::Buzzword);
+    assert_not(&plain, Category::SentenceLength);
+
+    let text = format!(
+        "---\ntitle: Notes\ntoneguard:\n  profile: changelog\n  type: generated\n---\n\n{body}"
+    );
+    let report = analyzer.analyze_profile_name(&text, "default").unwrap();
+    assert_eq!(report.profile, "changelog");
+    assert_has(&report, Category::SentenceLength);
+    assert_not(&report, Category::Buzzword);
+
+    let text = format!(
+        "---\ntoneguard:\n  disable: [sentence-length]\n  rules:\n    max_sentence_length: 4\n    severity_overrides:\n      buzzword: error\n---\n\n{body}"
+    );
+    let report = analyzer.analyze(&text);
+    assert_not(&report, Category::SentenceLength);
+    let buzzword = report
+        .diagnostics
+        .iter()
+        .find(|d| d.category == Category::Buzzword)
+        .unwrap();
+    assert_eq!(buzzword.severity, dwg_core::Severity::Error);
+}
+
+#[test]
+fn invalid_frontmatter_overrides_are_reported_where_written() {
+    let analyzer = Analyzer::new(Config::default()).unwrap();
+    let text = "---\ntitle: Notes\ntoneguard:\n  profle: docs\n  type: generated\n  disable: [tone, nope]\n  rules:\n    max_sentence_length: long\n---\n\nPlain text.";
+    let report = analyzer.analyze(text);
+    let issues: Vec<(usize, usize, &str)> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "directive/invalid-frontmatter")
+        .map(|d| (d.location.line, d.location.column, d.message.as_str()))
+        .collect();
+    assert_eq!(issues.len(), 4, "{issues:#?}");
+    assert_eq!(issues[0].0, 4);
+    assert!(issues[0].2.contains("did you mean `profile`"));
+    assert_eq!((issues[1].0, issues[1].1), (5, 3));
+    assert!(issues[1].2.contains("unknown document type `generated`"));
+    assert!(issues[2].2.contains("unknown category or rule `nope`"));
+    assert_eq!(issues[3].0, 8);
+}
+
+#[test]
+fn frontmatter_overrides_that_do_not_compile_are_reported() {
+    let cfg = config_from_yaml(
+        r#"
+document_types:
+  generated:
+    rules:
+      forbidden_patterns: ["(unclosed"]
+"#,
+    );
+    let analyzer = Analyzer::new(cfg).unwrap();
+    let text = "---\ntitle: Notes\ntoneguard:\n  type: generated\n---\n\nPlain text.";
+    for _ in 0..2 {
+        let report = analyzer.analyze(text);
+        let issues: Vec<_> = report
+            .diagnostics
+            .iter()
+            .filter(|d| d.rule == "directive/invalid-frontmatter")
+            .collect();
+        assert_eq!(issues.len(), 1, "{issues:#?}");
+        assert_eq!(issues[0].location.line, 3);
+        assert!(issues[0].message.contains("unclosed group"));
+    }
+}
+
+#[test]
+fn directives_are_used_only_when_they_hide_a_finding() {
+    let analyzer = Analyzer::new(Config::default()).unwrap();
+    let unused = |text: &str| {
+        analyzer
+            .analyze(text)
+            .diagnostics
+            .iter()
+            .filter(|d| d.rule == "directive/unused")
+            .count()
+    };
+    assert_eq!(
+        unused("<!-- dwg:off -->\n## Plain heading\n\nPlain prose here.\n<!-- dwg:on -->\n"),
+        1
+    );
+    assert_eq!(
+        unused("<!-- dwg:off -->\nWe leverage synergy here.\n<!-- dwg:on -->\n"),
+        0
+    );
+    assert_eq!(
+        unused(
+            "<!-- dwg:ignore buzzword -->\nWe leverage synergy here.\n<!-- dwg:end-ignore -->\n"
+        ),
+        0
+    );
+}
+
+#[test]
+fn directives_report_unknown_names_unterminated_ignores_and_unused() {
+    let analyzer = Analyzer::new(Config::default()).unwrap();
+    let text = "We leverage synergy here. <!-- dwg:ignore-line -->\n\nPlain text here. <!-- dwg:ignore-line -->\n\n<!-- dwg:ignore buzzwrd -->\nPlain prose.\n<!-- dwg:end-ignore -->\n\n<!-- dwg:ignore tone -->\nMore plain prose.\n\nUse `<!-- dwg:off -->` to stop checks.\n";
+    let report = analyzer.analyze(text);
+    let directives: Vec<(&str, usize)> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.category == Category::Directive)
+        .map(|d| (d.rule.as_str(), d.location.line))
+        .collect();
+    assert_eq!(
+        directives,
+        [
+            ("directive/unused", 3),
+            ("directive/unknown-name", 5),
+            ("directive/unused", 5),
+            ("directive/unterminated-ignore", 9),
+            ("directive/unused", 9),
+        ]
+    );
+    let unknown = report
+        .diagnostics
+        .iter()
+        .find(|d| d.rule == "directive/unknown-name")
+        .unwrap();
+    assert!(unknown.message.contains("turns every check off"));
+    assert_eq!(
+        unknown.suggestion.as_deref(),
+        Some("Did you mean `buzzword`?")
+    );
+
+    let fixed = dwg_core::apply_fixes(text, &report.diagnostics, false).text;
+    assert_eq!(fixed, text);
+    let fixed = dwg_core::apply_fixes(text, &report.diagnostics, true).text;
+    assert!(fixed
+        .starts_with("We leverage synergy here. <!-- dwg:ignore-line -->\n\nPlain text here.\n"));
+    assert!(!fixed.contains("buzzwrd") && !fixed.contains("dwg:end-ignore"));
+    assert!(!fixed.contains("dwg:ignore tone"));
+    assert!(fixed.contains("`<!-- dwg:off -->`"));
+
+    // Each fix does what its rule asks rather than removing the directive.
+    let fix_text = |rule: &str| {
+        let diag = report.diagnostics.iter().find(|d| d.rule == rule).unwrap();
+        diag.fix
+            .as_ref()
+            .map(|fix| fix.edits[0].replacement.clone())
+    };
+    assert_eq!(
+        fix_text("directive/unknown-name").as_deref(),
+        Some("buzzword")
+    );
+    // The unused ignore is removed, not closed.
+    assert_eq!(fix_text("directive/unterminated-ignore"), None);
+
+    let text = "<!-- dwg:ignore buzzword, zzzzzz -->\nWe leverage synergy here.\n";
+    let report = analyzer.analyze(text);
+    let rules: Vec<&str> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.category == Category::Directive)
+        .map(|d| d.rule.as_str())
+        .collect();
+    assert_eq!(
+        rules,
+        ["directive/unknown-name", "directive/unterminated-ignore"]
+    );
+    assert!(report.diagnostics[0].fix.is_none());
+    let fixed = dwg_core::apply_fixes(text, &report.diagnostics, true).text;
+    assert_eq!(
+        fixed,
+        "<!-- dwg:ignore buzzword, zzzzzz -->\nWe leverage synergy here.\n\n<!-- dwg:end-ignore -->\n"
+    );
+}
+
+fn config_from_yaml(yaml: &str) -> Config {
+    serde_yaml::from_str(yaml).expect("valid config yaml")
+}
+
+const HOUSE_RULES: &str = r#"
+profiles:
+  - name: readme
+    globs: ["README.md"]
+custom_rules:
+  - id: no-simply
+    phrases: ["simply", "just"]
+    message: "Drop `$0`; it talks down to the reader."
+    replacement: ""
+  - id: product-name
+    pattern: '\btone ?(?P<rest>guard)\b'
+    allow: ["ToneGuard"]
+    severity: error
+    message: "Write ToneGuard, not `$0` (matched `${rest}`)."
+    replacement: ToneGuard
+    scope: paragraph
+  - id: readme-only
+    phrases: ["click here"]
+    profiles: [readme]
+"#;
+
+#[test]
+fn custom_rules_report_with_their_own_ids() {
+    let text = "You simply install Toneguard. ToneGuard is spelled right here.";
+    let report = analyze_with(config_from_yaml(HOUSE_RULES), text);
+    let custom: Vec<_> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.category == Category::Custom)
+        .collect();
+    assert_eq!(custom.len(), 2, "{custom:#?}");
+    let simply = custom
+        .iter()
+        .find(|d| d.rule == "custom/no-simply")
+        .unwrap();
+    assert_eq!(
+        simply.message,
+        "Drop `simply`; it talks down to the reader."
+    );
+    let name = custom
+        .iter()
+        .find(|d| d.rule == "custom/product-name")
+        .unwrap();
+    assert_eq!(name.severity, dwg_core::Severity::Error);
+    assert_eq!(
+        name.message,
+        "Write ToneGuard, not `Toneguard` (matched `guard`)."
+    );
+    assert_eq!(report.category_counts.get(&Category::Custom), Some(&2));
+}
+
+#[test]
+fn custom_rule_replacements_are_machine_applicable() {
+    let text = "You simply install tone guard.";
+    let report = analyze_with(config_from_yaml(HOUSE_RULES), text);
+    let outcome = dwg_core::apply_fixes(text, &report.diagnostics, false);
+    assert_eq!(outcome.text, "You install ToneGuard.");
+}
+
+#[test]
+fn custom_rules_respect_profiles_scopes_and_ignores() {
+    let analyzer = Analyzer::new(config_from_yaml(HOUSE_RULES)).unwrap();
+    let text = "Click here to start.\n\n- Toneguard in a list item.";
+    let rules = |profile: &str| -> Vec<String> {
+        analyzer
+            .analyze_profile_name(text, profile)
+            .unwrap()
+            .diagnostics
+            .into_iter()
+            .filter(|d| d.category == Category::Custom)
+            .map(|d| d.rule)
+            .collect()
+    };
+    // The product-name rule is paragraph-scoped, so the list item is skipped.
+    assert!(rules("default").is_empty());
+    assert_eq!(rules("readme"), vec!["custom/readme-only".to_string()]);
+
+    let ignored = "<!-- dwg:ignore custom/no-simply -->\nYou simply run it.";
+    let report = analyze_with(config_from_yaml(HOUSE_RULES), ignored);
+    assert_not(&report, Category::Custom);
+}
+
+#[test]
+fn invalid_custom_rules_are_rejected() {
+    let unknown_profile =
+        config_from_yaml("custom_rules:\n  - id: x\n    phrases: [simply]\n    profiles: [nope]\n");
+    assert!(Analyzer::new(unknown_profile).is_err());
+    let bad_regex = config_from_yaml("custom_rules:\n  - id: x\n    pattern: '('\n");
+    assert!(Analyzer::new(bad_regex).is_err());
+    let duplicate = config_from_yaml(
+        "custom_rules:\n  - id: x\n    phrases: [a]\n  - id: custom/x\n    phrases: [b]\n",
+    );
+    assert!(Analyzer::new(duplicate).is_err());
+    let unknown_override = config_from_yaml("severity_overrides:\n  custom/missing: hint\n");
+    assert!(Analyzer::new(unknown_override).is_err());
+}
@@ -4,14 +4,17 @@
 //! and provides real-time diagnostics, code actions, and configuration support.
 
 use std::collections::{HashMap, HashSet};
-use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::Context;
 use dashmap::DashMap;
 use dwg_core::{
-    parse_category, Analyzer, Category, Config, Diagnostic as CoreDiagnostic, Severity,
+    discovery::{self, ConfigTree},
+    docstrings,
+    links::LinkChecker,
+    rules::RuleSelector,
+    Analyzer, Config, Diagnostic as CoreDiagnostic, DocumentReport, Severity,
 };
 use serde_json::Value;
 use tokio::sync::RwLock;
@@ -25,29 +28,40 @@ struct DocumentState {
     version: i32,
 }
 
+/// Category and rule-ID filters from the client settings.
 #[derive(Clone, Default)]
 struct CategoryFilter {
-    only: HashSet<Category>,
-    enable: HashSet<Category>,
-    disable: HashSet<Category>,
+    only: HashSet<RuleSelector>,
+    enable: HashSet<RuleSelector>,
+    disable: HashSet<RuleSelector>,
 }
 
 impl CategoryFilter {
-    fn allows(&self, category: Category) -> bool {
+    fn allows(&self, diag: &CoreDiagnostic) -> bool {
+        let any = |set: &HashSet<RuleSelector>| {
+            set.iter().any(|sel| sel.matches(diag.category, &diag.rule))
+        };
         if !self.only.is_empty() {
-            return self.only.contains(&category);
+            return any(&self.only);
         }
         if !self.enable.is_empty() {
-            return !self.disable.contains(&category) || self.enable.contains(&category);
+            return !any(&self.disable) || any(&self.enable);
         }
-        !self.disable.contains(&category)
+        !any(&self.disable)
     }
 }
 
 /// ToneGuard Language Server backend.
 struct Backend {
     client: Client,
+    /// Analyzer for the workspace root config.
     analyzer: RwLock<Arc<Analyzer>>,
+    /// Root config plus `.toneguard.yml` overrides, once a workspace is open.
+    configs: RwLock<Option<Arc<ConfigTree>>>,
+    /// Link checkers by config layer fingerprint, so target anchors and the
+    /// file index are read once rather than on every pass. Cleared when the
+    /// config reloads or files change on disk.
+    link_checkers: RwLock<HashMap<String, Arc<LinkChecker>>>,
     documents: DashMap<Url, DocumentState>,
     workspace_root: RwLock<Option<PathBuf>>,
     config_path: RwLock<Option<PathBuf>>,
@@ -62,6 +76,8 @@ impl Backend {
         Self {
             client,
             analyzer: RwLock::new(Arc::new(analyzer)),
+            configs: RwLock::new(None),
+            link_checkers: RwLock::new(HashMap::new()),
             documents: DashMap::new(),
             workspace_root: RwLock::new(None),
             config_path: RwLock::new(None),
@@ -70,16 +86,6 @@ impl Backend {
         }
     }
 
-    fn load_config(path: &Path) -> anyhow::Result<Config> {
-        let text = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read {}", path.display()))?;
-        let value: serde_yaml::Value = serde_yaml::from_str(&text)
-            .with_context(|| format!("Failed to parse YAML {}", path.display()))?;
-        let cfg: Config = serde_yaml::from_value(value)
-            .with_context(|| format!("Invalid config structure in {}", path.display()))?;
-        Ok(cfg)
-    }
-
     async fn reload_analyzer(&self) -> anyhow::Result<()> {
         let workspace_root = self.workspace_root.read().await.clone();
         let Some(workspace_root) = workspace_root else {
@@ -89,14 +95,14 @@ impl Backend {
         let configured = self.config_path.read().await.clone();
         let resolved = configured.unwrap_or_else(|| workspace_root.join("layth-style.yml"));
 
-        let cfg = if resolved.exists() {
-            Self::load_config(&resolved)?
-        } else {
-            Config::default()
-        };
-
-        let analyzer = Analyzer::new(cfg).context("failed to create analyzer")?;
-        *self.analyzer.write().await = Arc::new(analyzer);
+        let tree = ConfigTree::open(&workspace_root, Some(&resolved))?;
+        let analyzer = tree
+            .root()?
+            .analyzer()
+            .context("failed to create analyzer")?;
+        *self.analyzer.write().await = analyzer;
+        *self.configs.write().await = Some(Arc::new(tree));
+        self.link_checkers.write().await.clear();
         *self.config_path.write().await = Some(resolved.clone());
 
         self.client
@@ -135,22 +141,113 @@ impl Backend {
         };
         let content = &doc.content;
 
-        let analyzer = self.analyzer.read().await.clone();
+        let analyzer = self.analyzer_for_uri(uri).await;
 
         let profile_name = self.profile_for_uri(&analyzer, uri).await;
-        let report = analyzer
-            .analyze_profile_name(content, &profile_name)
-            .unwrap_or_else(|_| analyzer.analyze(content));
+        let source_path = uri
+            .to_file_path()
+            .ok()
+            .filter(|path| docstrings::is_source_path(path));
+        let report = match source_path {
+            // Source files: lint the prose in doc comments and docstrings.
+            Some(path) => {
+                match docstrings::analyze_source(&analyzer, &path, content, &profile_name) {
+                    Ok(Some(report)) => report,
+                    _ => return vec![],
+                }
+            }
+            None => {
+                let mut report = analyzer
+                    .analyze_profile_name(content, &profile_name)
+                    .unwrap_or_else(|_| analyzer.analyze(content));
+                if let Ok(path) = uri.to_file_path() {
+                    self.check_links(&analyzer, &path, content, &mut report)
+                        .await;
+                }
+                report
+            }
+        };
 
         let filter = self.category_filter.read().await.clone();
         report
             .diagnostics
             .into_iter()
-            .filter(|d| filter.allows(d.category))
+            .filter(|d| filter.allows(d))
             .map(|d| self.to_lsp_diagnostic(&d, content))
             .collect()
     }
 
+    /// Add broken links and anchors to `report`, with the `links` settings
+    /// of the config layer for `path`. Target files are read from disk, so
+    /// links to unsaved edits in other documents are checked against the
+    /// saved copies.
+    async fn check_links(
+        &self,
+        analyzer: &Analyzer,
+        path: &Path,
+        content: &str,
+        report: &mut DocumentReport,
+    ) {
+        let Some(tree) = self.configs.read().await.clone() else {
+            return;
+        };
+        let Ok(layer) = tree.for_path(path) else {
+            return;
+        };
+        let config = &layer.config().links;
+        if !config.enabled {
+            return;
+        }
+        let cached = self
+            .link_checkers
+            .read()
+            .await
+            .get(layer.fingerprint())
+            .cloned();
+        let checker = match cached {
+            Some(checker) => checker,
+            None => match LinkChecker::new(tree.root_dir(), config) {
+                Ok(checker) => {
+                    let checker = Arc::new(checker);
+                    self.link_checkers
+                        .write()
+                        .await
+                        .insert(layer.fingerprint().to_string(), checker.clone());
+                    checker
+                }
+                Err(err) => {
+                    self.client
+                        .log_message(MessageType::ERROR, format!("Link check skipped: {err:#}"))
+                        .await;
+                    return;
+                }
+            },
+        };
+        analyzer.check_links(&checker, path, content, report);
+    }
+
+    /// The analyzer for `uri`: the root config with any `.toneguard.yml`
+    /// files between the workspace root and the document layered on.
+    async fn analyzer_for_uri(&self, uri: &Url) -> Arc<Analyzer> {
+        let root = self.analyzer.read().await.clone();
+        let tree = self.configs.read().await.clone();
+        let (Some(tree), Ok(path)) = (tree, uri.to_file_path()) else {
+            return root;
+        };
+        match tree.for_path(&path).and_then(|layer| layer.analyzer()) {
+            Ok(analyzer) => analyzer,
+            Err(err) => {
+                self.client
+                    .log_message(
+                        MessageType::ERROR,
+                        format!("Failed to load config for {}: {err:#}", path.display()),
+                    )
+                    .await;
+                root
+            }
+        }
+    }
+
     async fn profile_for_uri(&self, analyzer: &Analyzer, uri: &Url) -> String {
         if let Some(forced) = self.forced_profile.read().await.clone() {
             if !forced.trim().is_empty() {
@@ -172,6 +269,9 @@ impl Backend {
         let relative_str = relative
             .to_string_lossy()
             .replace(std::path::MAIN_SEPARATOR, "/");
+        if docstrings::is_source_path(&path) {
+            return docstrings::profile_for_path(analyzer, &relative_str).to_string();
+        }
         analyzer.profile_for_path(&relative_str).to_string()
     }
 
@@ -216,16 +316,35 @@ impl Backend {
             message.push_str(suggestion);
         }
 
+        // Carry fix edits as LSP ranges so code actions don't need to re-run the analyzer.
+        let data = diag.fix.as_ref().map(|fix| {
+            let edits: Vec<TextEdit> = fix
+                .edits
+                .iter()
+                .map(|edit| TextEdit {
+                    range: Range {
+                        start: Self::byte_to_position(text, edit.span.0),
+                        end: Self::byte_to_position(text, edit.span.1),
+                    },
+                    new_text: edit.replacement.clone(),
+                })
+                .collect();
+            serde_json::json!({
+                "applicability": fix.applicability.to_string(),
+                "edits": edits,
+            })
+        });
+
         tower_lsp::lsp_types::Diagnostic {
             range,
             severity: Some(severity),
-            code: Some(NumberOrString::String(format!("{:?}", diag.category))),
+            code: Some(NumberOrString::String(diag.rule.clone())),
             code_description: None,
             source: Some("toneguard".to_string()),
             message,
             related_information: None,
             tags: None,
-            data: None,
+            data,
         }
     }
 
@@ -276,8 +395,8 @@ impl LanguageServer for Backend {
             if let Some(Value::Array(items)) = map.get("onlyCategories") {
                 for item in items {
                     if let Some(name) = item.as_str() {
-                        if let Some(cat) = parse_category(name) {
-                            filter.only.insert(cat);
+                        if let Some(sel) = RuleSelector::parse(name) {
+                            filter.only.insert(sel);
                         }
                     }
                 }
@@ -285,8 +404,8 @@ impl LanguageServer for Backend {
             if let Some(Value::Array(items)) = map.get("enableCategories") {
                 for item in items {
                     if let Some(name) = item.as_str() {
-                        if let Some(cat) = parse_category(name) {
-                            filter.enable.insert(cat);
+                        if let Some(sel) = RuleSelector::parse(name) {
+                            filter.enable.insert(sel);
                         }
                     }
                 }
@@ -294,8 +413,8 @@ impl LanguageServer for Backend {
             if let Some(Value::Array(items)) = map.get("disableCategories") {
                 for item in items {
                     if let Some(name) = item.as_str() {
-                        if let Some(cat) = parse_category(name) {
-                            filter.disable.insert(cat);
+                        if let Some(sel) = RuleSelector::parse(name) {
+                            filter.disable.insert(sel);
                         }
                     }
                 }
@@ -386,7 +505,10 @@ impl LanguageServer for Backend {
         let is_config = saved_path
             .as_ref()
             .zip(config_path.as_ref())
-            .is_some_and(|(a, b)| a == b);
+            .is_some_and(|(a, b)| a == b)
+            || saved_path
+                .as_deref()
+                .is_some_and(discovery::is_local_config);
 
         if is_config {
             if let Err(err) = self.reload_analyzer().await {
@@ -402,6 +524,9 @@ impl LanguageServer for Backend {
                 self.publish_diagnostics(uri).await;
             }
         } else {
+            // The saved copy is what other documents' links are checked
+            // against.
+            self.link_checkers.write().await.clear();
             self.publish_diagnostics(uri).await;
         }
     }
@@ -433,8 +558,8 @@ impl LanguageServer for Backend {
             if let Some(Value::Array(items)) = map.get("onlyCategories") {
                 for item in items {
                     if let Some(name) = item.as_str() {
-                        if let Some(cat) = parse_category(name) {
-                            filter.only.insert(cat);
+                        if let Some(sel) = RuleSelector::parse(name) {
+                            filter.only.insert(sel);
                         }
                     }
                 }
@@ -442,8 +567,8 @@ impl LanguageServer for Backend {
             if let Some(Value::Array(items)) = map.get("enableCategories") {
                 for item in items {
                     if let Some(name) = item.as_str() {
-                        if let Some(cat) = parse_category(name) {
-                            filter.enable.insert(cat);
+                        if let Some(sel) = RuleSelector::parse(name) {
+                            filter.enable.insert(sel);
                         }
                     }
                 }
@@ -451,8 +576,8 @@ impl LanguageServer for Backend {
             if let Some(Value::Array(items)) = map.get("disableCategories") {
                 for item in items {
                     if let Some(name) = item.as_str() {
-                        if let Some(cat) = parse_category(name) {
-                            filter.disable.insert(cat);
+                        if let Some(sel) = RuleSelector::parse(name) {
+                            filter.disable.insert(sel);
                         }
                     }
                 }
@@ -475,10 +600,20 @@ impl LanguageServer for Backend {
     }
 
     async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        // Link targets and their anchors may have changed.
+        self.link_checkers.write().await.clear();
         let config_path = self.config_path.read().await.clone();
         let mut should_reload = false;
 
         for change in &params.changes {
+            if change
+                .uri
+                .to_file_path()
+                .is_ok_and(|path| discovery::is_local_config(&path))
+            {
+                should_reload = true;
+                break;
+            }
             if let Some(config_path) = &config_path {
                 if let Ok(path) = change.uri.to_file_path() {
                     if &path == config_path {
@@ -529,6 +664,55 @@ impl LanguageServer for Backend {
         // Add "Ignore this line" action for each diagnostic
         for diag in &params.context.diagnostics {
             if diag.source.as_deref() == Some("toneguard") {
+                let rule = match &diag.code {
+                    Some(NumberOrString::String(rule)) => rule.as_str(),
+                    _ => "",
+                };
+                // Directive diagnostics are about the comments themselves;
+                // ignoring them makes no sense.
+                let directive = rule.starts_with("directive/");
+                if let Some(edits) = diag
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.get("edits"))
+                    .and_then(|edits| serde_json::from_value::<Vec<TextEdit>>(edits.clone()).ok())
+                    .filter(|edits| !edits.is_empty())
+                {
+                    let safe = diag
+                        .data
+                        .as_ref()
+                        .and_then(|data| data.get("applicability"))
+                        .and_then(Value::as_str)
+                        == Some("safe");
+                    let replacement = edits[0].new_text.clone();
+                    let mut changes = HashMap::new();
+                    changes.insert(uri.clone(), edits);
+                    let title = match rule {
+                        "directive/unused" => "Remove this directive (ToneGuard)".to_string(),
+                        "directive/unterminated-ignore" => {
+                            "Close with `<!-- dwg:end-ignore -->` (ToneGuard)".to_string()
+                        }
+                        _ if replacement.is_empty() => "Apply ToneGuard fix".to_string(),
+                        _ => format!("Replace with `{replacement}` (ToneGuard)"),
+                    };
+                    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                        title,
+                        kind: Some(CodeActionKind::QUICKFIX),
+                        diagnostics: Some(vec![diag.clone()]),
+                        edit: Some(WorkspaceEdit {
+                            changes: Some(changes),
+                            ..Default::default()
+                        }),
+                        command: None,
+                        is_preferred: Some(safe),
+                        disabled: None,
+                        data: None,
+                    }));
+                }
+                if directive {
+                    continue;
+                }
+
                 // Create an "Ignore line" action
                 let line = diag.range.start.line;
                 let insert_char = self
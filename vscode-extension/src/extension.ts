@@ -2911,6 +2911,11 @@ const ALL_CATEGORIES = [
 // Common file types for ToneGuard
 const ALL_FILE_TYPES = ['.md', '.txt', '.rst', '.ts', '.tsx', '.js', '.jsx', '.py', '.rs'];
 
+// Languages whose doc comments and docstrings the server lints
+const DOC_COMMENT_LANGUAGES = [
+    'rust', 'typescript', 'typescriptreact', 'javascript', 'javascriptreact', 'python'
+];
+
 function extractIgnoreGlobsFromConfig(configPath: string): string[] {
     try {
         const text = fs.readFileSync(configPath, 'utf8');
@@ -5764,11 +5769,16 @@ export function activate(context: vscode.ExtensionContext): void {
         // For absolute paths, watch the specific file
         fileEvents.push(vscode.workspace.createFileSystemWatcher(configPath));
     }
+    // Per-directory overrides layered over the root config
+    fileEvents.push(vscode.workspace.createFileSystemWatcher('**/.toneguard.{yml,yaml}'));
 
     const clientOptions: LanguageClientOptions = {
         documentSelector: [
             { scheme: 'file', language: 'markdown' },
             { scheme: 'file', language: 'plaintext' },
+            ...(vscode.workspace.getConfiguration('dwg').get<boolean>('lintDocComments', true)
+                ? DOC_COMMENT_LANGUAGES.map((language) => ({ scheme: 'file', language }))
+                : []),
         ],
         initializationOptions: {
             configPath,